@@ -15,31 +15,32 @@ use {
     thiserror::Error,
 };
 
+const ADDRESS_LOOKUP_PROGRAM_ID: Pubkey = address_lookup_table::program::ID;
+const BPF_UPGRADEABLE_LOADER_PROGRAM_ID: Pubkey = solana_sdk::bpf_loader_upgradeable::ID;
+const CONFIG_PROGRAM_ID: Pubkey = solana_sdk::config::program::ID;
+const STAKE_PROGRAM_ID: Pubkey = stake::program::ID;
+const SYSTEM_PROGRAM_ID: Pubkey = system_program::ID;
+const SYSVAR_PROGRAM_ID: Pubkey = sysvar::ID;
+const VOTE_PROGRAM_ID: Pubkey = vote::program::ID;
+
 lazy_static! {
-    static ref ADDRESS_LOOKUP_PROGRAM_ID: Pubkey = address_lookup_table::program::id();
-    static ref BPF_UPGRADEABLE_LOADER_PROGRAM_ID: Pubkey = solana_sdk::bpf_loader_upgradeable::id();
-    static ref CONFIG_PROGRAM_ID: Pubkey = solana_config_program::id();
-    static ref STAKE_PROGRAM_ID: Pubkey = stake::program::id();
-    static ref SYSTEM_PROGRAM_ID: Pubkey = system_program::id();
-    static ref SYSVAR_PROGRAM_ID: Pubkey = sysvar::id();
-    static ref VOTE_PROGRAM_ID: Pubkey = vote::program::id();
     pub static ref PARSABLE_PROGRAM_IDS: HashMap<Pubkey, ParsableAccount> = {
         let mut m = HashMap::new();
         m.insert(
-            *ADDRESS_LOOKUP_PROGRAM_ID,
+            ADDRESS_LOOKUP_PROGRAM_ID,
             ParsableAccount::AddressLookupTable,
         );
         m.insert(
-            *BPF_UPGRADEABLE_LOADER_PROGRAM_ID,
+            BPF_UPGRADEABLE_LOADER_PROGRAM_ID,
             ParsableAccount::BpfUpgradeableLoader,
         );
-        m.insert(*CONFIG_PROGRAM_ID, ParsableAccount::Config);
-        m.insert(*SYSTEM_PROGRAM_ID, ParsableAccount::Nonce);
+        m.insert(CONFIG_PROGRAM_ID, ParsableAccount::Config);
+        m.insert(SYSTEM_PROGRAM_ID, ParsableAccount::Nonce);
         m.insert(spl_token::id(), ParsableAccount::SplToken);
         m.insert(spl_token_2022::id(), ParsableAccount::SplToken2022);
-        m.insert(*STAKE_PROGRAM_ID, ParsableAccount::Stake);
-        m.insert(*SYSVAR_PROGRAM_ID, ParsableAccount::Sysvar);
-        m.insert(*VOTE_PROGRAM_ID, ParsableAccount::Vote);
+        m.insert(STAKE_PROGRAM_ID, ParsableAccount::Stake);
+        m.insert(SYSVAR_PROGRAM_ID, ParsableAccount::Sysvar);
+        m.insert(VOTE_PROGRAM_ID, ParsableAccount::Vote);
         m
     };
 }
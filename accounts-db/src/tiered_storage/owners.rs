@@ -16,9 +16,7 @@ use {
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd)]
 pub struct OwnerOffset(pub u32);
 
-lazy_static! {
-    pub static ref OWNER_NO_OWNER: Pubkey = Pubkey::default();
-}
+pub const OWNER_NO_OWNER: Pubkey = Pubkey::new_from_array([0; 32]);
 
 /// Owner block holds a set of unique addresses of account owners,
 /// and an account meta has a owner_offset field for accessing
@@ -34,6 +34,11 @@ echo "Executing $testName"
 case $testName in
 test-stable)
   _ ci/intercept.sh cargo test --jobs "$JOBS" --all --tests --exclude solana-local-cluster ${V:+--verbose} -- --nocapture
+  # The sdk builds `solana-program` with `ed25519` and the std `ed25519-dalek`,
+  # so check the feature on its own as light clients use it
+  _ cargo test --jobs "$JOBS" --package solana-program --features ed25519 --lib ${V:+--verbose} -- --nocapture
+  # The borsh codec for stake instructions is opt-in, so its tests need the feature
+  _ cargo test --jobs "$JOBS" --package solana-program --features stake-borsh --lib ${V:+--verbose} -- stake::instruction --nocapture
   ;;
 test-stable-sbf)
   # Clear the C dependency files, if dependency moves these files are not regenerated
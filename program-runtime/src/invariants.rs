@@ -0,0 +1,389 @@
+//! Debug checks that a native program left its accounts in a consistent state.
+//!
+//! The transaction context rejects most illegal account modifications as they
+//! happen, and lamport conservation is checked when an instruction is popped.
+//! [`with_invariant_checks`] snapshots the accounts of an instruction before
+//! it is processed and checks the same rules again afterwards, per
+//! account, so that a processor which bypasses [`BorrowedAccount`] or a bug in
+//! those checks is caught at the instruction that caused it.
+//!
+//! Accounts the processor did not own may legitimately change when it invokes
+//! another program, so the ownership checks are skipped for an instruction
+//! that made an inner instruction. The invoked program's own modifications are
+//! checked by the transaction context as usual.
+//!
+//! [`BorrowedAccount`]: solana_sdk::transaction_context::BorrowedAccount
+
+use {
+    crate::invoke_context::InvokeContext,
+    solana_sdk::{
+        account::ReadableAccount,
+        instruction::InstructionError,
+        pubkey::Pubkey,
+        transaction_context::{
+            IndexOfAccount, InstructionAccount, InstructionContext, TransactionContext,
+        },
+    },
+    thiserror::Error,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum InvariantViolation {
+    #[error("lamports not conserved: {pre} before the instruction, {post} after")]
+    LamportsNotConserved { pre: u128, post: u128 },
+    #[error("read-only account {0} was modified")]
+    ReadonlyAccountModified(Pubkey),
+    #[error("owner of account {0} was changed by a program that did not own it")]
+    OwnerChangedByNonOwner(Pubkey),
+    #[error("account {0} not owned by the program had its data changed or lamports debited")]
+    ExternalAccountModified(Pubkey),
+    #[error("account {0} is missing or still borrowed")]
+    AccountUnavailable(Pubkey),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AccountSnapshot {
+    index_in_transaction: IndexOfAccount,
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+    is_writable: bool,
+}
+
+/// The state of the current instruction's accounts at some point in time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionSnapshot {
+    program_id: Pubkey,
+    accounts: Vec<AccountSnapshot>,
+    instruction_trace_length: usize,
+}
+
+impl InstructionSnapshot {
+    /// Snapshot the accounts of the current instruction, skipping duplicates.
+    pub fn new(transaction_context: &TransactionContext) -> Result<Self, InstructionError> {
+        Self::of_instruction(
+            transaction_context,
+            transaction_context.get_current_instruction_context()?,
+            transaction_context.get_instruction_trace_length(),
+        )
+    }
+
+    /// Snapshot the accounts of the instruction made of `program_indices` and
+    /// `instruction_accounts`, before it is processed.
+    pub fn before_instruction(
+        transaction_context: &TransactionContext,
+        program_indices: &[IndexOfAccount],
+        instruction_accounts: &[InstructionAccount],
+    ) -> Result<Self, InstructionError> {
+        let mut instruction_context = InstructionContext::default();
+        instruction_context.configure(program_indices, instruction_accounts, &[]);
+        // Processing adds the instruction itself to the trace
+        Self::of_instruction(
+            transaction_context,
+            &instruction_context,
+            transaction_context
+                .get_instruction_trace_length()
+                .saturating_add(1),
+        )
+    }
+
+    fn of_instruction(
+        transaction_context: &TransactionContext,
+        instruction_context: &InstructionContext,
+        instruction_trace_length: usize,
+    ) -> Result<Self, InstructionError> {
+        let program_id = *instruction_context.get_last_program_key(transaction_context)?;
+        let mut accounts = Vec::new();
+        for index_in_instruction in 0..instruction_context.get_number_of_instruction_accounts() {
+            if instruction_context
+                .is_instruction_account_duplicate(index_in_instruction)?
+                .is_some()
+            {
+                continue;
+            }
+            let account = instruction_context
+                .try_borrow_instruction_account(transaction_context, index_in_instruction)?;
+            accounts.push(AccountSnapshot {
+                index_in_transaction: account.get_index_in_transaction(),
+                key: *account.get_key(),
+                owner: *account.get_owner(),
+                lamports: account.get_lamports(),
+                data: account.get_data().to_vec(),
+                is_writable: account.is_writable(),
+            });
+        }
+        Ok(Self {
+            program_id,
+            accounts,
+            instruction_trace_length,
+        })
+    }
+
+    /// Compare the snapshot against the current state of the same accounts.
+    ///
+    /// Lamports must be conserved across the instruction's accounts. Read-only
+    /// accounts must be unchanged, and accounts the program did not own must
+    /// keep their owner and data and may only be credited, unless an inner
+    /// instruction was made since the snapshot was taken.
+    pub fn verify(
+        &self,
+        transaction_context: &TransactionContext,
+    ) -> Result<(), InvariantViolation> {
+        let made_inner_instruction =
+            transaction_context.get_instruction_trace_length() > self.instruction_trace_length;
+        let mut pre_lamports = 0u128;
+        let mut post_lamports = 0u128;
+        for pre in &self.accounts {
+            let post = transaction_context
+                .get_account_at_index(pre.index_in_transaction)
+                .ok()
+                .and_then(|account| account.try_borrow().ok())
+                .ok_or(InvariantViolation::AccountUnavailable(pre.key))?;
+            pre_lamports = pre_lamports.saturating_add(u128::from(pre.lamports));
+            post_lamports = post_lamports.saturating_add(u128::from(post.lamports()));
+
+            let owner_changed = post.owner() != &pre.owner;
+            let data_changed = post.data() != pre.data.as_slice();
+            if !pre.is_writable
+                && (owner_changed || data_changed || post.lamports() != pre.lamports)
+            {
+                return Err(InvariantViolation::ReadonlyAccountModified(pre.key));
+            }
+            if pre.owner != self.program_id && !made_inner_instruction {
+                if owner_changed {
+                    return Err(InvariantViolation::OwnerChangedByNonOwner(pre.key));
+                }
+                if data_changed || post.lamports() < pre.lamports {
+                    return Err(InvariantViolation::ExternalAccountModified(pre.key));
+                }
+            }
+        }
+        if pre_lamports != post_lamports {
+            return Err(InvariantViolation::LamportsNotConserved {
+                pre: pre_lamports,
+                post: post_lamports,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Run `process_instruction`, which processes the instruction made of
+/// `program_indices` and `instruction_accounts`, and, in debug builds, panic
+/// if it succeeds but leaves the instruction's accounts in a state that
+/// violates the rules checked by [`InstructionSnapshot::verify`].
+///
+/// Release builds run `process_instruction` without taking a snapshot.
+pub fn with_invariant_checks<F>(
+    invoke_context: &mut InvokeContext,
+    program_indices: &[IndexOfAccount],
+    instruction_accounts: &[InstructionAccount],
+    process_instruction: F,
+) -> Result<(), InstructionError>
+where
+    F: FnOnce(&mut InvokeContext) -> Result<(), InstructionError>,
+{
+    if !cfg!(debug_assertions) {
+        return process_instruction(invoke_context);
+    }
+    let snapshot = InstructionSnapshot::before_instruction(
+        invoke_context.transaction_context,
+        program_indices,
+        instruction_accounts,
+    )?;
+    let result = process_instruction(invoke_context);
+    if result.is_ok() {
+        if let Err(violation) = snapshot.verify(invoke_context.transaction_context) {
+            panic!("instruction invariant violated: {violation}");
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            declare_process_instruction, invoke_context::mock_process_instruction,
+            with_mock_invoke_context,
+        },
+        solana_sdk::{
+            account::{AccountSharedData, WritableAccount},
+            instruction::AccountMeta,
+            native_loader,
+        },
+    };
+
+    declare_process_instruction!(MockBypassingBuiltin, 1, |invoke_context| {
+        // Change an account the program does not own without going
+        // through `BorrowedAccount`
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+        let index_in_transaction =
+            instruction_context.get_index_of_instruction_account_in_transaction(0)?;
+        transaction_context
+            .get_account_at_index(index_in_transaction)?
+            .borrow_mut()
+            .set_data_from_slice(&[1]);
+        Ok(())
+    });
+
+    fn instruction_account(index: IndexOfAccount, is_writable: bool) -> InstructionAccount {
+        InstructionAccount {
+            index_in_transaction: index,
+            index_in_caller: index,
+            index_in_callee: index.saturating_sub(1),
+            is_signer: false,
+            is_writable,
+        }
+    }
+
+    #[test]
+    fn test_with_invariant_checks() {
+        let program_id = Pubkey::new_unique();
+        let other_program_id = Pubkey::new_unique();
+        let transaction_accounts = vec![
+            (
+                program_id,
+                AccountSharedData::new(1, 0, &native_loader::id()),
+            ),
+            (
+                Pubkey::new_unique(),
+                AccountSharedData::new(10, 1, &program_id),
+            ),
+            (
+                Pubkey::new_unique(),
+                AccountSharedData::new(10, 1, &other_program_id),
+            ),
+            (
+                Pubkey::new_unique(),
+                AccountSharedData::new(10, 1, &program_id),
+            ),
+        ];
+        let program_indices = [0];
+        let instruction_accounts = [
+            instruction_account(1, true),
+            instruction_account(2, true),
+            instruction_account(3, false),
+        ];
+        with_mock_invoke_context!(invoke_context, transaction_context, transaction_accounts);
+        invoke_context
+            .transaction_context
+            .get_next_instruction_context()
+            .unwrap()
+            .configure(&program_indices, &instruction_accounts, &[]);
+        invoke_context.push().unwrap();
+
+        // A transfer from an owned account to an external one is fine
+        with_invariant_checks(
+            &mut invoke_context,
+            &program_indices,
+            &instruction_accounts,
+            |invoke_context| {
+                let transaction_context = &invoke_context.transaction_context;
+                let instruction_context = transaction_context.get_current_instruction_context()?;
+                instruction_context
+                    .try_borrow_instruction_account(transaction_context, 0)?
+                    .checked_sub_lamports(5)?;
+                instruction_context
+                    .try_borrow_instruction_account(transaction_context, 1)?
+                    .checked_add_lamports(5)
+            },
+        )
+        .unwrap();
+
+        // Errors are passed through without checking
+        assert_eq!(
+            with_invariant_checks(
+                &mut invoke_context,
+                &program_indices,
+                &instruction_accounts,
+                |_invoke_context| Err(InstructionError::InvalidArgument),
+            ),
+            Err(InstructionError::InvalidArgument)
+        );
+
+        // Modifications that bypass `BorrowedAccount` are caught
+        let modify_account = |index_in_transaction: IndexOfAccount,
+                              modify: fn(&mut AccountSharedData)|
+         -> Result<(), InvariantViolation> {
+            let snapshot = InstructionSnapshot::new(invoke_context.transaction_context).unwrap();
+            let account = invoke_context
+                .transaction_context
+                .get_account_at_index(index_in_transaction)
+                .unwrap();
+            let original = account.borrow().clone();
+            modify(&mut account.borrow_mut());
+            let result = snapshot.verify(invoke_context.transaction_context);
+            *account.borrow_mut() = original;
+            result
+        };
+        let account_key = |index_in_transaction| {
+            *invoke_context
+                .transaction_context
+                .get_key_of_account_at_index(index_in_transaction)
+                .unwrap()
+        };
+        assert_eq!(
+            modify_account(1, |account| account.set_lamports(6)),
+            Err(InvariantViolation::LamportsNotConserved { pre: 30, post: 31 })
+        );
+        assert_eq!(
+            modify_account(2, |account| account.set_data_from_slice(&[1])),
+            Err(InvariantViolation::ExternalAccountModified(account_key(2)))
+        );
+        assert_eq!(
+            modify_account(2, |account| account.set_owner(Pubkey::new_unique())),
+            Err(InvariantViolation::OwnerChangedByNonOwner(account_key(2)))
+        );
+        assert_eq!(
+            modify_account(3, |account| account.set_data_from_slice(&[1])),
+            Err(InvariantViolation::ReadonlyAccountModified(account_key(3)))
+        );
+        assert_eq!(
+            modify_account(1, |account| account.set_data_from_slice(&[1])),
+            Ok(())
+        );
+
+        // An external account may change in an inner instruction
+        let snapshot = InstructionSnapshot::new(invoke_context.transaction_context).unwrap();
+        invoke_context
+            .transaction_context
+            .get_next_instruction_context()
+            .unwrap()
+            .configure(&[0], &[instruction_account(2, true)], &[]);
+        invoke_context.push().unwrap();
+        invoke_context.pop().unwrap();
+        let account = invoke_context
+            .transaction_context
+            .get_account_at_index(2)
+            .unwrap();
+        account.borrow_mut().set_data_from_slice(&[1]);
+        assert_eq!(snapshot.verify(invoke_context.transaction_context), Ok(()));
+
+        invoke_context.pop().unwrap();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "instruction invariant violated")]
+    fn test_mock_process_instruction_checks_invariants() {
+        let account_key = Pubkey::new_unique();
+        mock_process_instruction(
+            &Pubkey::new_unique(),
+            Vec::new(),
+            &[],
+            vec![(
+                account_key,
+                AccountSharedData::new(10, 1, &Pubkey::new_unique()),
+            )],
+            vec![AccountMeta::new(account_key, false)],
+            Ok(()),
+            MockBypassingBuiltin::vm,
+            |_invoke_context| {},
+            |_invoke_context| {},
+        );
+    }
+}
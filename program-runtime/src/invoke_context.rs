@@ -2,6 +2,7 @@ use {
     crate::{
         compute_budget::ComputeBudget,
         ic_msg,
+        invariants::with_invariant_checks,
         loaded_programs::{LoadedProgram, LoadedProgramType, LoadedProgramsForTxBatch},
         log_collector::LogCollector,
         stable_log,
@@ -676,16 +677,100 @@ macro_rules! with_mock_invoke_context {
 }
 
 pub fn mock_process_instruction<F: FnMut(&mut InvokeContext), G: FnMut(&mut InvokeContext)>(
+    loader_id: &Pubkey,
+    program_indices: Vec<IndexOfAccount>,
+    instruction_data: &[u8],
+    transaction_accounts: Vec<TransactionAccount>,
+    instruction_account_metas: Vec<AccountMeta>,
+    expected_result: Result<(), InstructionError>,
+    builtin_function: BuiltinFunctionWithContext,
+    pre_adjustments: F,
+    post_adjustments: G,
+) -> Vec<AccountSharedData> {
+    let (result, transaction_accounts) = mock_process_instruction_with_result(
+        loader_id,
+        program_indices,
+        instruction_data,
+        transaction_accounts,
+        instruction_account_metas,
+        builtin_function,
+        pre_adjustments,
+        post_adjustments,
+    );
+    assert_eq!(result, expected_result);
+    transaction_accounts
+}
+
+/// Like [`mock_process_instruction`], but returns the result of the
+/// instruction instead of asserting on it, for callers such as fuzzers that
+/// cannot know the expected outcome up front.
+pub fn mock_process_instruction_with_result<
+    F: FnMut(&mut InvokeContext),
+    G: FnMut(&mut InvokeContext),
+>(
+    loader_id: &Pubkey,
+    program_indices: Vec<IndexOfAccount>,
+    instruction_data: &[u8],
+    transaction_accounts: Vec<TransactionAccount>,
+    instruction_account_metas: Vec<AccountMeta>,
+    builtin_function: BuiltinFunctionWithContext,
+    pre_adjustments: F,
+    post_adjustments: G,
+) -> (Result<(), InstructionError>, Vec<AccountSharedData>) {
+    let (result, transaction_accounts, _metering) = mock_process_instruction_with_metering(
+        loader_id,
+        program_indices,
+        instruction_data,
+        transaction_accounts,
+        instruction_account_metas,
+        builtin_function,
+        pre_adjustments,
+        post_adjustments,
+    );
+    (result, transaction_accounts)
+}
+
+/// The cost of processing an instruction, as measured by
+/// [`mock_process_instruction_with_metering`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct InstructionMetering {
+    /// Compute units charged, including those of any syscalls such as hashing
+    pub compute_units_consumed: u64,
+    /// Bytes of instruction data handed to the program to deserialize
+    pub instruction_data_len: usize,
+    /// Bytes of data in the instruction's accounts, each counted once
+    pub account_data_len: usize,
+    /// Bytes of data in the instruction's accounts whose data was modified,
+    /// after processing
+    pub account_data_written: usize,
+    /// Messages logged while processing
+    pub logs: Vec<String>,
+}
+
+/// Like [`mock_process_instruction_with_result`], but also measures what
+/// processing the instruction cost, so that tests can catch cost regressions
+/// without a validator.
+///
+/// In debug builds the program runs under [`with_invariant_checks`], so every
+/// test built on these helpers panics if a successful instruction leaves its
+/// accounts in a state the runtime would reject.
+pub fn mock_process_instruction_with_metering<
+    F: FnMut(&mut InvokeContext),
+    G: FnMut(&mut InvokeContext),
+>(
     loader_id: &Pubkey,
     mut program_indices: Vec<IndexOfAccount>,
     instruction_data: &[u8],
     mut transaction_accounts: Vec<TransactionAccount>,
     instruction_account_metas: Vec<AccountMeta>,
-    expected_result: Result<(), InstructionError>,
     builtin_function: BuiltinFunctionWithContext,
     mut pre_adjustments: F,
     mut post_adjustments: G,
-) -> Vec<AccountSharedData> {
+) -> (
+    Result<(), InstructionError>,
+    Vec<AccountSharedData>,
+    InstructionMetering,
+) {
     let mut instruction_accounts: Vec<InstructionAccount> =
         Vec::with_capacity(instruction_account_metas.len());
     for (instruction_account_index, account_meta) in instruction_account_metas.iter().enumerate() {
@@ -710,6 +795,17 @@ pub fn mock_process_instruction<F: FnMut(&mut InvokeContext), G: FnMut(&mut Invo
             is_writable: account_meta.is_writable,
         });
     }
+    // Accounts missing from the transaction are left out
+    let mut pre_account_data: Vec<(usize, Vec<u8>)> = instruction_accounts
+        .iter()
+        .map(|instruction_account| instruction_account.index_in_transaction as usize)
+        .filter_map(|index| {
+            let (_key, account) = transaction_accounts.get(index)?;
+            Some((index, account.data().to_vec()))
+        })
+        .collect();
+    pre_account_data.sort_by_key(|(index, _data)| *index);
+    pre_account_data.dedup_by_key(|(index, _data)| *index);
     program_indices.insert(0, transaction_accounts.len() as IndexOfAccount);
     let processor_account = AccountSharedData::new(0, 0, &native_loader::id());
     transaction_accounts.push((*loader_id, processor_account));
@@ -721,18 +817,45 @@ pub fn mock_process_instruction<F: FnMut(&mut InvokeContext), G: FnMut(&mut Invo
     );
     invoke_context.programs_loaded_for_tx_batch = &programs_loaded_for_tx_batch;
     pre_adjustments(&mut invoke_context);
-    let result = invoke_context.process_instruction(
-        instruction_data,
-        &instruction_accounts,
+    let mut compute_units_consumed = 0;
+    let result = with_invariant_checks(
+        &mut invoke_context,
         &program_indices,
-        &mut 0,
-        &mut ExecuteTimings::default(),
+        &instruction_accounts,
+        |invoke_context| {
+            invoke_context.process_instruction(
+                instruction_data,
+                &instruction_accounts,
+                &program_indices,
+                &mut compute_units_consumed,
+                &mut ExecuteTimings::default(),
+            )
+        },
     );
-    assert_eq!(result, expected_result);
     post_adjustments(&mut invoke_context);
+    let logs = invoke_context
+        .get_log_collector()
+        .map(|log_collector| log_collector.borrow().get_recorded_content().to_vec())
+        .unwrap_or_default();
     let mut transaction_accounts = transaction_context.deconstruct_without_keys().unwrap();
     transaction_accounts.pop();
-    transaction_accounts
+    let metering = InstructionMetering {
+        compute_units_consumed,
+        instruction_data_len: instruction_data.len(),
+        account_data_len: pre_account_data
+            .iter()
+            .map(|(_index, data)| data.len())
+            .sum(),
+        account_data_written: pre_account_data
+            .iter()
+            .filter_map(|(index, pre_data)| {
+                let post_data = transaction_accounts.get(*index)?.data();
+                (post_data != pre_data.as_slice()).then_some(post_data.len())
+            })
+            .sum(),
+        logs,
+    };
+    (result, transaction_accounts, metering)
 }
 
 #[cfg(test)]
@@ -1199,4 +1322,71 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_mock_process_instruction_with_metering() {
+        let program_key = Pubkey::new_unique();
+        let owned_key = Pubkey::new_unique();
+        let other_key = Pubkey::new_unique();
+        let transaction_accounts = vec![
+            (owned_key, AccountSharedData::new(1, 2, &program_key)),
+            (
+                other_key,
+                AccountSharedData::new(1, 3, &Pubkey::new_unique()),
+            ),
+        ];
+        let instruction_accounts = vec![
+            AccountMeta::new(owned_key, false),
+            AccountMeta::new_readonly(other_key, false),
+            AccountMeta::new_readonly(other_key, false),
+        ];
+
+        let instruction_data = bincode::serialize(&MockInstruction::ConsumeComputeUnits {
+            compute_units_to_consume: 10,
+            desired_result: Ok(()),
+        })
+        .unwrap();
+        let (result, _accounts, metering) = mock_process_instruction_with_metering(
+            &program_key,
+            Vec::new(),
+            &instruction_data,
+            transaction_accounts.clone(),
+            instruction_accounts.clone(),
+            MockBuiltin::vm,
+            |_invoke_context| {},
+            |_invoke_context| {},
+        );
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            metering,
+            InstructionMetering {
+                compute_units_consumed: MOCK_BUILTIN_COMPUTE_UNIT_COST + 10,
+                instruction_data_len: instruction_data.len(),
+                account_data_len: 5,
+                account_data_written: 0,
+                logs: vec![
+                    format!("Program {program_key} invoke [1]"),
+                    format!("Program {program_key} success"),
+                ],
+            }
+        );
+
+        let instruction_data = bincode::serialize(&MockInstruction::Resize { new_len: 7 }).unwrap();
+        let (result, _accounts, metering) = mock_process_instruction_with_metering(
+            &program_key,
+            Vec::new(),
+            &instruction_data,
+            transaction_accounts,
+            instruction_accounts,
+            MockBuiltin::vm,
+            |_invoke_context| {},
+            |_invoke_context| {},
+        );
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            metering.compute_units_consumed,
+            MOCK_BUILTIN_COMPUTE_UNIT_COST
+        );
+        assert_eq!(metering.account_data_written, 7);
+    }
 }
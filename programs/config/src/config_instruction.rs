@@ -49,3 +49,10 @@ pub fn store<T: ConfigState>(
     let account_data = (ConfigKeys { keys }, data);
     Instruction::new_with_bincode(id(), &account_data, account_metas)
 }
+
+/// Compute units the config program consumes processing a `store`
+/// instruction, for setting the compute budget of transactions that include
+/// it.
+pub fn estimated_compute_units() -> u64 {
+    crate::config_processor::DEFAULT_COMPUTE_UNITS
+}
@@ -135,7 +135,10 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
 mod tests {
     use {
         super::*,
-        crate::{config_instruction, get_config_data, id, ConfigKeys, ConfigState},
+        crate::{
+            config_instruction, get_config_data, get_versioned_config, id, ConfigKeys, ConfigState,
+            VersionedConfig,
+        },
         bincode::serialized_size,
         serde_derive::{Deserialize, Serialize},
         solana_program_runtime::invoke_context::mock_process_instruction,
@@ -255,6 +258,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_process_store_versioned() {
+        solana_logger::setup();
+        let from_pubkey = Pubkey::new_unique();
+        let config_keypair = Keypair::new();
+        let config_pubkey = config_keypair.pubkey();
+        let signer_pubkey = Pubkey::new_unique();
+        let keys = vec![(config_pubkey, false), (signer_pubkey, true)];
+        let instructions = config_instruction::create_account::<VersionedConfig<MyConfig>>(
+            &from_pubkey,
+            &config_pubkey,
+            1,
+            keys.clone(),
+        );
+        let SystemInstruction::CreateAccount { space, .. } =
+            limited_deserialize(&instructions[0].data).unwrap()
+        else {
+            panic!("Not a CreateAccount system instruction")
+        };
+        let config_account = AccountSharedData::new(0, space as usize, &id());
+
+        let versioned = VersionedConfig::new(2, MyConfig::new(42));
+        let instruction = config_instruction::store(&config_pubkey, true, keys, &versioned);
+        let transaction_accounts = vec![
+            (config_pubkey, config_account),
+            (signer_pubkey, AccountSharedData::default()),
+        ];
+        let accounts = process_instruction(
+            &instruction.data,
+            transaction_accounts,
+            instruction.accounts.clone(),
+            Ok(()),
+        );
+        assert_eq!(
+            get_versioned_config::<MyConfig>(accounts[0].data(), 2).unwrap(),
+            MyConfig::new(42)
+        );
+        assert!(get_versioned_config::<MyConfig>(accounts[0].data(), 1).is_err());
+
+        // Once stored, the signer list must sign further updates
+        let mut instruction_accounts = instruction.accounts;
+        instruction_accounts[1].is_signer = false;
+        let transaction_accounts = vec![
+            (config_pubkey, accounts[0].clone()),
+            (signer_pubkey, AccountSharedData::default()),
+        ];
+        process_instruction(
+            &instruction.data,
+            transaction_accounts,
+            instruction_accounts,
+            Err(InstructionError::MissingRequiredSignature),
+        );
+    }
+
     #[test]
     fn test_process_store_fail_instruction_data_too_large() {
         solana_logger::setup();
@@ -8,6 +8,7 @@ pub use solana_sdk::config::program::id;
 use solana_sdk::stake::config::Config as StakeConfig;
 use {
     bincode::{deserialize, serialize, serialized_size},
+    serde::de::DeserializeOwned,
     serde_derive::{Deserialize, Serialize},
     solana_sdk::{
         account::{Account, AccountSharedData},
@@ -50,6 +51,45 @@ pub fn get_config_data(bytes: &[u8]) -> Result<&[u8], bincode::Error> {
         .map(|offset| &bytes[offset as usize..])
 }
 
+/// Config data prefixed by a version byte, so that the layout of `T` can
+/// change without ambiguity about how existing accounts are encoded.
+///
+/// The program itself does not interpret config data, so the version is only
+/// checked by readers, with [`get_versioned_config`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct VersionedConfig<T> {
+    pub version: u8,
+    pub config: T,
+}
+
+impl<T> VersionedConfig<T> {
+    pub fn new(version: u8, config: T) -> Self {
+        Self { version, config }
+    }
+}
+
+impl<T: ConfigState> ConfigState for VersionedConfig<T> {
+    fn max_space() -> u64 {
+        1 + T::max_space()
+    }
+}
+
+/// Deserialize the `VersionedConfig` stored in config account data, failing
+/// unless it has the expected `version`.
+pub fn get_versioned_config<T: DeserializeOwned>(
+    bytes: &[u8],
+    version: u8,
+) -> Result<T, bincode::Error> {
+    let versioned: VersionedConfig<T> = deserialize(get_config_data(bytes)?)?;
+    if versioned.version != version {
+        return Err(Box::new(bincode::ErrorKind::Custom(format!(
+            "config version {} does not match expected version {version}",
+            versioned.version
+        ))));
+    }
+    Ok(versioned.config)
+}
+
 // utility for pre-made Accounts
 pub fn create_config_account<T: ConfigState>(
     keys: Vec<(Pubkey, bool)>,
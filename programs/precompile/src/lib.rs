@@ -0,0 +1,228 @@
+//! Native processors for the precompiled signature verification programs.
+//!
+//! The bank checks `ed25519_program` and `secp256k1_program` instructions once,
+//! when the transaction is sanitized, and never invokes them. Harnesses that
+//! run instructions directly have no such pass, so this processor does the
+//! same verification when the instruction is executed, letting precompile
+//! instructions fail or succeed there as they would in a transaction.
+//!
+//! Like the bank's check, the processor only accepts top-level instructions;
+//! invoking a precompile through CPI is unsupported.
+
+use {
+    solana_program_runtime::{declare_process_instruction, ic_msg},
+    solana_sdk::{
+        account::ReadableAccount,
+        ed25519_program,
+        instruction::{InstructionError, TRANSACTION_LEVEL_STACK_HEIGHT},
+        precompiles::get_precompiles,
+        secp256k1_program,
+        sysvar::instructions,
+        transaction_context::{InstructionContext, TransactionContext},
+    },
+};
+
+/// Compute units charged per ed25519 signature, matching the cost model's
+/// `ED25519_VERIFY_COST`
+pub const ED25519_VERIFY_COMPUTE_UNITS: u64 = 2_280;
+/// Compute units charged per secp256k1 signature, matching the cost model's
+/// `SECP256K1_VERIFY_COST`
+pub const SECP256K1_VERIFY_COMPUTE_UNITS: u64 = 6_690;
+
+declare_process_instruction!(Entrypoint, 0, |invoke_context| {
+    if invoke_context.get_stack_height() != TRANSACTION_LEVEL_STACK_HEIGHT {
+        // Precompiles are not supported as an inner instruction
+        return Err(InstructionError::UnsupportedProgramId);
+    }
+
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let program_id = instruction_context.get_last_program_key(transaction_context)?;
+    let precompile = get_precompiles()
+        .iter()
+        .find(|precompile| precompile.program_id == *program_id)
+        .ok_or(InstructionError::IncorrectProgramId)?;
+    let compute_units_per_signature = if ed25519_program::check_id(program_id) {
+        ED25519_VERIFY_COMPUTE_UNITS
+    } else if secp256k1_program::check_id(program_id) {
+        SECP256K1_VERIFY_COMPUTE_UNITS
+    } else {
+        return Err(InstructionError::IncorrectProgramId);
+    };
+    // Both formats lead with the number of signatures. An instruction with
+    // none is charged for one, since builtins must consume compute units.
+    let num_signatures = instruction_context
+        .get_instruction_data()
+        .first()
+        .map_or(1, |num_signatures| u64::from(*num_signatures).max(1));
+    invoke_context
+        .consume_checked(compute_units_per_signature.saturating_mul(num_signatures))
+        .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
+
+    let instruction_datas = get_instruction_datas(transaction_context)?;
+    let instruction_datas: Vec<&[u8]> = instruction_datas.iter().map(Vec::as_slice).collect();
+    precompile
+        .verify(
+            instruction_context.get_instruction_data(),
+            &instruction_datas,
+            &invoke_context.feature_set,
+        )
+        .map_err(|err| {
+            ic_msg!(
+                invoke_context,
+                "{} verification failed: {}",
+                program_id,
+                err
+            );
+            err.into()
+        })
+});
+
+/// The data of the transaction's top-level instructions, which the signature
+/// offsets index into.
+///
+/// All of them are read from the instructions sysvar when the transaction
+/// loads it. Otherwise only the instructions executed so far, up to and
+/// including the current one, are known.
+fn get_instruction_datas(
+    transaction_context: &TransactionContext,
+) -> Result<Vec<Vec<u8>>, InstructionError> {
+    if let Some(index_in_transaction) =
+        transaction_context.find_index_of_account(&instructions::id())
+    {
+        let account = transaction_context.get_account_at_index(index_in_transaction)?;
+        let account = account
+            .try_borrow()
+            .map_err(|_| InstructionError::AccountBorrowFailed)?;
+        let mut instruction_datas = Vec::new();
+        #[allow(deprecated)]
+        while let Ok(instruction) =
+            instructions::load_instruction_at(instruction_datas.len(), account.data())
+        {
+            instruction_datas.push(instruction.data);
+        }
+        return Ok(instruction_datas);
+    }
+    (0..transaction_context.get_instruction_trace_length())
+        .map(|index_in_trace| {
+            transaction_context.get_instruction_context_at_index_in_trace(index_in_trace)
+        })
+        .filter(|instruction_context| {
+            instruction_context
+                .as_ref()
+                .map_or(true, |instruction_context| {
+                    instruction_context.get_stack_height() == 1
+                })
+        })
+        .map(|instruction_context| {
+            instruction_context
+                .map(InstructionContext::get_instruction_data)
+                .map(<[u8]>::to_vec)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_program_runtime::invoke_context::mock_process_instruction,
+        solana_sdk::{
+            account::{Account, AccountSharedData},
+            ed25519_instruction::new_ed25519_instruction,
+            instruction::Instruction,
+            precompiles::PrecompileError,
+            pubkey::Pubkey,
+            secp256k1_instruction::new_secp256k1_instruction,
+            sysvar::{
+                self,
+                instructions::{construct_instructions_data, BorrowedInstruction},
+            },
+        },
+    };
+
+    fn process_instruction(
+        instruction: &Instruction,
+        transaction_accounts: Vec<(Pubkey, AccountSharedData)>,
+        expected_result: Result<(), InstructionError>,
+    ) {
+        mock_process_instruction(
+            &instruction.program_id,
+            Vec::new(),
+            &instruction.data,
+            transaction_accounts,
+            instruction.accounts.clone(),
+            expected_result,
+            Entrypoint::vm,
+            |_invoke_context| {},
+            |_invoke_context| {},
+        );
+    }
+
+    fn ed25519_keypair() -> ed25519_dalek::Keypair {
+        let secret = ed25519_dalek::SecretKey::from_bytes(&[7; 32]).unwrap();
+        let public = ed25519_dalek::PublicKey::from(&secret);
+        ed25519_dalek::Keypair { secret, public }
+    }
+
+    #[test]
+    fn test_ed25519() {
+        let mut instruction = new_ed25519_instruction(&ed25519_keypair(), b"hello");
+        process_instruction(&instruction, Vec::new(), Ok(()));
+
+        // tamper with the signed message
+        *instruction.data.last_mut().unwrap() ^= 1;
+        process_instruction(
+            &instruction,
+            Vec::new(),
+            Err(PrecompileError::InvalidSignature.into()),
+        );
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        let secret_key = libsecp256k1::SecretKey::parse(&[7; 32]).unwrap();
+        let mut instruction = new_secp256k1_instruction(&secret_key, b"hello");
+        process_instruction(&instruction, Vec::new(), Ok(()));
+
+        *instruction.data.last_mut().unwrap() ^= 1;
+        process_instruction(
+            &instruction,
+            Vec::new(),
+            Err(PrecompileError::InvalidSignature.into()),
+        );
+    }
+
+    #[test]
+    fn test_secp256k1_with_instructions_sysvar() {
+        let secret_key = libsecp256k1::SecretKey::parse(&[7; 32]).unwrap();
+        let instruction = new_secp256k1_instruction(&secret_key, b"hello");
+        // the offsets index instruction 0, which the sysvar says is another
+        // instruction with no data
+        let other_program_id = Pubkey::new_unique();
+        let sysvar_data = construct_instructions_data(&[
+            BorrowedInstruction {
+                program_id: &other_program_id,
+                accounts: Vec::new(),
+                data: &[],
+            },
+            BorrowedInstruction {
+                program_id: &instruction.program_id,
+                accounts: Vec::new(),
+                data: &instruction.data,
+            },
+        ]);
+        let sysvar_account = AccountSharedData::from(Account {
+            lamports: 1,
+            data: sysvar_data,
+            owner: sysvar::id(),
+            executable: false,
+            rent_epoch: 0,
+        });
+        process_instruction(
+            &instruction,
+            vec![(instructions::id(), sysvar_account)],
+            Err(PrecompileError::InvalidSignature.into()),
+        );
+    }
+}
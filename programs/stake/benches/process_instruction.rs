@@ -0,0 +1,66 @@
+use {
+    criterion::{criterion_group, criterion_main, BatchSize, Criterion},
+    solana_sdk::{
+        feature_set::FeatureSet,
+        native_token::LAMPORTS_PER_SOL,
+        pubkey::Pubkey,
+        stake::{instruction, state::Authorized},
+    },
+    solana_stake_program::simulation::StakeSimulation,
+    std::sync::Arc,
+};
+
+fn bench_process_instruction(c: &mut Criterion) {
+    let vote_pubkey = Pubkey::new_unique();
+    let stake_pubkey = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let mut initialized = StakeSimulation::new(
+        Arc::new(FeatureSet::all_enabled()),
+        1_000 * LAMPORTS_PER_SOL,
+    );
+    initialized.create_vote_account(vote_pubkey, 0, LAMPORTS_PER_SOL);
+    initialized
+        .create_stake_account(
+            stake_pubkey,
+            &Authorized::auto(&authority),
+            LAMPORTS_PER_SOL,
+        )
+        .unwrap();
+    let delegate = instruction::delegate_stake(&stake_pubkey, &authority, &vote_pubkey);
+    let mut delegated = initialized.clone();
+    delegated.process_instruction(&delegate).unwrap();
+    delegated.advance_epoch();
+
+    c.bench_function("stake_delegate", |b| {
+        b.iter_batched(
+            || initialized.clone(),
+            |mut simulation| simulation.process_instruction(&delegate).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+    let deactivate = instruction::deactivate_stake(&stake_pubkey, &authority);
+    c.bench_function("stake_deactivate", |b| {
+        b.iter_batched(
+            || delegated.clone(),
+            |mut simulation| simulation.process_instruction(&deactivate).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+    let withdraw = instruction::withdraw(
+        &stake_pubkey,
+        &authority,
+        &Pubkey::new_unique(),
+        LAMPORTS_PER_SOL / 2,
+        None,
+    );
+    c.bench_function("stake_withdraw", |b| {
+        b.iter_batched(
+            || initialized.clone(),
+            |mut simulation| simulation.process_instruction(&withdraw).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, bench_process_instruction);
+criterion_main!(benches);
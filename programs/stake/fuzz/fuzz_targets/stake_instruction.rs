@@ -0,0 +1,10 @@
+#![no_main]
+
+use {
+    libfuzzer_sys::fuzz_target,
+    solana_stake_program::fuzz::{process_fuzz_input, FuzzInput},
+};
+
+fuzz_target!(|input: FuzzInput| {
+    let _ = process_fuzz_input(input);
+});
@@ -0,0 +1,222 @@
+//! Harness for fuzzing the stake program's instruction processor.
+//!
+//! [`FuzzInput`] describes an instruction and the accounts passed to it in
+//! terms `arbitrary` can generate efficiently: account keys are drawn from a
+//! small pool so accounts alias each other and reach the sysvars, and account
+//! data is either raw bytes or a well-formed stake or vote state. The cargo-fuzz
+//! targets in `programs/stake/fuzz` feed these into [`process_fuzz_input`].
+
+#![cfg(feature = "fuzz")]
+
+use {
+    crate::stake_instruction::Entrypoint,
+    arbitrary::Arbitrary,
+    solana_program_runtime::invoke_context::mock_process_instruction_with_result,
+    solana_sdk::{
+        account::{create_account_shared_data_for_test, AccountSharedData, ReadableAccount},
+        clock::Clock,
+        epoch_schedule::EpochSchedule,
+        feature_set::FeatureSet,
+        instruction::{AccountMeta, InstructionError},
+        packet::PACKET_DATA_SIZE,
+        program_utils::{deserialize_checked, limited_deserialize},
+        pubkey::Pubkey,
+        rent::Rent,
+        stake::{instruction::StakeInstruction, program::id, state::StakeStateV2},
+        stake_history::StakeHistory,
+        system_program,
+        sysvar::{clock, epoch_schedule, rent, stake_history},
+    },
+    solana_vote_program::vote_state::{VoteState, VoteStateVersions},
+    std::sync::Arc,
+};
+
+/// Number of distinct non-sysvar keys accounts are drawn from
+pub const FUZZ_KEY_POOL_SIZE: u8 = 8;
+/// Most instruction accounts passed to a single instruction
+pub const MAX_FUZZ_INSTRUCTION_ACCOUNTS: usize = 8;
+
+const SYSVAR_KEYS: [Pubkey; 4] = [clock::ID, epoch_schedule::ID, rent::ID, stake_history::ID];
+
+#[derive(Debug, Arbitrary)]
+pub enum FuzzInstructionData {
+    Raw(Vec<u8>),
+    Instruction(StakeInstruction),
+}
+
+#[derive(Debug, Arbitrary)]
+pub enum FuzzAccountData {
+    Raw(Vec<u8>),
+    Stake(StakeStateV2),
+    /// A default vote state, owned by the vote program
+    Vote,
+}
+
+#[derive(Debug, Arbitrary)]
+pub struct FuzzAccount {
+    /// Index into the key pool; sysvar keys follow the non-sysvar keys
+    pub key: u8,
+    pub lamports: u64,
+    pub data: FuzzAccountData,
+    /// Whether the account is owned by the stake program, for raw data
+    pub owned_by_stake_program: bool,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(Debug, Arbitrary)]
+pub struct FuzzInput {
+    pub instruction_data: FuzzInstructionData,
+    pub accounts: Vec<FuzzAccount>,
+    pub slot: u64,
+    pub epoch: u64,
+}
+
+fn fuzz_key(index: u8) -> Pubkey {
+    let index = index % (FUZZ_KEY_POOL_SIZE + SYSVAR_KEYS.len() as u8);
+    match index.checked_sub(FUZZ_KEY_POOL_SIZE) {
+        Some(sysvar_index) => SYSVAR_KEYS[sysvar_index as usize],
+        None => Pubkey::new_from_array([index + 1; 32]),
+    }
+}
+
+fn fuzz_account(account: &FuzzAccount) -> AccountSharedData {
+    match &account.data {
+        FuzzAccountData::Raw(data) => {
+            let owner = if account.owned_by_stake_program {
+                id()
+            } else {
+                system_program::id()
+            };
+            let mut shared = AccountSharedData::new(account.lamports, data.len(), &owner);
+            shared.set_data_from_slice(data);
+            shared
+        }
+        FuzzAccountData::Stake(stake_state) => AccountSharedData::new_data_with_space(
+            account.lamports,
+            stake_state,
+            StakeStateV2::size_of(),
+            &id(),
+        )
+        .unwrap(),
+        FuzzAccountData::Vote => AccountSharedData::new_data_with_space(
+            account.lamports,
+            &VoteStateVersions::new_current(VoteState::default()),
+            VoteState::size_of(),
+            &solana_vote_program::id(),
+        )
+        .unwrap(),
+    }
+}
+
+/// Run the stake processor on `input` with all features enabled.
+///
+/// Panics if the processor panics or if the instruction succeeds without
+/// conserving lamports across its accounts. Errors returned by the program are
+/// expected and passed through.
+pub fn process_fuzz_input(input: FuzzInput) -> Result<(), InstructionError> {
+    let clock = Clock {
+        slot: input.slot,
+        epoch: input.epoch,
+        ..Clock::default()
+    };
+    let mut transaction_accounts = vec![
+        (clock::id(), create_account_shared_data_for_test(&clock)),
+        (
+            epoch_schedule::id(),
+            create_account_shared_data_for_test(&EpochSchedule::default()),
+        ),
+        (
+            rent::id(),
+            create_account_shared_data_for_test(&Rent::default()),
+        ),
+        (
+            stake_history::id(),
+            create_account_shared_data_for_test(&StakeHistory::default()),
+        ),
+    ];
+    let mut instruction_accounts = Vec::with_capacity(MAX_FUZZ_INSTRUCTION_ACCOUNTS);
+    for account in input.accounts.iter().take(MAX_FUZZ_INSTRUCTION_ACCOUNTS) {
+        let pubkey = fuzz_key(account.key);
+        if !transaction_accounts.iter().any(|(key, _)| *key == pubkey) {
+            transaction_accounts.push((pubkey, fuzz_account(account)));
+        }
+        instruction_accounts.push(AccountMeta {
+            pubkey,
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        });
+    }
+    let instruction_data = match input.instruction_data {
+        FuzzInstructionData::Raw(data) => data,
+        FuzzInstructionData::Instruction(instruction) => bincode::serialize(&instruction).unwrap(),
+    };
+    // The processor decodes instructions by hand, which must agree with bincode
+    assert_eq!(
+        StakeInstruction::decode(
+            instruction_data
+                .get(..PACKET_DATA_SIZE)
+                .unwrap_or(&instruction_data)
+        )
+        .ok(),
+        limited_deserialize::<StakeInstruction>(&instruction_data).ok(),
+    );
+    if instruction_data.len() <= PACKET_DATA_SIZE {
+        assert_eq!(
+            StakeInstruction::decode_checked(&instruction_data).ok(),
+            deserialize_checked::<StakeInstruction>(&instruction_data).ok(),
+        );
+    }
+
+    let pre_lamports: u128 = transaction_accounts
+        .iter()
+        .map(|(_, account)| u128::from(account.lamports()))
+        .sum();
+    let (result, post_accounts) = mock_process_instruction_with_result(
+        &id(),
+        Vec::new(),
+        &instruction_data,
+        transaction_accounts,
+        instruction_accounts,
+        Entrypoint::vm,
+        |invoke_context| {
+            invoke_context.feature_set = Arc::new(FeatureSet::all_enabled());
+        },
+        |_invoke_context| {},
+    );
+
+    assert_ne!(result, Err(InstructionError::UnbalancedInstruction));
+    if result.is_ok() {
+        let post_lamports: u128 = post_accounts
+            .iter()
+            .map(|account| u128::from(account.lamports()))
+            .sum();
+        assert_eq!(pre_lamports, post_lamports);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, arbitrary::Unstructured};
+
+    #[test]
+    fn test_fuzz_key() {
+        assert_eq!(fuzz_key(0), Pubkey::new_from_array([1; 32]));
+        assert_eq!(fuzz_key(FUZZ_KEY_POOL_SIZE), clock::id());
+        assert_eq!(fuzz_key(FUZZ_KEY_POOL_SIZE + 3), stake_history::id());
+        assert_eq!(fuzz_key(FUZZ_KEY_POOL_SIZE + 4), fuzz_key(0));
+    }
+
+    #[test]
+    fn test_process_fuzz_input() {
+        for seed in 0..=u8::MAX {
+            let bytes: Vec<u8> = (0..1024u32)
+                .map(|i| (i.wrapping_mul(31) as u8) ^ seed)
+                .collect();
+            if let Ok(input) = FuzzInput::arbitrary(&mut Unstructured::new(&bytes)) {
+                let _ = process_fuzz_input(input);
+            }
+        }
+    }
+}
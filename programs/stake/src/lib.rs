@@ -12,9 +12,13 @@ use solana_sdk::{
 };
 
 pub mod config;
+pub mod fuzz;
 pub mod points;
 #[doc(hidden)]
 pub mod rewards;
+pub mod rewards_pools;
+#[cfg(feature = "dev-context-only-utils")]
+pub mod simulation;
 pub mod stake_instruction;
 pub mod stake_state;
 
@@ -222,7 +222,7 @@ mod tests {
         //  10_000_000 SOL is a big but not unreasaonable stake
         let stake = new_stake(
             native_token::sol_to_lamports(10_000_000f64),
-            &Pubkey::default(),
+            &Pubkey::new_unique(),
             &vote_state,
             std::u64::MAX,
         );
@@ -240,7 +240,7 @@ mod tests {
         let stake_lamports = 1;
         let mut stake = new_stake(
             stake_lamports,
-            &Pubkey::default(),
+            &Pubkey::new_unique(),
             &vote_state,
             std::u64::MAX,
         );
@@ -295,7 +295,7 @@ mod tests {
         let mut vote_state = VoteState::default();
         // assume stake.stake() is right
         // bootstrap means fully-vested stake at epoch 0
-        let mut stake = new_stake(1, &Pubkey::default(), &vote_state, std::u64::MAX);
+        let mut stake = new_stake(1, &Pubkey::new_unique(), &vote_state, std::u64::MAX);
 
         // this one can't collect now, credits_observed == vote_state.credits()
         assert_eq!(
@@ -622,7 +622,7 @@ mod tests {
         //  10_000_000 SOL is a big but not unreasaonable stake
         let stake = new_stake(
             native_token::sol_to_lamports(10_000_000f64),
-            &Pubkey::default(),
+            &Pubkey::new_unique(),
             &vote_state,
             std::u64::MAX,
         );
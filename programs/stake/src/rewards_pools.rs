@@ -0,0 +1,240 @@
+//! Rewards pools: stake program accounts in the `RewardsPool` state, created at
+//! genesis through `GenesisConfig::rewards_pools`.
+//!
+//! Rewards are spread over [`NUM_REWARDS_POOLS`] accounts to cut down on write
+//! contention between the transactions drawing from them. The first pool has
+//! the base id declared here, and each following id is the hash of the one
+//! before it.
+//!
+//! Once the `stake_replenish_rewards_pool_instruction` feature is active, pools
+//! are topped up with the stake program's `ReplenishRewardsPool` instruction,
+//! which rejects any destination that is not a genuine pool before moving the
+//! lamports with a system program transfer. Until then, [`replenish`] builds a
+//! plain system program transfer, and the only validation of the destination
+//! is the client-side [`check_rewards_pool`].
+
+use solana_sdk::{
+    account::{Account, ReadableAccount},
+    hash::hash,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    rent::Rent,
+    stake::{self, state::StakeStateV2},
+    sync::LazyLock,
+    system_instruction,
+};
+
+// base rewards pool ID
+solana_sdk::declare_id!("StakeRewards1111111111111111111111111111111");
+
+/// Number of rewards pools
+pub const NUM_REWARDS_POOLS: usize = 256;
+
+static IDS: LazyLock<Vec<Pubkey>> = LazyLock::new(|| {
+    std::iter::successors(Some(id()), |pubkey| {
+        Some(Pubkey::from(hash(pubkey.as_ref()).to_bytes()))
+    })
+    .take(NUM_REWARDS_POOLS)
+    .collect()
+});
+
+/// The ids of all rewards pools, base id first.
+pub fn ids() -> &'static [Pubkey] {
+    &IDS
+}
+
+/// Whether `pubkey` is the id of a rewards pool.
+pub fn is_rewards_pool_id(pubkey: &Pubkey) -> bool {
+    ids().contains(pubkey)
+}
+
+/// Rewards pool accounts for `GenesisConfig::rewards_pools`, each holding
+/// `lamports_per_pool`.
+pub fn create_genesis_accounts(lamports_per_pool: u64) -> Vec<(Pubkey, Account)> {
+    ids()
+        .iter()
+        .map(|pubkey| {
+            let account = Account::new_data_with_space(
+                lamports_per_pool,
+                &StakeStateV2::RewardsPool,
+                StakeStateV2::size_of(),
+                &stake::program::id(),
+            )
+            .unwrap();
+            (*pubkey, account)
+        })
+        .collect()
+}
+
+/// Checks that `account`, at `pubkey`, is a genuine rewards pool: at a pool
+/// id, owned by the stake program and in the `RewardsPool` state.
+pub fn check_rewards_pool(
+    pubkey: &Pubkey,
+    account: &impl ReadableAccount,
+) -> Result<(), InstructionError> {
+    check_rewards_pool_data(pubkey, account.owner(), account.data())
+}
+
+/// Like [`check_rewards_pool`], for an account given by its `owner` and
+/// `data`.
+pub(crate) fn check_rewards_pool_data(
+    pubkey: &Pubkey,
+    owner: &Pubkey,
+    data: &[u8],
+) -> Result<(), InstructionError> {
+    if !is_rewards_pool_id(pubkey) {
+        return Err(InstructionError::InvalidArgument);
+    }
+    if !stake::program::check_id(owner) {
+        return Err(InstructionError::IncorrectProgramId);
+    }
+    match bincode::deserialize(data) {
+        Ok(StakeStateV2::RewardsPool) => Ok(()),
+        _ => Err(InstructionError::InvalidAccountData),
+    }
+}
+
+/// Transfers `lamports` from `funder` to the rewards pool at `pool_pubkey`,
+/// given the pool's current `pool_account`.
+///
+/// The system program credits any account it is asked to, so the destination
+/// is checked with [`check_rewards_pool`] before building the transfer. This
+/// check runs against `pool_account` as the caller fetched it and is not
+/// repeated when the transaction is processed; use
+/// `stake::instruction::replenish_rewards_pool` instead once the
+/// `stake_replenish_rewards_pool_instruction` feature is active.
+pub fn replenish(
+    funder: &Pubkey,
+    pool_pubkey: &Pubkey,
+    pool_account: &impl ReadableAccount,
+    lamports: u64,
+) -> Result<Instruction, InstructionError> {
+    check_rewards_pool(pool_pubkey, pool_account)?;
+    Ok(system_instruction::transfer(funder, pool_pubkey, lamports))
+}
+
+/// The balance of a rewards pool account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RewardsPoolBalance {
+    pub pubkey: Pubkey,
+    pub lamports: u64,
+    /// Lamports above the pool's rent exempt reserve
+    pub redeemable: u64,
+}
+
+/// Reports the balances of the genuine rewards pools among fetched `accounts`,
+/// in the order given.
+///
+/// Accounts that are not rewards pools, including accounts at a pool id that
+/// aren't owned by the stake program in the `RewardsPool` state, are skipped.
+pub fn pool_balances<'a, T: ReadableAccount + 'a>(
+    accounts: impl IntoIterator<Item = (&'a Pubkey, &'a T)>,
+    rent: &Rent,
+) -> Vec<RewardsPoolBalance> {
+    accounts
+        .into_iter()
+        .filter(|(pubkey, account)| check_rewards_pool(pubkey, *account).is_ok())
+        .map(|(pubkey, account)| RewardsPoolBalance {
+            pubkey: *pubkey,
+            lamports: account.lamports(),
+            redeemable: account
+                .lamports()
+                .saturating_sub(rent.minimum_balance(account.data().len())),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::account::{AccountSharedData, WritableAccount},
+    };
+
+    #[test]
+    fn test_ids() {
+        let ids = ids();
+        assert_eq!(ids.len(), NUM_REWARDS_POOLS);
+        assert_eq!(ids[0], id());
+        assert_eq!(ids[1], Pubkey::from(hash(id().as_ref()).to_bytes()));
+        let unique: std::collections::HashSet<_> = ids.iter().collect();
+        assert_eq!(unique.len(), NUM_REWARDS_POOLS);
+        assert!(is_rewards_pool_id(&ids[NUM_REWARDS_POOLS - 1]));
+        assert!(!is_rewards_pool_id(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_pool_balances() {
+        let rent = Rent::default();
+        let reserve = rent.minimum_balance(StakeStateV2::size_of());
+        let mut accounts: Vec<(Pubkey, AccountSharedData)> = create_genesis_accounts(reserve + 42)
+            .into_iter()
+            .take(2)
+            .map(|(pubkey, account)| (pubkey, account.into()))
+            .collect();
+        assert_eq!(accounts[1].0, ids()[1]);
+        // Not a pool id
+        accounts.push((
+            Pubkey::new_unique(),
+            AccountSharedData::from(create_genesis_accounts(1).remove(0).1),
+        ));
+        // A pool id, but not in the `RewardsPool` state
+        accounts.push((
+            ids()[2],
+            AccountSharedData::new_data_with_space(
+                reserve + 42,
+                &StakeStateV2::Uninitialized,
+                StakeStateV2::size_of(),
+                &stake::program::id(),
+            )
+            .unwrap(),
+        ));
+        accounts[1].1.set_lamports(reserve - 1);
+
+        assert_eq!(
+            pool_balances(
+                accounts.iter().map(|(pubkey, account)| (pubkey, account)),
+                &rent
+            ),
+            vec![
+                RewardsPoolBalance {
+                    pubkey: ids()[0],
+                    lamports: reserve + 42,
+                    redeemable: 42,
+                },
+                RewardsPoolBalance {
+                    pubkey: ids()[1],
+                    lamports: reserve - 1,
+                    redeemable: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replenish() {
+        let funder = Pubkey::new_unique();
+        let (pool_pubkey, pool_account) = create_genesis_accounts(1).remove(0);
+        assert_eq!(
+            replenish(&funder, &pool_pubkey, &pool_account, 42),
+            Ok(system_instruction::transfer(&funder, &pool_pubkey, 42))
+        );
+
+        assert_eq!(
+            replenish(&funder, &Pubkey::new_unique(), &pool_account, 42),
+            Err(InstructionError::InvalidArgument)
+        );
+        let mut account = AccountSharedData::from(pool_account);
+        account.set_owner(Pubkey::new_unique());
+        assert_eq!(
+            replenish(&funder, &pool_pubkey, &account, 42),
+            Err(InstructionError::IncorrectProgramId)
+        );
+        account.set_owner(stake::program::id());
+        account.set_data_from_slice(&[0; 4]);
+        assert_eq!(
+            replenish(&funder, &pool_pubkey, &account, 42),
+            Err(InstructionError::InvalidAccountData)
+        );
+    }
+}
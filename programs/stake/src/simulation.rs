@@ -0,0 +1,398 @@
+//! Multi-epoch simulation of stake accounts, for integration tests.
+//!
+//! [`StakeSimulation`] keeps a set of accounts together with a clock and stake
+//! history, runs stake instructions against them through the real processor,
+//! and advances epochs the way the runtime does: the stake history entry for
+//! the ending epoch is computed from every delegation in the simulation before
+//! the clock moves on. This lets stake pool and other downstream authors test
+//! full delegate → warmup → redeem → deactivate → cooldown → withdraw
+//! lifecycles against this program's exact warmup and cooldown semantics.
+
+use {
+    crate::{
+        config,
+        points::{null_tracer, PointValue},
+        rewards,
+        stake_instruction::Entrypoint,
+        stake_state,
+    },
+    solana_program_runtime::invoke_context::mock_process_instruction_with_result,
+    solana_sdk::{
+        account::{create_account_shared_data_for_test, AccountSharedData, ReadableAccount},
+        account_utils::StateMut,
+        clock::{Clock, Epoch},
+        epoch_schedule::EpochSchedule,
+        feature_set::FeatureSet,
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        rent::Rent,
+        stake::{
+            self,
+            program::id,
+            state::{Delegation, StakeActivationStatus, StakeStateV2},
+        },
+        stake_history::StakeHistory,
+        sysvar::{clock, epoch_schedule, rent, stake_history},
+    },
+    solana_vote_program::vote_state::{self, VoteState, VoteStateVersions},
+    std::{collections::HashMap, sync::Arc},
+};
+
+#[derive(Debug, Clone)]
+pub struct StakeSimulation {
+    feature_set: Arc<FeatureSet>,
+    clock: Clock,
+    epoch_schedule: EpochSchedule,
+    rent: Rent,
+    stake_history: StakeHistory,
+    /// Stake that is fully effective from genesis, like the bootstrap
+    /// validator's, so that new delegations have a cluster to warm up against
+    bootstrap_stake: u64,
+    accounts: HashMap<Pubkey, AccountSharedData>,
+}
+
+impl StakeSimulation {
+    pub fn new(feature_set: Arc<FeatureSet>, bootstrap_stake: u64) -> Self {
+        Self {
+            feature_set,
+            clock: Clock::default(),
+            epoch_schedule: EpochSchedule::default(),
+            rent: Rent::default(),
+            stake_history: StakeHistory::default(),
+            bootstrap_stake,
+            accounts: HashMap::new(),
+        }
+    }
+
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    pub fn epoch(&self) -> Epoch {
+        self.clock.epoch
+    }
+
+    pub fn rent(&self) -> &Rent {
+        &self.rent
+    }
+
+    pub fn stake_history(&self) -> &StakeHistory {
+        &self.stake_history
+    }
+
+    pub fn new_rate_activation_epoch(&self) -> Option<Epoch> {
+        self.feature_set
+            .new_warmup_cooldown_rate_epoch(&self.epoch_schedule)
+    }
+
+    /// Returns the account at `pubkey`, or a default system account if the
+    /// simulation has not seen it
+    pub fn get_account(&self, pubkey: &Pubkey) -> AccountSharedData {
+        self.accounts.get(pubkey).cloned().unwrap_or_default()
+    }
+
+    pub fn set_account(&mut self, pubkey: Pubkey, account: AccountSharedData) {
+        self.accounts.insert(pubkey, account);
+    }
+
+    pub fn lamports(&self, pubkey: &Pubkey) -> u64 {
+        self.get_account(pubkey).lamports()
+    }
+
+    /// Create a vote account whose voter and withdrawer are `vote_pubkey`
+    pub fn create_vote_account(&mut self, vote_pubkey: Pubkey, commission: u8, lamports: u64) {
+        let vote_account =
+            vote_state::create_account(&vote_pubkey, &Pubkey::new_unique(), commission, lamports);
+        self.set_account(vote_pubkey, vote_account);
+    }
+
+    /// Credit `vote_pubkey` with `credits` earned in the current epoch, as if
+    /// the validator had voted.
+    pub fn add_vote_credits(&mut self, vote_pubkey: &Pubkey, credits: u64) {
+        let mut vote_account = self.get_account(vote_pubkey);
+        let mut vote_state = VoteState::deserialize(vote_account.data()).unwrap();
+        vote_state.increment_credits(self.clock.epoch, credits);
+        vote_account
+            .set_state(&VoteStateVersions::new_current(vote_state))
+            .unwrap();
+        self.set_account(*vote_pubkey, vote_account);
+    }
+
+    /// Create a rent exempt stake account and initialize it.
+    ///
+    /// `lamports` is in addition to the rent exempt reserve.
+    pub fn create_stake_account(
+        &mut self,
+        stake_pubkey: Pubkey,
+        authorized: &stake::state::Authorized,
+        lamports: u64,
+    ) -> Result<(), InstructionError> {
+        let reserve = self.rent.minimum_balance(StakeStateV2::size_of());
+        self.set_account(
+            stake_pubkey,
+            AccountSharedData::new(
+                reserve.saturating_add(lamports),
+                StakeStateV2::size_of(),
+                &id(),
+            ),
+        );
+        self.process_instruction(&stake::instruction::initialize(
+            &stake_pubkey,
+            authorized,
+            &stake::state::Lockup::default(),
+        ))
+    }
+
+    fn sysvar_accounts(&self) -> Vec<(Pubkey, AccountSharedData)> {
+        #[allow(deprecated)]
+        let config_account = (
+            stake::config::id(),
+            config::create_account(0, &stake::config::Config::default()),
+        );
+        vec![
+            (
+                clock::id(),
+                create_account_shared_data_for_test(&self.clock),
+            ),
+            (
+                epoch_schedule::id(),
+                create_account_shared_data_for_test(&self.epoch_schedule),
+            ),
+            (rent::id(), create_account_shared_data_for_test(&self.rent)),
+            (
+                stake_history::id(),
+                create_account_shared_data_for_test(&self.stake_history),
+            ),
+            config_account,
+        ]
+    }
+
+    /// Run `instruction` through the stake program, treating every signer in
+    /// its account metas as having signed. Accounts are only updated if the
+    /// instruction succeeds.
+    pub fn process_instruction(
+        &mut self,
+        instruction: &Instruction,
+    ) -> Result<(), InstructionError> {
+        let mut transaction_accounts = self.sysvar_accounts();
+        let number_of_sysvars = transaction_accounts.len();
+        for meta in &instruction.accounts {
+            if !transaction_accounts
+                .iter()
+                .any(|(pubkey, _)| *pubkey == meta.pubkey)
+            {
+                transaction_accounts.push((meta.pubkey, self.get_account(&meta.pubkey)));
+            }
+        }
+        let keys: Vec<Pubkey> = transaction_accounts
+            .iter()
+            .map(|(pubkey, _)| *pubkey)
+            .collect();
+
+        let feature_set = Arc::clone(&self.feature_set);
+        let (result, post_accounts) = mock_process_instruction_with_result(
+            &id(),
+            Vec::new(),
+            &instruction.data,
+            transaction_accounts,
+            instruction.accounts.clone(),
+            Entrypoint::vm,
+            |invoke_context| {
+                invoke_context.feature_set = Arc::clone(&feature_set);
+            },
+            |_invoke_context| {},
+        );
+        result?;
+        for (pubkey, account) in keys.into_iter().zip(post_accounts).skip(number_of_sysvars) {
+            self.set_account(pubkey, account);
+        }
+        Ok(())
+    }
+
+    fn delegations(&self) -> Vec<Delegation> {
+        self.accounts
+            .values()
+            .filter_map(stake_state::delegation_from)
+            .collect()
+    }
+
+    /// End the current epoch: record its stake history entry and move the
+    /// clock to the first slot of the next epoch.
+    pub fn advance_epoch(&mut self) {
+        let bootstrap = Delegation {
+            stake: self.bootstrap_stake,
+            activation_epoch: Epoch::MAX,
+            ..Delegation::default()
+        };
+        let delegations = self.delegations();
+        let entry = stake_state::new_stake_history_entry(
+            self.clock.epoch,
+            delegations.iter().chain(std::iter::once(&bootstrap)),
+            &self.stake_history,
+            self.new_rate_activation_epoch(),
+        );
+        self.stake_history.add(self.clock.epoch, entry);
+        self.clock.epoch += 1;
+        self.clock.slot = self
+            .epoch_schedule
+            .get_first_slot_in_epoch(self.clock.epoch);
+    }
+
+    pub fn advance_epochs(&mut self, epochs: u64) {
+        for _ in 0..epochs {
+            self.advance_epoch();
+        }
+    }
+
+    /// Pay out rewards for the previous epoch to `stake_pubkey`, the way the
+    /// runtime does at an epoch boundary. Returns the staker and voter rewards.
+    pub fn redeem_rewards(
+        &mut self,
+        stake_pubkey: &Pubkey,
+        point_value: &PointValue,
+    ) -> Result<(u64, u64), InstructionError> {
+        let rewarded_epoch = self
+            .clock
+            .epoch
+            .checked_sub(1)
+            .ok_or(InstructionError::InvalidArgument)?;
+        let mut stake_account = self.get_account(stake_pubkey);
+        let stake_state: StakeStateV2 = stake_account.state()?;
+        let vote_pubkey = stake_state
+            .delegation()
+            .ok_or(InstructionError::InvalidAccountData)?
+            .voter_pubkey;
+        let vote_state = VoteState::deserialize(self.get_account(&vote_pubkey).data())?;
+        let rewards = rewards::redeem_rewards(
+            rewarded_epoch,
+            stake_state,
+            &mut stake_account,
+            &vote_state,
+            point_value,
+            &self.stake_history,
+            null_tracer(),
+            self.new_rate_activation_epoch(),
+        )?;
+        self.set_account(*stake_pubkey, stake_account);
+        Ok(rewards)
+    }
+
+    /// The effective, activating and deactivating stake of `stake_pubkey` in
+    /// the current epoch
+    pub fn stake_activation(&self, stake_pubkey: &Pubkey) -> StakeActivationStatus {
+        stake_state::delegation_from(&self.get_account(stake_pubkey))
+            .map(|delegation| {
+                delegation.stake_activating_and_deactivating(
+                    self.clock.epoch,
+                    &self.stake_history,
+                    self.new_rate_activation_epoch(),
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn effective_stake(&self, stake_pubkey: &Pubkey) -> u64 {
+        self.stake_activation(stake_pubkey).effective
+    }
+
+    /// Assert the effective stake of `stake_pubkey` in the current epoch
+    #[track_caller]
+    pub fn assert_effective_stake(&self, stake_pubkey: &Pubkey, expected: u64) {
+        assert_eq!(
+            self.effective_stake(stake_pubkey),
+            expected,
+            "effective stake of {stake_pubkey} in epoch {}",
+            self.clock.epoch
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{native_token::LAMPORTS_PER_SOL, stake::state::Authorized},
+    };
+
+    #[test]
+    fn test_stake_lifecycle() {
+        let mut simulation = StakeSimulation::new(
+            Arc::new(FeatureSet::all_enabled()),
+            1_000 * LAMPORTS_PER_SOL,
+        );
+        let vote_pubkey = Pubkey::new_unique();
+        let stake_pubkey = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let stake_lamports = 10 * LAMPORTS_PER_SOL;
+
+        simulation.create_vote_account(vote_pubkey, 0, LAMPORTS_PER_SOL);
+        simulation
+            .create_stake_account(stake_pubkey, &Authorized::auto(&authority), stake_lamports)
+            .unwrap();
+        simulation
+            .process_instruction(&stake::instruction::delegate_stake(
+                &stake_pubkey,
+                &authority,
+                &vote_pubkey,
+            ))
+            .unwrap();
+        simulation.assert_effective_stake(&stake_pubkey, 0);
+        assert_eq!(
+            simulation.stake_activation(&stake_pubkey).activating,
+            stake_lamports
+        );
+
+        // Small relative to the bootstrap stake, so it warms up in one epoch
+        simulation.advance_epoch();
+        simulation.assert_effective_stake(&stake_pubkey, stake_lamports);
+
+        simulation.add_vote_credits(&vote_pubkey, 100);
+        simulation.advance_epoch();
+        let lamports_before_rewards = simulation.lamports(&stake_pubkey);
+        let (staker_rewards, voter_rewards) = simulation
+            .redeem_rewards(
+                &stake_pubkey,
+                &PointValue {
+                    rewards: LAMPORTS_PER_SOL,
+                    points: u128::from(stake_lamports) * 100,
+                },
+            )
+            .unwrap();
+        assert_eq!(staker_rewards, LAMPORTS_PER_SOL);
+        assert_eq!(voter_rewards, 0);
+        assert_eq!(
+            simulation.lamports(&stake_pubkey),
+            lamports_before_rewards + staker_rewards
+        );
+
+        simulation
+            .process_instruction(&stake::instruction::deactivate_stake(
+                &stake_pubkey,
+                &authority,
+            ))
+            .unwrap();
+        assert_eq!(
+            simulation.stake_activation(&stake_pubkey).deactivating,
+            stake_lamports + staker_rewards
+        );
+        let withdraw_all = stake::instruction::withdraw(
+            &stake_pubkey,
+            &authority,
+            &recipient,
+            simulation.lamports(&stake_pubkey),
+            None,
+        );
+        assert_eq!(
+            simulation.process_instruction(&withdraw_all),
+            Err(InstructionError::InsufficientFunds)
+        );
+
+        simulation.advance_epoch();
+        simulation.assert_effective_stake(&stake_pubkey, 0);
+        let total_lamports = simulation.lamports(&stake_pubkey);
+        simulation.process_instruction(&withdraw_all).unwrap();
+        assert_eq!(simulation.lamports(&stake_pubkey), 0);
+        assert_eq!(simulation.lamports(&recipient), total_lamports);
+    }
+}
@@ -3,19 +3,21 @@ use {
         config,
         stake_state::{
             authorize, authorize_with_seed, deactivate, deactivate_delinquent, delegate,
-            initialize, merge, new_warmup_cooldown_rate_epoch, redelegate, set_lockup, split,
-            withdraw,
+            initialize, initialize_if_needed, merge, new_warmup_cooldown_rate_epoch, redelegate,
+            replenish_rewards_pool, set_lockup, split, update_custodian, withdraw,
         },
     },
     log::*,
     solana_program_runtime::{
-        declare_process_instruction, sysvar_cache::get_sysvar_with_account_check,
+        declare_process_instruction, ic_msg, sysvar_cache::get_sysvar_with_account_check,
     },
     solana_sdk::{
+        compute_units::BuiltinComputeUnits,
         feature_set,
         instruction::InstructionError,
-        program_utils::limited_deserialize,
+        packet::PACKET_DATA_SIZE,
         pubkey::Pubkey,
+        sanitize::Sanitize,
         stake::{
             instruction::{LockupArgs, StakeInstruction},
             program::id,
@@ -51,7 +53,39 @@ fn get_optional_pubkey<'a>(
     )
 }
 
-pub const DEFAULT_COMPUTE_UNITS: u64 = 750;
+/// Checks the instruction's accounts against `StakeInstruction::account_spec`
+/// up front, so that a misordered account is reported by its index.
+///
+/// Only compiled for tests: the spec states what the processor enforces, so
+/// running it ahead of dispatch must not change any test's outcome, and a
+/// test that fails here points at a spec that has drifted from the processor.
+#[cfg(test)]
+fn check_instruction_accounts(
+    invoke_context: &solana_program_runtime::invoke_context::InvokeContext,
+    instruction: &StakeInstruction,
+) -> Result<(), InstructionError> {
+    use solana_sdk::{account_spec::check_account_metas, instruction::AccountMeta};
+
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let accounts = (0..instruction_context.get_number_of_instruction_accounts())
+        .map(|index| {
+            Ok(AccountMeta {
+                pubkey: *transaction_context.get_key_of_account_at_index(
+                    instruction_context.get_index_of_instruction_account_in_transaction(index)?,
+                )?,
+                is_signer: instruction_context.is_instruction_account_signer(index)?,
+                is_writable: instruction_context.is_instruction_account_writable(index)?,
+            })
+        })
+        .collect::<Result<Vec<_>, InstructionError>>()?;
+    check_account_metas(instruction.account_spec(), &accounts).map_err(|err| {
+        ic_msg!(invoke_context, "{}", err);
+        err.into()
+    })
+}
+
+pub const DEFAULT_COMPUTE_UNITS: u64 = StakeInstruction::DEFAULT_COMPUTE_UNITS;
 
 declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context| {
     let transaction_context = &invoke_context.transaction_context;
@@ -68,8 +102,49 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
         Ok(me)
     };
 
+    let instruction = if invoke_context
+        .feature_set
+        .is_active(&feature_set::reject_noncanonical_stake_instruction_data::id())
+    {
+        // The same checks as `program_utils::deserialize_checked`
+        if data.len() > PACKET_DATA_SIZE {
+            Err(InstructionError::InvalidInstructionData)
+        } else {
+            StakeInstruction::decode_checked(data)
+        }
+    } else {
+        // Decoding reads at most `PACKET_DATA_SIZE` bytes, the same limit that
+        // `limited_deserialize` places on bincode
+        StakeInstruction::decode(data.get(..PACKET_DATA_SIZE).unwrap_or(data))
+    };
+
+    let instruction = if invoke_context
+        .feature_set
+        .is_active(&feature_set::sanitize_stake_instructions::id())
+    {
+        instruction.and_then(|instruction| {
+            instruction.sanitize().map_err(|err| {
+                ic_msg!(
+                    invoke_context,
+                    "Invalid stake instruction {:?}: {}",
+                    instruction,
+                    err
+                );
+                InstructionError::InvalidInstructionData
+            })?;
+            Ok(instruction)
+        })
+    } else {
+        instruction
+    };
+
+    #[cfg(test)]
+    if let Ok(instruction) = &instruction {
+        check_instruction_accounts(invoke_context, instruction)?;
+    }
+
     let signers = instruction_context.get_signers(transaction_context)?;
-    match limited_deserialize(data) {
+    match instruction {
         Ok(StakeInstruction::Initialize(authorized, lockup)) => {
             let mut me = get_stake_account()?;
             let rent = get_sysvar_with_account_check::rent(invoke_context, instruction_context, 1)?;
@@ -367,6 +442,41 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
                 Err(InstructionError::InvalidInstructionData)
             }
         }
+        Ok(StakeInstruction::InitializeIfNeeded(authorized, lockup)) => {
+            if invoke_context
+                .feature_set
+                .is_active(&feature_set::stake_initialize_if_needed_instruction::id())
+            {
+                let mut me = get_stake_account()?;
+                let rent =
+                    get_sysvar_with_account_check::rent(invoke_context, instruction_context, 1)?;
+                initialize_if_needed(&mut me, &authorized, &lockup, &rent)
+            } else {
+                Err(InstructionError::InvalidInstructionData)
+            }
+        }
+        Ok(StakeInstruction::UpdateCustodian(new_custodian)) => {
+            if invoke_context
+                .feature_set
+                .is_active(&feature_set::stake_update_custodian_instruction::id())
+            {
+                let mut me = get_stake_account()?;
+                update_custodian(&mut me, &new_custodian, &signers)
+            } else {
+                Err(InstructionError::InvalidInstructionData)
+            }
+        }
+        Ok(StakeInstruction::ReplenishRewardsPool(lamports)) => {
+            if invoke_context
+                .feature_set
+                .is_active(&feature_set::stake_replenish_rewards_pool_instruction::id())
+            {
+                instruction_context.check_number_of_instruction_accounts(3)?;
+                replenish_rewards_pool(invoke_context, lamports)
+            } else {
+                Err(InstructionError::InvalidInstructionData)
+            }
+        }
         Err(err) => Err(err),
     }
 });
@@ -376,7 +486,7 @@ mod tests {
     use {
         super::*,
         crate::{
-            config,
+            config, rewards_pools,
             stake_state::{
                 authorized_from, create_stake_history_from_delegations, from, new_stake,
                 stake_from, Delegation, Meta, Stake, StakeStateV2,
@@ -384,17 +494,21 @@ mod tests {
         },
         assert_matches::assert_matches,
         bincode::serialize,
-        solana_program_runtime::invoke_context::mock_process_instruction,
+        solana_program_runtime::{
+            invoke_context::{mock_process_instruction, mock_process_instruction_with_metering},
+            loaded_programs::{LoadedProgram, LoadedProgramsForTxBatch},
+        },
         solana_sdk::{
             account::{
-                create_account_shared_data_for_test, AccountSharedData, ReadableAccount,
-                WritableAccount,
+                create_account_shared_data_for_test, AccountBuilder, AccountSharedData,
+                ReadableAccount, WritableAccount,
             },
             account_utils::StateMut,
             clock::{Clock, Epoch, UnixTimestamp},
             epoch_schedule::EpochSchedule,
             feature_set::FeatureSet,
             instruction::{AccountMeta, Instruction},
+            native_loader,
             pubkey::Pubkey,
             rent::Rent,
             stake::{
@@ -402,7 +516,7 @@ mod tests {
                 instruction::{
                     self, authorize_checked, authorize_checked_with_seed, initialize_checked,
                     set_lockup_checked, AuthorizeCheckedWithSeedArgs, AuthorizeWithSeedArgs,
-                    LockupArgs, StakeError,
+                    LockupArgs, LockupCheckedArgs, StakeError,
                 },
                 stake_flags::StakeFlags,
                 state::{
@@ -440,11 +554,11 @@ mod tests {
     }
 
     fn create_default_account() -> AccountSharedData {
-        AccountSharedData::new(0, 0, &Pubkey::new_unique())
+        AccountBuilder::new().owner(&Pubkey::new_unique()).build()
     }
 
     fn create_default_stake_account() -> AccountSharedData {
-        AccountSharedData::new(0, 0, &id())
+        AccountBuilder::new().owner(&id()).build()
     }
 
     fn invalid_stake_state_pubkey() -> Pubkey {
@@ -485,6 +599,49 @@ mod tests {
         )
     }
 
+    /// Like `process_instruction`, with the system program loaded so that the
+    /// stake program can invoke it.
+    fn process_instruction_with_system_program(
+        feature_set: Arc<FeatureSet>,
+        instruction: &Instruction,
+        mut transaction_accounts: Vec<(Pubkey, AccountSharedData)>,
+        expected_result: Result<(), InstructionError>,
+    ) -> Vec<AccountSharedData> {
+        let mut system_program_account = AccountSharedData::new(1, 0, &native_loader::id());
+        system_program_account.set_executable(true);
+        transaction_accounts.push((system_program::id(), system_program_account));
+        mock_process_instruction(
+            &id(),
+            Vec::new(),
+            &instruction.data,
+            transaction_accounts,
+            instruction.accounts.clone(),
+            expected_result,
+            Entrypoint::vm,
+            |invoke_context| {
+                invoke_context.feature_set = Arc::clone(&feature_set);
+                let mut programs_loaded_for_tx_batch = LoadedProgramsForTxBatch::default();
+                programs_loaded_for_tx_batch.replenish(
+                    id(),
+                    Arc::new(LoadedProgram::new_builtin(0, 0, Entrypoint::vm)),
+                );
+                programs_loaded_for_tx_batch.replenish(
+                    system_program::id(),
+                    Arc::new(LoadedProgram::new_builtin(
+                        0,
+                        0,
+                        solana_system_program::system_processor::Entrypoint::vm,
+                    )),
+                );
+                // The invoke context borrows the programs for longer than
+                // this closure runs
+                invoke_context.programs_loaded_for_tx_batch =
+                    Box::leak(Box::new(programs_loaded_for_tx_batch));
+            },
+            |_invoke_context| {},
+        )
+    }
+
     fn process_instruction_as_one_arg(
         feature_set: Arc<FeatureSet>,
         instruction: &Instruction,
@@ -516,13 +673,17 @@ mod tests {
                     } else if rent::check_id(pubkey) {
                         create_account_shared_data_for_test(&Rent::default())
                     } else if *pubkey == invalid_stake_state_pubkey() {
-                        AccountSharedData::new(0, 0, &id())
+                        create_default_stake_account()
                     } else if *pubkey == invalid_vote_state_pubkey() {
-                        AccountSharedData::new(0, 0, &solana_vote_program::id())
+                        AccountBuilder::new()
+                            .owner(&solana_vote_program::id())
+                            .build()
                     } else if *pubkey == spoofed_stake_state_pubkey() {
-                        AccountSharedData::new(0, 0, &spoofed_stake_program_id())
+                        AccountBuilder::new()
+                            .owner(&spoofed_stake_program_id())
+                            .build()
                     } else {
-                        AccountSharedData::new(0, 0, &id())
+                        create_default_stake_account()
                     },
                 )
             })
@@ -577,7 +738,7 @@ mod tests {
             Arc::clone(&feature_set),
             &instruction::initialize(
                 &Pubkey::new_unique(),
-                &Authorized::default(),
+                &Authorized::auto(&Pubkey::new_unique()),
                 &Lockup::default(),
             ),
             Err(InstructionError::InvalidAccountData),
@@ -695,7 +856,7 @@ mod tests {
             Arc::clone(&feature_set),
             &instruction::initialize(
                 &spoofed_stake_state_pubkey(),
-                &Authorized::default(),
+                &Authorized::auto(&Pubkey::new_unique()),
                 &Lockup::default(),
             ),
             Err(InstructionError::InvalidAccountOwner),
@@ -816,6 +977,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_stake_process_instruction_trailing_bytes() {
+        let mut data = serialize(&StakeInstruction::Deactivate).unwrap();
+        data.push(0);
+
+        // Ignored, so the instruction goes on to look for its accounts
+        let mut feature_set = FeatureSet::all_enabled();
+        feature_set.deactivate(&feature_set::reject_noncanonical_stake_instruction_data::id());
+        process_instruction(
+            Arc::new(feature_set),
+            &data,
+            Vec::new(),
+            Vec::new(),
+            Err(InstructionError::NotEnoughAccountKeys),
+        );
+
+        process_instruction(
+            feature_set_all_enabled(),
+            &data,
+            Vec::new(),
+            Vec::new(),
+            Err(InstructionError::InvalidInstructionData),
+        );
+    }
+
     #[test_case(feature_set_old_warmup_cooldown_no_minimum_delegation(); "old_warmup_cooldown_no_min_delegation")]
     #[test_case(feature_set_old_warmup_cooldown(); "old_warmup_cooldown")]
     #[test_case(feature_set_all_enabled(); "all_enabled")]
@@ -846,7 +1032,7 @@ mod tests {
         process_instruction(
             Arc::clone(&feature_set),
             &serialize(&StakeInstruction::Initialize(
-                Authorized::default(),
+                Authorized::auto(&stake_address),
                 Lockup::default(),
             ))
             .unwrap(),
@@ -859,7 +1045,7 @@ mod tests {
         process_instruction(
             Arc::clone(&feature_set),
             &serialize(&StakeInstruction::Initialize(
-                Authorized::default(),
+                Authorized::auto(&stake_address),
                 Lockup::default(),
             ))
             .unwrap(),
@@ -876,7 +1062,7 @@ mod tests {
         process_instruction(
             Arc::clone(&feature_set),
             &serialize(&StakeInstruction::Initialize(
-                Authorized::default(),
+                Authorized::auto(&stake_address),
                 Lockup::default(),
             ))
             .unwrap(),
@@ -1554,6 +1740,270 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_stake_initialize_if_needed() {
+        let feature_set = feature_set_all_enabled();
+        let rent = Rent::default();
+        let rent_exempt_reserve = rent.minimum_balance(StakeStateV2::size_of());
+        let stake_address = solana_sdk::pubkey::new_rand();
+        let stake_account =
+            AccountSharedData::new(rent_exempt_reserve, StakeStateV2::size_of(), &id());
+        let authorized = Authorized::auto(&stake_address);
+        let lockup = Lockup {
+            epoch: 1,
+            unix_timestamp: 0,
+            custodian: solana_sdk::pubkey::new_rand(),
+        };
+        let instruction = instruction::initialize_if_needed(&stake_address, &authorized, &lockup);
+        let mut transaction_accounts = vec![
+            (stake_address, stake_account),
+            (rent::id(), create_account_shared_data_for_test(&rent)),
+        ];
+
+        // initializes an uninitialized account
+        let accounts = process_instruction(
+            Arc::clone(&feature_set),
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts.clone(),
+            Ok(()),
+        );
+        let initialized = StakeStateV2::Initialized(Meta {
+            authorized,
+            rent_exempt_reserve,
+            lockup,
+        });
+        assert_eq!(from(&accounts[0]).unwrap(), initialized);
+
+        // 2nd time is a no-op
+        transaction_accounts[0] = (stake_address, accounts[0].clone());
+        let accounts = process_instruction(
+            Arc::clone(&feature_set),
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts.clone(),
+            Ok(()),
+        );
+        assert_eq!(accounts[0], transaction_accounts[0].1);
+
+        // so is a delegated account with the same meta
+        let mut stake_account = accounts[0].clone();
+        stake_account
+            .set_state(&just_stake(initialized.meta().unwrap(), 1))
+            .unwrap();
+        transaction_accounts[0] = (stake_address, stake_account);
+        process_instruction(
+            Arc::clone(&feature_set),
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts.clone(),
+            Ok(()),
+        );
+
+        // different authorities or lockup fail, as with `Initialize`
+        transaction_accounts[0] = (stake_address, accounts[0].clone());
+        for instruction in [
+            instruction::initialize_if_needed(
+                &stake_address,
+                &Authorized::auto(&solana_sdk::pubkey::new_rand()),
+                &lockup,
+            ),
+            instruction::initialize_if_needed(&stake_address, &authorized, &Lockup::default()),
+        ] {
+            process_instruction(
+                Arc::clone(&feature_set),
+                &instruction.data,
+                transaction_accounts.clone(),
+                instruction.accounts.clone(),
+                Err(InstructionError::InvalidAccountData),
+            );
+        }
+
+        // rejected before the feature is activated
+        let mut feature_set = FeatureSet::all_enabled();
+        feature_set.deactivate(&feature_set::stake_initialize_if_needed_instruction::id());
+        process_instruction(
+            Arc::new(feature_set),
+            &instruction.data,
+            transaction_accounts,
+            instruction.accounts,
+            Err(InstructionError::InvalidInstructionData),
+        );
+    }
+
+    #[test]
+    fn test_sanitize_stake_instruction() {
+        for instruction in [
+            StakeInstruction::Withdraw(0),
+            StakeInstruction::Authorize(Pubkey::default(), StakeAuthorize::Withdrawer),
+            StakeInstruction::SetLockupChecked(LockupCheckedArgs {
+                unix_timestamp: Some(-1),
+                epoch: None,
+            }),
+        ] {
+            let instruction_data = serialize(&instruction).unwrap();
+            // rejected before any account is read
+            process_instruction(
+                feature_set_all_enabled(),
+                &instruction_data,
+                Vec::new(),
+                Vec::new(),
+                Err(InstructionError::InvalidInstructionData),
+            );
+
+            let mut feature_set = FeatureSet::all_enabled();
+            feature_set.deactivate(&feature_set::sanitize_stake_instructions::id());
+            process_instruction(
+                Arc::new(feature_set),
+                &instruction_data,
+                Vec::new(),
+                Vec::new(),
+                Err(InstructionError::NotEnoughAccountKeys),
+            );
+        }
+    }
+
+    #[test]
+    fn test_estimated_compute_units() {
+        let instruction = instruction::get_minimum_delegation();
+        let (result, _accounts, metering) = mock_process_instruction_with_metering(
+            &id(),
+            Vec::new(),
+            &instruction.data,
+            Vec::new(),
+            instruction.accounts,
+            Entrypoint::vm,
+            |_invoke_context| {},
+            |_invoke_context| {},
+        );
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            metering.compute_units_consumed,
+            StakeInstruction::GetMinimumDelegation.estimated_compute_units()
+        );
+    }
+
+    #[test]
+    fn test_update_custodian() {
+        let feature_set = feature_set_all_enabled();
+        let stake_address = solana_sdk::pubkey::new_rand();
+        let custodian_address = solana_sdk::pubkey::new_rand();
+        let new_custodian_address = solana_sdk::pubkey::new_rand();
+        let withdrawer_address = solana_sdk::pubkey::new_rand();
+        let lockup = Lockup {
+            unix_timestamp: 1,
+            epoch: 1,
+            custodian: custodian_address,
+        };
+        let meta = Meta {
+            lockup,
+            ..Meta::auto(&withdrawer_address)
+        };
+        let stake_account = AccountSharedData::new_data_with_space(
+            42,
+            &StakeStateV2::Initialized(meta),
+            StakeStateV2::size_of(),
+            &id(),
+        )
+        .unwrap();
+        let transaction_accounts = vec![
+            (stake_address, stake_account.clone()),
+            (custodian_address, AccountSharedData::default()),
+        ];
+        let instruction = instruction::update_custodian(
+            &stake_address,
+            &custodian_address,
+            &new_custodian_address,
+        );
+
+        // the custodian rotates itself, the schedule is untouched
+        let accounts = process_instruction(
+            Arc::clone(&feature_set),
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts.clone(),
+            Ok(()),
+        );
+        assert_eq!(
+            from(&accounts[0]).unwrap(),
+            StakeStateV2::Initialized(Meta {
+                lockup: Lockup {
+                    custodian: new_custodian_address,
+                    ..lockup
+                },
+                ..meta
+            }),
+        );
+
+        // and on a delegated stake
+        let mut delegated_account = stake_account.clone();
+        delegated_account.set_state(&just_stake(meta, 42)).unwrap();
+        let accounts = process_instruction(
+            Arc::clone(&feature_set),
+            &instruction.data,
+            vec![
+                (stake_address, delegated_account),
+                (custodian_address, AccountSharedData::default()),
+            ],
+            instruction.accounts.clone(),
+            Ok(()),
+        );
+        assert_eq!(
+            from(&accounts[0]).unwrap().meta().unwrap().lockup.custodian,
+            new_custodian_address
+        );
+
+        // nobody else can, not even the withdrawer
+        let instruction = instruction::update_custodian(
+            &stake_address,
+            &withdrawer_address,
+            &new_custodian_address,
+        );
+        process_instruction(
+            Arc::clone(&feature_set),
+            &instruction.data,
+            vec![
+                (stake_address, stake_account.clone()),
+                (withdrawer_address, AccountSharedData::default()),
+            ],
+            instruction.accounts.clone(),
+            Err(InstructionError::MissingRequiredSignature),
+        );
+
+        // uninitialized stakes have no custodian
+        let instruction = instruction::update_custodian(
+            &stake_address,
+            &custodian_address,
+            &new_custodian_address,
+        );
+        let mut uninitialized_transaction_accounts = transaction_accounts.clone();
+        uninitialized_transaction_accounts[0].1 = AccountSharedData::new_data_with_space(
+            42,
+            &StakeStateV2::Uninitialized,
+            StakeStateV2::size_of(),
+            &id(),
+        )
+        .unwrap();
+        process_instruction(
+            Arc::clone(&feature_set),
+            &instruction.data,
+            uninitialized_transaction_accounts,
+            instruction.accounts.clone(),
+            Err(InstructionError::InvalidAccountData),
+        );
+
+        // rejected before the feature is activated
+        let mut feature_set = FeatureSet::all_enabled();
+        feature_set.deactivate(&feature_set::stake_update_custodian_instruction::id());
+        process_instruction(
+            Arc::new(feature_set),
+            &instruction.data,
+            transaction_accounts,
+            instruction.accounts,
+            Err(InstructionError::InvalidInstructionData),
+        );
+    }
+
     #[test_case(feature_set_old_warmup_cooldown_no_minimum_delegation(); "old_warmup_cooldown_no_min_delegation")]
     #[test_case(feature_set_old_warmup_cooldown(); "old_warmup_cooldown")]
     #[test_case(feature_set_all_enabled(); "all_enabled")]
@@ -7912,4 +8362,88 @@ mod tests {
             Err(StakeError::RedelegateToSameVoteAccount.into()),
         );
     }
+
+    #[test]
+    fn test_replenish_rewards_pool() {
+        let feature_set = feature_set_all_enabled();
+        let (pool_address, pool_account) = rewards_pools::create_genesis_accounts(100).remove(0);
+        let pool_account = AccountSharedData::from(pool_account);
+        let funder_address = solana_sdk::pubkey::new_rand();
+        let funder_account = AccountSharedData::new(1_000, 0, &system_program::id());
+        let transaction_accounts = vec![
+            (pool_address, pool_account.clone()),
+            (funder_address, funder_account.clone()),
+        ];
+        let instruction = instruction::replenish_rewards_pool(&pool_address, &funder_address, 42);
+
+        let accounts = process_instruction_with_system_program(
+            Arc::clone(&feature_set),
+            &instruction,
+            transaction_accounts.clone(),
+            Ok(()),
+        );
+        assert_eq!(accounts[0].lamports(), 142);
+        assert_eq!(accounts[0].data(), pool_account.data());
+        assert_eq!(accounts[1].lamports(), 958);
+
+        // the funder has to sign
+        let mut unsigned_instruction = instruction.clone();
+        unsigned_instruction.accounts[1].is_signer = false;
+        process_instruction_with_system_program(
+            Arc::clone(&feature_set),
+            &unsigned_instruction,
+            transaction_accounts.clone(),
+            Err(InstructionError::MissingRequiredSignature),
+        );
+
+        // a pool id that does not hold a rewards pool
+        let mut stake_account = pool_account.clone();
+        stake_account
+            .set_state(&StakeStateV2::Initialized(Meta::auto(&funder_address)))
+            .unwrap();
+        process_instruction_with_system_program(
+            Arc::clone(&feature_set),
+            &instruction,
+            vec![
+                (pool_address, stake_account),
+                (funder_address, funder_account.clone()),
+            ],
+            Err(InstructionError::InvalidAccountData),
+        );
+
+        // a pool id not owned by the stake program
+        let mut spoofed_pool_account = pool_account.clone();
+        spoofed_pool_account.set_owner(spoofed_stake_program_id());
+        process_instruction_with_system_program(
+            Arc::clone(&feature_set),
+            &instruction,
+            vec![
+                (pool_address, spoofed_pool_account),
+                (funder_address, funder_account.clone()),
+            ],
+            Err(InstructionError::IncorrectProgramId),
+        );
+
+        // a rewards pool account at an address that is not a pool id
+        let other_address = solana_sdk::pubkey::new_rand();
+        process_instruction_with_system_program(
+            Arc::clone(&feature_set),
+            &instruction::replenish_rewards_pool(&other_address, &funder_address, 42),
+            vec![
+                (other_address, pool_account.clone()),
+                (funder_address, funder_account),
+            ],
+            Err(InstructionError::InvalidArgument),
+        );
+
+        // rejected until the feature is activated
+        let mut feature_set = FeatureSet::all_enabled();
+        feature_set.deactivate(&feature_set::stake_replenish_rewards_pool_instruction::id());
+        process_instruction_with_system_program(
+            Arc::new(feature_set),
+            &instruction,
+            transaction_accounts,
+            Err(InstructionError::InvalidInstructionData),
+        );
+    }
 }
@@ -9,6 +9,7 @@
 )]
 pub use solana_sdk::stake::state::*;
 use {
+    crate::rewards_pools::check_rewards_pool_data,
     solana_program_runtime::{ic_msg, invoke_context::InvokeContext},
     solana_sdk::{
         account::{AccountSharedData, ReadableAccount},
@@ -25,6 +26,7 @@ use {
             tools::{acceptable_reference_epoch_credits, eligible_for_deactivate_delinquent},
         },
         stake_history::{StakeHistory, StakeHistoryEntry},
+        system_instruction,
         transaction_context::{
             BorrowedAccount, IndexOfAccount, InstructionContext, TransactionContext,
         },
@@ -170,6 +172,28 @@ pub fn initialize(
     }
 }
 
+/// Like `initialize`, but succeeds without changes if the stake account is
+/// already initialized, or delegated, with the same `authorized` and `lockup`.
+pub fn initialize_if_needed(
+    stake_account: &mut BorrowedAccount,
+    authorized: &Authorized,
+    lockup: &Lockup,
+    rent: &Rent,
+) -> Result<(), InstructionError> {
+    if stake_account.get_data().len() != StakeStateV2::size_of() {
+        return Err(InstructionError::InvalidAccountData);
+    }
+
+    match stake_account.get_state()? {
+        StakeStateV2::Initialized(meta) | StakeStateV2::Stake(meta, _, _)
+            if meta.authorized == *authorized && meta.lockup == *lockup =>
+        {
+            Ok(())
+        }
+        _ => initialize(stake_account, authorized, lockup, rent),
+    }
+}
+
 /// Authorize the given pubkey to manage stake (deactivate, withdraw). This may be called
 /// multiple times, but will implicitly withdraw authorization from the previously authorized
 /// staker. The default staker is the owner of the stake account's pubkey.
@@ -369,6 +393,54 @@ pub fn set_lockup(
     }
 }
 
+pub fn update_custodian(
+    stake_account: &mut BorrowedAccount,
+    new_custodian: &Pubkey,
+    signers: &HashSet<Pubkey>,
+) -> Result<(), InstructionError> {
+    match stake_account.get_state()? {
+        StakeStateV2::Initialized(mut meta) => {
+            meta.update_custodian(new_custodian, signers)?;
+            stake_account.set_state(&StakeStateV2::Initialized(meta))
+        }
+        StakeStateV2::Stake(mut meta, stake, stake_flags) => {
+            meta.update_custodian(new_custodian, signers)?;
+            stake_account.set_state(&StakeStateV2::Stake(meta, stake, stake_flags))
+        }
+        _ => Err(InstructionError::InvalidAccountData),
+    }
+}
+
+/// Moves `lamports` from the funding account, instruction account 1, into
+/// the rewards pool at instruction account 0 with a system program transfer,
+/// after checking that the pool is genuine.
+pub fn replenish_rewards_pool(
+    invoke_context: &mut InvokeContext,
+    lamports: u64,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let pool_pubkey = {
+        let pool = instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+        if let Err(err) = check_rewards_pool_data(pool.get_key(), pool.get_owner(), pool.get_data())
+        {
+            ic_msg!(invoke_context, "{} is not a rewards pool", pool.get_key());
+            return Err(err);
+        }
+        *pool.get_key()
+    };
+    if !instruction_context.is_instruction_account_signer(1)? {
+        return Err(InstructionError::MissingRequiredSignature);
+    }
+    let funder_pubkey = *transaction_context.get_key_of_account_at_index(
+        instruction_context.get_index_of_instruction_account_in_transaction(1)?,
+    )?;
+    invoke_context.native_invoke(
+        system_instruction::transfer(&funder_pubkey, &pool_pubkey, lamports).into(),
+        &[],
+    )
+}
+
 pub fn split(
     invoke_context: &InvokeContext,
     transaction_context: &TransactionContext,
@@ -9,6 +9,7 @@ use {
         sysvar_cache::get_sysvar_with_account_check,
     },
     solana_sdk::{
+        compute_units::BuiltinComputeUnits,
         instruction::InstructionError,
         nonce,
         program_utils::limited_deserialize,
@@ -295,7 +296,7 @@ fn transfer_with_seed(
     )
 }
 
-pub const DEFAULT_COMPUTE_UNITS: u64 = 150;
+pub const DEFAULT_COMPUTE_UNITS: u64 = SystemInstruction::DEFAULT_COMPUTE_UNITS;
 
 declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context| {
     let transaction_context = &invoke_context.transaction_context;
@@ -560,7 +561,8 @@ mod tests {
         crate::{get_system_account_kind, SystemAccountKind},
         bincode::serialize,
         solana_program_runtime::{
-            invoke_context::mock_process_instruction, with_mock_invoke_context,
+            invoke_context::{mock_process_instruction, mock_process_instruction_with_metering},
+            with_mock_invoke_context,
         },
     };
 
@@ -605,6 +607,32 @@ mod tests {
         account::create_account_shared_data_for_test(&Rent::free())
     }
 
+    #[test]
+    fn test_estimated_compute_units() {
+        let from = Pubkey::new_unique();
+        let to = Pubkey::new_unique();
+        let instruction = system_instruction::transfer(&from, &to, 50);
+        let (result, _accounts, metering) = mock_process_instruction_with_metering(
+            &system_program::id(),
+            Vec::new(),
+            &instruction.data,
+            vec![
+                (from, AccountSharedData::new(100, 0, &system_program::id())),
+                (to, AccountSharedData::new(1, 0, &system_program::id())),
+            ],
+            instruction.accounts,
+            Entrypoint::vm,
+            |_invoke_context| {},
+            |_invoke_context| {},
+        );
+        assert_eq!(result, Ok(()));
+        let instruction: SystemInstruction = bincode::deserialize(&instruction.data).unwrap();
+        assert_eq!(
+            metering.compute_units_consumed,
+            instruction.estimated_compute_units()
+        );
+    }
+
     #[test]
     fn test_create_account() {
         let new_owner = Pubkey::from([9; 32]);
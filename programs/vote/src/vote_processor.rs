@@ -9,6 +9,7 @@ use {
         sysvar_cache::get_sysvar_with_account_check,
     },
     solana_sdk::{
+        compute_units::BuiltinComputeUnits,
         instruction::InstructionError,
         program_utils::limited_deserialize,
         pubkey::Pubkey,
@@ -51,7 +52,7 @@ fn process_authorize_with_seed_instruction(
 
 // Citing `runtime/src/block_cost_limit.rs`, vote has statically defined 2100
 // units; can consume based on instructions in the future like `bpf_loader` does.
-pub const DEFAULT_COMPUTE_UNITS: u64 = 2_100;
+pub const DEFAULT_COMPUTE_UNITS: u64 = VoteInstruction::DEFAULT_COMPUTE_UNITS;
 
 declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context| {
     let transaction_context = &invoke_context.transaction_context;
@@ -251,7 +252,9 @@ mod tests {
             },
         },
         bincode::serialize,
-        solana_program_runtime::invoke_context::mock_process_instruction,
+        solana_program_runtime::invoke_context::{
+            mock_process_instruction, mock_process_instruction_with_metering,
+        },
         solana_sdk::{
             account::{self, Account, AccountSharedData, ReadableAccount},
             account_utils::StateMut,
@@ -501,6 +504,34 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_estimated_compute_units() {
+        let (vote_pubkey, _authorized_voter, authorized_withdrawer, vote_account) =
+            create_test_account_with_authorized();
+        let node_pubkey = Pubkey::new_unique();
+        let instruction =
+            update_validator_identity(&vote_pubkey, &authorized_withdrawer, &node_pubkey);
+        let (result, _accounts, metering) = mock_process_instruction_with_metering(
+            &id(),
+            Vec::new(),
+            &instruction.data,
+            vec![
+                (vote_pubkey, vote_account),
+                (node_pubkey, AccountSharedData::default()),
+                (authorized_withdrawer, AccountSharedData::default()),
+            ],
+            instruction.accounts,
+            Entrypoint::vm,
+            |_invoke_context| {},
+            |_invoke_context| {},
+        );
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            metering.compute_units_consumed,
+            VoteInstruction::UpdateValidatorIdentity.estimated_compute_units()
+        );
+    }
+
     #[test]
     fn test_vote_process_instruction_decode_bail() {
         process_instruction(
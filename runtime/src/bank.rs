@@ -5998,7 +5998,7 @@ impl Bank {
                     self.add_builtin(
                         builtin.program_id,
                         builtin.name.to_string(),
-                        LoadedProgram::new_builtin(0, builtin.name.len(), builtin.entrypoint),
+                        builtin.new_loaded_program(0),
                     );
                 }
             }
@@ -7351,10 +7351,8 @@ impl Bank {
                     self.add_builtin(
                         builtin.program_id,
                         builtin.name.to_string(),
-                        LoadedProgram::new_builtin(
+                        builtin.new_loaded_program(
                             self.feature_set.activated_slot(&feature_id).unwrap_or(0),
-                            builtin.name.len(),
-                            builtin.entrypoint,
                         ),
                     );
                 }
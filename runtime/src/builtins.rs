@@ -1,7 +1,12 @@
 use {
-    solana_program_runtime::invoke_context::BuiltinFunctionWithContext,
+    solana_program_runtime::{
+        invoke_context::BuiltinFunctionWithContext, loaded_programs::LoadedProgram,
+    },
     solana_sdk::{
-        bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable, feature_set, pubkey::Pubkey,
+        bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable,
+        clock::Slot,
+        feature_set::{self, FeatureSet},
+        pubkey::Pubkey,
     },
 };
 
@@ -13,6 +18,37 @@ pub struct BuiltinPrototype {
     pub entrypoint: BuiltinFunctionWithContext,
 }
 
+impl BuiltinPrototype {
+    /// Creates the program cache entry for this builtin, deployed at `slot`.
+    pub fn new_loaded_program(&self, slot: Slot) -> LoadedProgram {
+        LoadedProgram::new_builtin(slot, self.name.len(), self.entrypoint)
+    }
+
+    /// Returns the slot this builtin became available at under `feature_set`,
+    /// or `None` if its feature is not active.
+    pub fn enabled_slot(&self, feature_set: &FeatureSet) -> Option<Slot> {
+        match self.feature_id {
+            None => Some(0),
+            Some(feature_id) => feature_set.activated_slot(&feature_id),
+        }
+    }
+}
+
+/// Iterates over the [`BUILTINS`] enabled under `feature_set`, along with the
+/// slot each became available at.
+///
+/// Embedders that manage their own program cache can use this to register
+/// every builtin program at once rather than listing program ids by hand.
+pub fn enabled_builtins(
+    feature_set: &FeatureSet,
+) -> impl Iterator<Item = (&'static BuiltinPrototype, Slot)> + '_ {
+    BUILTINS.iter().filter_map(|builtin| {
+        builtin
+            .enabled_slot(feature_set)
+            .map(|slot| (builtin, slot))
+    })
+}
+
 impl std::fmt::Debug for BuiltinPrototype {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let mut builder = f.debug_struct("BuiltinPrototype");
@@ -107,4 +143,55 @@ pub static BUILTINS: &[BuiltinPrototype] = &[
         name: "loader_v4",
         entrypoint: solana_loader_v4_program::Entrypoint::vm,
     },
+    BuiltinPrototype {
+        feature_id: Some(feature_set::precompile_builtin_processors::id()),
+        program_id: solana_sdk::ed25519_program::id(),
+        name: "ed25519_program",
+        entrypoint: solana_precompile_program::Entrypoint::vm,
+    },
+    BuiltinPrototype {
+        feature_id: Some(feature_set::precompile_builtin_processors::id()),
+        program_id: solana_sdk::secp256k1_program::id(),
+        name: "secp256k1_program",
+        entrypoint: solana_precompile_program::Entrypoint::vm,
+    },
 ];
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::collections::HashSet};
+
+    #[test]
+    fn test_builtins_are_unique() {
+        let program_ids: HashSet<_> = BUILTINS.iter().map(|b| b.program_id).collect();
+        assert_eq!(program_ids.len(), BUILTINS.len());
+        let names: HashSet<_> = BUILTINS.iter().map(|b| b.name).collect();
+        assert_eq!(names.len(), BUILTINS.len());
+    }
+
+    #[test]
+    fn test_enabled_builtins() {
+        let mut feature_set = FeatureSet::default();
+        assert!(enabled_builtins(&feature_set)
+            .all(|(builtin, slot)| builtin.feature_id.is_none() && slot == 0));
+        assert_eq!(
+            enabled_builtins(&feature_set).count(),
+            BUILTINS.iter().filter(|b| b.feature_id.is_none()).count()
+        );
+
+        feature_set.activate(&feature_set::zk_token_sdk_enabled::id(), 42);
+        let (builtin, slot) = enabled_builtins(&feature_set)
+            .find(|(builtin, _)| builtin.feature_id.is_some())
+            .unwrap();
+        assert_eq!(
+            builtin.program_id,
+            solana_zk_token_sdk::zk_token_proof_program::id()
+        );
+        assert_eq!(slot, 42);
+
+        assert_eq!(
+            enabled_builtins(&FeatureSet::all_enabled()).count(),
+            BUILTINS.len()
+        );
+    }
+}
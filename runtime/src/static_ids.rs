@@ -4,13 +4,11 @@ use {
     solana_sdk::pubkey::Pubkey,
 };
 
-lazy_static! {
-    /// Vector of static token & mint IDs
-    pub static ref STATIC_IDS: Vec<Pubkey> = vec![
-        inline_spl_associated_token_account::id(),
-        inline_spl_associated_token_account::program_v1_1_0::id(),
-        inline_spl_token::id(),
-        inline_spl_token::native_mint::id(),
-        inline_spl_token_2022::id(),
-    ];
-}
+/// Static token & mint IDs
+pub const STATIC_IDS: [Pubkey; 5] = [
+    inline_spl_associated_token_account::ID,
+    inline_spl_associated_token_account::program_v1_1_0::ID,
+    inline_spl_token::ID,
+    inline_spl_token::native_mint::ID,
+    inline_spl_token_2022::ID,
+];
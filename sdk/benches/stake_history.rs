@@ -0,0 +1,56 @@
+#![feature(test)]
+
+extern crate test;
+use {
+    solana_sdk::{
+        pubkey::Pubkey,
+        stake::state::Delegation,
+        stake_history::{StakeHistory, StakeHistoryEntry, MAX_ENTRIES},
+    },
+    test::Bencher,
+};
+
+fn full_stake_history() -> StakeHistory {
+    let mut stake_history = StakeHistory::default();
+    for epoch in 0..MAX_ENTRIES as u64 {
+        stake_history.add(
+            epoch,
+            StakeHistoryEntry {
+                effective: 1_000_000_000 * (epoch + 1),
+                activating: 100_000_000,
+                deactivating: 100_000_000,
+            },
+        );
+    }
+    stake_history
+}
+
+#[bench]
+fn bench_stake_history_get(b: &mut Bencher) {
+    let stake_history = full_stake_history();
+    b.iter(|| {
+        for epoch in 0..MAX_ENTRIES as u64 {
+            test::black_box(stake_history.get(epoch));
+        }
+    });
+}
+
+#[bench]
+fn bench_stake_history_iter_range(b: &mut Bencher) {
+    let stake_history = full_stake_history();
+    b.iter(|| test::black_box(stake_history.iter_range(100..200).count()));
+}
+
+#[bench]
+fn bench_delegation_warmup_cooldown(b: &mut Bencher) {
+    let stake_history = full_stake_history();
+    let mut delegation = Delegation::new(&Pubkey::new_unique(), 50_000_000_000, 10);
+    delegation.deactivation_epoch = 300;
+    b.iter(|| {
+        test::black_box(delegation.stake_activating_and_deactivating(
+            MAX_ENTRIES as u64 - 1,
+            &stake_history,
+            None,
+        ))
+    });
+}
@@ -72,8 +72,8 @@ fn deprecated_id_to_tokens(
     tokens: &mut proc_macro2::TokenStream,
 ) {
     tokens.extend(quote! {
-        /// The static program ID.
-        pub static ID: #pubkey_type = #id;
+        /// The const program ID.
+        pub const ID: #pubkey_type = #id;
 
         /// Returns `true` if given pubkey is the program ID.
         #[deprecated()]
@@ -83,7 +83,7 @@ fn deprecated_id_to_tokens(
 
         /// Returns the program ID.
         #[deprecated()]
-        pub fn id() -> #pubkey_type {
+        pub const fn id() -> #pubkey_type {
             ID
         }
 
@@ -445,3 +445,105 @@ pub fn derive_clone_zeroed(input: proc_macro::TokenStream) -> proc_macro::TokenS
     }
     .into()
 }
+
+/// The path of the `solana_program` crate given with a
+/// `#[<attr_name>(crate = "...")]` attribute, or `::solana_program` without
+/// one.
+fn parse_crate_path(attrs: &[syn::Attribute], attr_name: &str) -> Result<Path> {
+    let mut crate_path = syn::parse_quote!(::solana_program);
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident(attr_name)) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                crate_path = meta.value()?.parse::<LitStr>()?.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("expected `crate = \"...\"`"))
+            }
+        })?;
+    }
+    Ok(crate_path)
+}
+
+/// Implements `ConstPackedLen` for a struct or enum whose fields all
+/// implement it, summing the fields' packed lengths at compile time.
+///
+/// An enum takes one byte for its discriminant plus the packed length of its
+/// largest variant, matching borsh's worst-case `get_packed_len`.
+///
+/// The generated code refers to `::solana_program`. Crates that depend on
+/// `solana_sdk` instead add `#[const_packed_len(crate = "solana_sdk")]`.
+#[proc_macro_derive(ConstPackedLen, attributes(const_packed_len))]
+pub fn derive_const_packed_len(input: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(input as syn::DeriveInput);
+    let crate_path = match parse_crate_path(&input.attrs, "const_packed_len") {
+        Ok(crate_path) => crate_path,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let trait_path = quote! { #crate_path::borsh1::ConstPackedLen };
+
+    let fields_len = |fields: &syn::Fields| {
+        let lens = fields.iter().map(|field| {
+            let ty = &field.ty;
+            quote! { + <#ty as #trait_path>::PACKED_LEN }
+        });
+        quote! { (0 #(#lens)*) }
+    };
+    let packed_len = match &input.data {
+        syn::Data::Struct(data) => fields_len(&data.fields),
+        syn::Data::Enum(data) => {
+            let max = data
+                .variants
+                .iter()
+                .map(|variant| fields_len(&variant.fields))
+                .fold(quote! { 0 }, |max, len| {
+                    quote! { #crate_path::borsh1::const_max(#max, #len) }
+                });
+            quote! { (1 + #max) }
+        }
+        syn::Data::Union(data) => {
+            return syn::Error::new_spanned(
+                data.union_token,
+                "ConstPackedLen cannot be derived for unions",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    for param in input.generics.type_params_mut() {
+        param.bounds.push(syn::parse_quote!(#trait_path));
+    }
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        impl #impl_generics #trait_path for #name #ty_generics #where_clause {
+            const PACKED_LEN: usize = #packed_len;
+        }
+    }
+    .into()
+}
+
+/// Implements `AccountDiscriminator` for a type, with the type's name as its
+/// Anchor account name.
+///
+/// The generated code refers to `::solana_program`. Crates that depend on
+/// `solana_sdk` instead add `#[account_discriminator(crate = "solana_sdk")]`.
+#[proc_macro_derive(AccountDiscriminator, attributes(account_discriminator))]
+pub fn derive_account_discriminator(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    let crate_path = match parse_crate_path(&input.attrs, "account_discriminator") {
+        Ok(crate_path) => crate_path,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let name = &input.ident;
+    let account_name = name.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        impl #impl_generics #crate_path::discriminator::AccountDiscriminator
+            for #name #ty_generics #where_clause
+        {
+            const ACCOUNT_NAME: &'static str = #account_name;
+        }
+    }
+    .into()
+}
@@ -0,0 +1,54 @@
+//! Benchmarks for `hashv` and the hot paths built on it.
+//!
+//! To measure the gain from hardware acceleration, compare against the
+//! portable implementation with `cargo bench --bench hash --features sha2/force-soft`.
+
+use {
+    criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput},
+    solana_program::{
+        hash::{hash, hashv},
+        pubkey::Pubkey,
+    },
+};
+
+fn bench_hash(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hash");
+    for size in [32, 256, 1232, 64 * 1024] {
+        let data = vec![7u8; size];
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &data, |b, data| {
+            b.iter(|| hash(black_box(data)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_hashv(c: &mut Criterion) {
+    // The shape of a program address derivation: seeds, bump, program id and
+    // marker
+    let program_id = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let vals: [&[u8]; 5] = [
+        b"metadata",
+        owner.as_ref(),
+        &[255],
+        program_id.as_ref(),
+        b"ProgramDerivedAddress",
+    ];
+    c.bench_function("hashv_program_address", |b| {
+        b.iter(|| hashv(black_box(&vals)))
+    });
+}
+
+fn bench_find_program_address(c: &mut Criterion) {
+    let program_id = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    c.bench_function("find_program_address", |b| {
+        b.iter(|| {
+            Pubkey::find_program_address(&[b"metadata", black_box(owner.as_ref())], &program_id)
+        })
+    });
+}
+
+criterion_group!(benches, bench_hash, bench_hashv, bench_find_program_address);
+criterion_main!(benches);
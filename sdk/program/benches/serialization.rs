@@ -0,0 +1,94 @@
+use {
+    bincode::{deserialize, serialize},
+    criterion::{black_box, criterion_group, criterion_main, Criterion},
+    solana_program::{
+        borsh1::{get_instance_packed_len, get_packed_len},
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+        pubkey::Pubkey,
+        short_vec::{self, ShortU16, ShortVec},
+        stake::state::{Authorized, Lockup, Meta, StakeStateV2},
+    },
+    std::str::FromStr,
+};
+
+fn make_message() -> Message {
+    let payer = Pubkey::new_unique();
+    let instructions: Vec<_> = (0..4)
+        .map(|_| {
+            let accounts = (0..4)
+                .map(|_| AccountMeta::new(Pubkey::new_unique(), false))
+                .chain(std::iter::once(AccountMeta::new(payer, true)))
+                .collect();
+            Instruction::new_with_bytes(Pubkey::new_unique(), &[0; 32], accounts)
+        })
+        .collect();
+    Message::new_with_blockhash(&instructions, Some(&payer), &Hash::new_unique())
+}
+
+fn bench_message(c: &mut Criterion) {
+    let message = make_message();
+    let serialized = serialize(&message).unwrap();
+
+    c.bench_function("message_serialize", |b| {
+        b.iter(|| serialize(black_box(&message)).unwrap())
+    });
+    c.bench_function("message_deserialize", |b| {
+        b.iter(|| deserialize::<Message>(black_box(&serialized)).unwrap())
+    });
+    c.bench_function("message_hash", |b| b.iter(|| black_box(&message).hash()));
+}
+
+fn bench_short_vec(c: &mut Criterion) {
+    let short_vec = ShortVec(vec![0u8; 127]);
+    let serialized = serialize(&short_vec).unwrap();
+    let encoded_len = serialize(&ShortU16(u16::MAX)).unwrap();
+
+    c.bench_function("short_u16_encode", |b| {
+        b.iter(|| serialize(&ShortU16(black_box(u16::MAX))).unwrap())
+    });
+    c.bench_function("short_u16_decode", |b| {
+        b.iter(|| short_vec::decode_shortu16_len(black_box(&encoded_len)).unwrap())
+    });
+    c.bench_function("short_vec_encode", |b| {
+        b.iter(|| serialize(black_box(&short_vec)).unwrap())
+    });
+    c.bench_function("short_vec_decode", |b| {
+        b.iter(|| deserialize::<ShortVec<u8>>(black_box(&serialized)).unwrap())
+    });
+}
+
+fn bench_borsh_packed_len(c: &mut Criterion) {
+    let stake_state = StakeStateV2::Initialized(Meta {
+        rent_exempt_reserve: 42,
+        authorized: Authorized::auto(&Pubkey::new_unique()),
+        lockup: Lockup::default(),
+    });
+
+    c.bench_function("borsh_get_packed_len", |b| b.iter(get_packed_len::<Lockup>));
+    c.bench_function("borsh_get_instance_packed_len", |b| {
+        b.iter(|| get_instance_packed_len(black_box(&stake_state)).unwrap())
+    });
+}
+
+fn bench_pubkey_base58(c: &mut Criterion) {
+    let pubkey = Pubkey::new_unique();
+    let encoded = pubkey.to_string();
+
+    c.bench_function("pubkey_to_string", |b| {
+        b.iter(|| black_box(&pubkey).to_string())
+    });
+    c.bench_function("pubkey_from_str", |b| {
+        b.iter(|| Pubkey::from_str(black_box(&encoded)).unwrap())
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_message,
+    bench_short_vec,
+    bench_borsh_packed_len,
+    bench_pubkey_base58
+);
+criterion_main!(benches);
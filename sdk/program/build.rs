@@ -1 +0,0 @@
-../../frozen-abi/build.rs
\ No newline at end of file
@@ -0,0 +1,197 @@
+//! Declarative descriptions of the accounts an instruction takes.
+//!
+//! The `# Account references` section of an instruction's documentation lists
+//! its accounts in order, with their signer and writable flags. An
+//! [`AccountSpec`] per account states the same contract in code, so that the
+//! accounts of an instruction can be checked against it with
+//! [`check_account_metas`], and a mismatch reported by the index of the first
+//! offending account rather than by whatever the processor trips over.
+
+use {
+    crate::{
+        instruction::{AccountMeta, InstructionError},
+        pubkey::Pubkey,
+    },
+    thiserror::Error,
+};
+
+/// The contract for one account of an instruction
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AccountSpec {
+    pub is_signer: bool,
+    pub is_writable: bool,
+    /// The only address the account may have, for sysvars and other
+    /// well-known accounts
+    pub address: Option<Pubkey>,
+    /// Whether the account may be left out. Only trailing accounts may be
+    /// optional.
+    pub is_optional: bool,
+}
+
+impl AccountSpec {
+    /// A read-only account
+    pub const fn readonly() -> Self {
+        Self {
+            is_signer: false,
+            is_writable: false,
+            address: None,
+            is_optional: false,
+        }
+    }
+
+    /// A writable account
+    pub const fn writable() -> Self {
+        Self {
+            is_writable: true,
+            ..Self::readonly()
+        }
+    }
+
+    /// A read-only account that must sign
+    pub const fn signer() -> Self {
+        Self {
+            is_signer: true,
+            ..Self::readonly()
+        }
+    }
+
+    /// A read-only account at a fixed address, such as a sysvar
+    pub const fn address(address: Pubkey) -> Self {
+        Self {
+            address: Some(address),
+            ..Self::readonly()
+        }
+    }
+
+    /// The same account, but one that may be left out
+    pub const fn optional(self) -> Self {
+        Self {
+            is_optional: true,
+            ..self
+        }
+    }
+}
+
+/// The first way in which an instruction's accounts break their spec
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AccountSpecError {
+    #[error("expected at least {expected} accounts, got {actual}")]
+    NotEnoughAccounts { expected: usize, actual: usize },
+    #[error("account {index} should be {expected}, got {actual}")]
+    WrongAddress {
+        index: usize,
+        expected: Pubkey,
+        actual: Pubkey,
+    },
+    #[error("account {index} should be a signer")]
+    MissingSignature { index: usize },
+    #[error("account {index} should be writable")]
+    NotWritable { index: usize },
+}
+
+impl From<AccountSpecError> for InstructionError {
+    fn from(err: AccountSpecError) -> Self {
+        match err {
+            AccountSpecError::NotEnoughAccounts { .. } => Self::NotEnoughAccountKeys,
+            AccountSpecError::WrongAddress { .. } | AccountSpecError::NotWritable { .. } => {
+                Self::InvalidArgument
+            }
+            AccountSpecError::MissingSignature { .. } => Self::MissingRequiredSignature,
+        }
+    }
+}
+
+/// Checks `accounts` against `spec`, in order, reporting the first account
+/// that breaks it.
+///
+/// Every account that isn't optional must be present, and every account
+/// present must have the address, signer and writable flags its spec calls
+/// for. Accounts beyond the end of the spec are not checked.
+pub fn check_account_metas(
+    spec: &[AccountSpec],
+    accounts: &[AccountMeta],
+) -> Result<(), AccountSpecError> {
+    for (index, account_spec) in spec.iter().enumerate() {
+        let Some(account) = accounts.get(index) else {
+            if account_spec.is_optional {
+                break;
+            }
+            return Err(AccountSpecError::NotEnoughAccounts {
+                expected: spec.iter().take_while(|spec| !spec.is_optional).count(),
+                actual: accounts.len(),
+            });
+        };
+        if let Some(address) = account_spec.address {
+            if account.pubkey != address {
+                return Err(AccountSpecError::WrongAddress {
+                    index,
+                    expected: address,
+                    actual: account.pubkey,
+                });
+            }
+        }
+        if account_spec.is_signer && !account.is_signer {
+            return Err(AccountSpecError::MissingSignature { index });
+        }
+        if account_spec.is_writable && !account.is_writable {
+            return Err(AccountSpecError::NotWritable { index });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::sysvar};
+
+    #[test]
+    fn test_check_account_metas() {
+        const SPEC: &[AccountSpec] = &[
+            AccountSpec::writable(),
+            AccountSpec::address(sysvar::clock::ID),
+            AccountSpec::signer(),
+            AccountSpec::signer().optional(),
+        ];
+        let stake = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let mut accounts = vec![
+            AccountMeta::new(stake, false),
+            AccountMeta::new_readonly(sysvar::clock::ID, false),
+            AccountMeta::new_readonly(authority, true),
+        ];
+        assert_eq!(check_account_metas(SPEC, &accounts), Ok(()));
+
+        accounts.push(AccountMeta::new_readonly(authority, false));
+        assert_eq!(
+            check_account_metas(SPEC, &accounts),
+            Err(AccountSpecError::MissingSignature { index: 3 })
+        );
+        accounts[3].is_signer = true;
+        // Extra accounts are not checked
+        accounts.push(AccountMeta::new_readonly(Pubkey::new_unique(), false));
+        assert_eq!(check_account_metas(SPEC, &accounts), Ok(()));
+
+        assert_eq!(
+            check_account_metas(SPEC, &accounts[..2]),
+            Err(AccountSpecError::NotEnoughAccounts {
+                expected: 3,
+                actual: 2
+            })
+        );
+        accounts[1].pubkey = sysvar::rent::ID;
+        accounts[0].is_writable = false;
+        assert_eq!(
+            check_account_metas(SPEC, &accounts),
+            Err(AccountSpecError::NotWritable { index: 0 })
+        );
+        accounts[0].is_writable = true;
+        assert_eq!(
+            check_account_metas(SPEC, &accounts),
+            Err(AccountSpecError::WrongAddress {
+                index: 1,
+                expected: sysvar::clock::ID,
+                actual: sysvar::rent::ID,
+            })
+        );
+    }
+}
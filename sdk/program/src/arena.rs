@@ -0,0 +1,147 @@
+//! Instructions allocated from a [`Bump`] arena.
+//!
+//! Building an [`Instruction`] allocates a `Vec` for its account metas and
+//! another for its data, and dropping it frees both. Hosts that build and
+//! discard thousands of instructions per slot can instead allocate them from a
+//! caller-supplied arena with [`ArenaInstruction`], and free all of them at
+//! once by resetting or dropping the arena.
+//!
+//! Requires the `arena` feature, and is not available on-chain.
+//!
+//! # Examples
+//!
+//! ```
+//! use solana_program::{
+//!     arena::{ArenaInstruction, Bump},
+//!     instruction::AccountMeta,
+//!     pubkey::Pubkey,
+//! };
+//!
+//! let program_id = Pubkey::new_unique();
+//! let account = Pubkey::new_unique();
+//!
+//! let mut bump = Bump::new();
+//! for slot in 0..4u64 {
+//!     for i in 0..1000u64 {
+//!         let instruction = ArenaInstruction::new_with_bincode(
+//!             &bump,
+//!             program_id,
+//!             &(slot, i),
+//!             &[AccountMeta::new(account, false)],
+//!         );
+//!         assert_eq!(instruction.data.len(), 16);
+//!     }
+//!     // Frees every instruction built for this slot
+//!     bump.reset();
+//! }
+//! ```
+
+#![cfg(all(feature = "arena", not(target_os = "solana")))]
+
+pub use bumpalo::Bump;
+use {
+    crate::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+    serde::Serialize,
+};
+
+/// An [`Instruction`] whose account metas and data live in a [`Bump`] arena.
+///
+/// The arena never runs destructors, which is fine for the plain data held
+/// here.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ArenaInstruction<'bump> {
+    /// Pubkey of the program that executes this instruction.
+    pub program_id: Pubkey,
+    /// Metadata describing accounts that should be passed to the program.
+    pub accounts: &'bump [AccountMeta],
+    /// Opaque data passed to the program for its own interpretation.
+    pub data: &'bump [u8],
+}
+
+impl<'bump> ArenaInstruction<'bump> {
+    /// Like [`Instruction::new_with_bytes`], but copying `data` and
+    /// `accounts` into `bump`.
+    pub fn new_with_bytes(
+        bump: &'bump Bump,
+        program_id: Pubkey,
+        data: &[u8],
+        accounts: &[AccountMeta],
+    ) -> Self {
+        Self {
+            program_id,
+            accounts: bump.alloc_slice_clone(accounts),
+            data: bump.alloc_slice_copy(data),
+        }
+    }
+
+    /// Like [`Instruction::new_with_bincode`], but serializing `data` into
+    /// `bump` and copying `accounts` into it.
+    pub fn new_with_bincode<T: Serialize>(
+        bump: &'bump Bump,
+        program_id: Pubkey,
+        data: &T,
+        accounts: &[AccountMeta],
+    ) -> Self {
+        let len = bincode::serialized_size(data).unwrap() as usize;
+        let buffer = bump.alloc_slice_fill_copy(len, 0u8);
+        bincode::serialize_into(&mut buffer[..], data).unwrap();
+        Self {
+            program_id,
+            accounts: bump.alloc_slice_clone(accounts),
+            data: buffer,
+        }
+    }
+
+    /// Copy this instruction out of the arena.
+    pub fn to_instruction(&self) -> Instruction {
+        Instruction {
+            program_id: self.program_id,
+            accounts: self.accounts.to_vec(),
+            data: self.data.to_vec(),
+        }
+    }
+}
+
+impl From<ArenaInstruction<'_>> for Instruction {
+    fn from(instruction: ArenaInstruction<'_>) -> Self {
+        instruction.to_instruction()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::system_instruction::SystemInstruction};
+
+    #[test]
+    fn test_arena_instruction() {
+        let program_id = Pubkey::new_unique();
+        let accounts = [
+            AccountMeta::new(Pubkey::new_unique(), true),
+            AccountMeta::new_readonly(Pubkey::new_unique(), false),
+        ];
+        let data = SystemInstruction::Transfer { lamports: 42 };
+
+        let mut bump = Bump::new();
+        let instruction = ArenaInstruction::new_with_bincode(&bump, program_id, &data, &accounts);
+        assert_eq!(
+            Instruction::from(instruction),
+            Instruction::new_with_bincode(program_id, &data, accounts.to_vec())
+        );
+
+        let bytes = [1, 2, 3];
+        let instruction = ArenaInstruction::new_with_bytes(&bump, program_id, &bytes, &[]);
+        assert_eq!(
+            instruction.to_instruction(),
+            Instruction::new_with_bytes(program_id, &bytes, vec![])
+        );
+
+        assert!(bump.allocated_bytes() > 0);
+        bump.reset();
+        let instruction = ArenaInstruction::new_with_bytes(&bump, program_id, &[], &[]);
+        assert!(instruction.accounts.is_empty());
+        assert!(instruction.data.is_empty());
+    }
+}
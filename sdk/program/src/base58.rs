@@ -0,0 +1,206 @@
+//! Base58 encoding and decoding specialized for 32-byte values.
+//!
+//! [`Pubkey`] and [`Hash`] are converted to and from base58 constantly, for
+//! logging and JSON serialization. The generic [`bs58`] implementation works
+//! one digit at a time and allocates; since the input size is fixed, these
+//! functions instead work on the number as eight 32-bit limbs, five base58
+//! digits at a time, entirely on the stack. The output is identical to `bs58`.
+//!
+//! [`Pubkey`]: crate::pubkey::Pubkey
+//! [`Hash`]: crate::hash::Hash
+
+#![allow(clippy::arithmetic_side_effects)]
+
+/// Maximum length of the base58 encoding of 32 bytes
+pub(crate) const MAX_ENCODED_LEN: usize = 44;
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+const INVALID_DIGIT: u8 = 0xff;
+
+/// Maps an ASCII character to its base58 digit, or `INVALID_DIGIT`
+const DIGITS: [u8; 128] = {
+    let mut digits = [INVALID_DIGIT; 128];
+    let mut i = 0;
+    while i < ALPHABET.len() {
+        digits[ALPHABET[i] as usize] = i as u8;
+        i += 1;
+    }
+    digits
+};
+
+/// Number of base58 digits that fit in one limb
+const DIGITS_PER_LIMB: usize = 5;
+/// 58^5, the largest power of 58 below 2^32
+const LIMB_RADIX: u64 = 58u64.pow(DIGITS_PER_LIMB as u32);
+
+const LIMBS: usize = 8;
+/// Enough limbs of base58 digits to hold any 256-bit number
+const ENCODED_LIMBS: usize = MAX_ENCODED_LEN.div_ceil(DIGITS_PER_LIMB);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DecodeError {
+    /// The string contains a character outside the base58 alphabet
+    InvalidCharacter,
+    /// The string does not decode to exactly 32 bytes
+    WrongSize,
+}
+
+/// Encodes `input` into `output`, returning the encoded string.
+pub(crate) fn encode_32<'a>(input: &[u8; 32], output: &'a mut [u8; MAX_ENCODED_LEN]) -> &'a str {
+    let mut limbs = [0u32; LIMBS];
+    for (limb, bytes) in limbs.iter_mut().zip(input.chunks_exact(4)) {
+        *limb = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    let leading_zeros = input.iter().take_while(|byte| **byte == 0).count();
+
+    // Repeatedly divide by 58^5, collecting digits least significant first
+    let mut digits = [0u8; ENCODED_LIMBS * DIGITS_PER_LIMB];
+    let mut first_limb = leading_zeros / 4;
+    for chunk in digits.rchunks_exact_mut(DIGITS_PER_LIMB) {
+        let mut remainder = 0u64;
+        for limb in &mut limbs[first_limb..] {
+            let acc = (remainder << 32) | u64::from(*limb);
+            *limb = (acc / LIMB_RADIX) as u32;
+            remainder = acc % LIMB_RADIX;
+        }
+        for digit in chunk.iter_mut().rev() {
+            *digit = (remainder % 58) as u8;
+            remainder /= 58;
+        }
+        while first_limb < LIMBS && limbs[first_limb] == 0 {
+            first_limb += 1;
+        }
+        if first_limb == LIMBS {
+            break;
+        }
+    }
+
+    // Leading zero bytes are encoded as '1's, followed by the significant digits
+    let significant = &digits[digits.iter().take_while(|digit| **digit == 0).count()..];
+    let len = leading_zeros + significant.len();
+    output[..leading_zeros].fill(ALPHABET[0]);
+    for (out, digit) in output[leading_zeros..len].iter_mut().zip(significant) {
+        *out = ALPHABET[*digit as usize];
+    }
+    // Every byte written comes from `ALPHABET`, which is ASCII
+    std::str::from_utf8(&output[..len]).unwrap()
+}
+
+/// Decodes a base58 string that must represent exactly 32 bytes.
+pub(crate) fn decode_32(input: &str) -> Result<[u8; 32], DecodeError> {
+    let input = input.as_bytes();
+    if input.len() > MAX_ENCODED_LEN {
+        return Err(DecodeError::WrongSize);
+    }
+    let mut digits = [0u8; MAX_ENCODED_LEN];
+    for (digit, c) in digits.iter_mut().zip(input) {
+        *digit = DIGITS
+            .get(*c as usize)
+            .copied()
+            .filter(|digit| *digit != INVALID_DIGIT)
+            .ok_or(DecodeError::InvalidCharacter)?;
+    }
+    let digits = &digits[..input.len()];
+    let leading_ones = digits.iter().take_while(|digit| **digit == 0).count();
+
+    // Multiply-accumulate five digits at a time, the first chunk taking the
+    // remainder so that every following chunk is full
+    let mut limbs = [0u32; LIMBS];
+    let first_chunk_len = match digits.len() % DIGITS_PER_LIMB {
+        0 => DIGITS_PER_LIMB,
+        len => len,
+    };
+    let (first_chunk, rest) = digits.split_at(first_chunk_len.min(digits.len()));
+    for chunk in std::iter::once(first_chunk).chain(rest.chunks_exact(DIGITS_PER_LIMB)) {
+        let mut carry = 0u64;
+        let mut multiplier = 1u64;
+        for digit in chunk {
+            carry = carry * 58 + u64::from(*digit);
+            multiplier *= 58;
+        }
+        for limb in limbs.iter_mut().rev() {
+            let acc = u64::from(*limb) * multiplier + carry;
+            *limb = acc as u32;
+            carry = acc >> 32;
+        }
+        if carry != 0 {
+            return Err(DecodeError::WrongSize);
+        }
+    }
+
+    let mut output = [0u8; 32];
+    for (bytes, limb) in output.chunks_exact_mut(4).zip(limbs) {
+        bytes.copy_from_slice(&limb.to_be_bytes());
+    }
+    // Each leading '1' stands for exactly one leading zero byte
+    let leading_zeros = output.iter().take_while(|byte| **byte == 0).count();
+    if leading_zeros != leading_ones {
+        return Err(DecodeError::WrongSize);
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_round_trip(bytes: [u8; 32]) {
+        let expected = bs58::encode(bytes).into_string();
+        let mut buf = [0u8; MAX_ENCODED_LEN];
+        assert_eq!(encode_32(&bytes, &mut buf), expected);
+        assert_eq!(decode_32(&expected), Ok(bytes));
+    }
+
+    #[test]
+    fn test_matches_bs58() {
+        check_round_trip([0; 32]);
+        check_round_trip([0xff; 32]);
+        let mut bytes = [0; 32];
+        bytes[31] = 1;
+        check_round_trip(bytes);
+        for leading_zeros in 0..32 {
+            let mut bytes = [0x5a; 32];
+            bytes[..leading_zeros].fill(0);
+            check_round_trip(bytes);
+            bytes[leading_zeros] = 1;
+            check_round_trip(bytes);
+        }
+        for _ in 0..1000 {
+            check_round_trip(rand::random());
+        }
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(decode_32(""), Err(DecodeError::WrongSize));
+        assert_eq!(decode_32("1111"), Err(DecodeError::WrongSize));
+        assert_eq!(decode_32(&"1".repeat(33)), Err(DecodeError::WrongSize));
+        assert_eq!(decode_32(&"z".repeat(45)), Err(DecodeError::WrongSize));
+
+        let max = bs58::encode([0xff; 32]).into_string();
+        assert_eq!(max.len(), MAX_ENCODED_LEN);
+        // One past 2^256 - 1 overflows
+        let mut overflow = max.clone().into_bytes();
+        let last = overflow.last_mut().unwrap();
+        *last = ALPHABET[DIGITS[*last as usize] as usize + 1];
+        assert_eq!(
+            decode_32(std::str::from_utf8(&overflow).unwrap()),
+            Err(DecodeError::WrongSize)
+        );
+        // 33 bytes of data
+        let too_long = bs58::encode([1; 33]).into_string();
+        assert_eq!(decode_32(&too_long), Err(DecodeError::WrongSize));
+        // A zero byte with no matching '1'
+        let mut bytes = [0xff; 32];
+        bytes[0] = 0;
+        let mut missing_one = bs58::encode(bytes).into_string();
+        missing_one.remove(0);
+        assert_eq!(decode_32(&missing_one), Err(DecodeError::WrongSize));
+
+        for invalid in ["0", "I", "O", "l", "+", "\u{e9}"] {
+            let s = format!("{invalid}{}", &max[2..]);
+            assert_eq!(decode_32(&s), Err(DecodeError::InvalidCharacter));
+        }
+    }
+}
@@ -8,12 +8,19 @@
 //! be removed in a future release
 //!
 //! [borsh]: https://borsh.io/
+#[cfg(feature = "borsh010")]
 use borsh0_10::{maybestd::io::Error, BorshDeserialize, BorshSchema, BorshSerialize};
+use std::{
+    any::type_name,
+    collections::HashMap,
+    sync::{PoisonError, RwLock},
+};
 
 /// Get the worst-case packed length for the given BorshSchema
 ///
 /// Note: due to the serializer currently used by Borsh, this function cannot
 /// be used on-chain in the Solana SBF execution environment.
+#[cfg(feature = "borsh010")]
 #[deprecated(
     since = "1.17.0",
     note = "Please use `borsh0_10::get_packed_len` instead"
@@ -32,6 +39,7 @@ pub fn get_packed_len<S: BorshSchema>() -> usize {
 /// or equal to the expected size will properly deserialize. For example, if the
 /// user passes a buffer destined for a different type, the error won't get caught
 /// as easily.
+#[cfg(feature = "borsh010")]
 #[deprecated(
     since = "1.17.0",
     note = "Please use `borsh0_10::try_from_slice_unchecked` instead"
@@ -47,6 +55,7 @@ pub fn try_from_slice_unchecked<T: BorshDeserialize>(data: &[u8]) -> Result<T, E
 /// sequence, such as a Vec or HashMap.  Since it is impossible to know the packed
 /// length only from the type's schema, this can be used when an instance already
 /// exists, to figure out how much space to allocate in an account.
+#[cfg(feature = "borsh010")]
 #[deprecated(
     since = "1.17.0",
     note = "Please use `borsh0_10::get_instance_packed_len` instead"
@@ -56,6 +65,42 @@ pub fn get_instance_packed_len<T: BorshSerialize>(instance: &T) -> Result<usize,
     crate::borsh0_10::get_instance_packed_len(instance)
 }
 
+/// Packed lengths already computed by `get_packed_len`, keyed by type.
+///
+/// The key is the full type name rather than the schema declaration, which is
+/// only the bare type name and so is shared by same-named types in different
+/// modules. Unlike a `TypeId`, it does not need the type to be `'static`; the
+/// lifetimes it leaves out do not change the packed length.
+///
+/// Building a schema container walks the whole type and allocates a map of
+/// every definition in it, so the result is computed once per type.
+#[derive(Default)]
+pub(crate) struct PackedLenCache(RwLock<HashMap<&'static str, usize>>);
+
+impl PackedLenCache {
+    pub(crate) fn get_or_insert_with<S: ?Sized>(
+        &self,
+        get_packed_len: impl FnOnce() -> usize,
+    ) -> usize {
+        let type_name = type_name::<S>();
+        if let Some(packed_len) = self
+            .0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(type_name)
+        {
+            return *packed_len;
+        }
+        let packed_len = get_packed_len();
+        self.0
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(type_name, packed_len);
+        packed_len
+    }
+}
+
+#[cfg(any(feature = "borsh09", feature = "borsh010"))]
 macro_rules! impl_get_packed_len_v0 {
     ($borsh:ident $(,#[$meta:meta])?) => {
         /// Get the worst-case packed length for the given BorshSchema
@@ -64,15 +109,22 @@ macro_rules! impl_get_packed_len_v0 {
         /// be used on-chain in the Solana SBF execution environment.
         $(#[$meta])?
         pub fn get_packed_len<S: $borsh::BorshSchema>() -> usize {
-            let $borsh::schema::BorshSchemaContainer { declaration, definitions } =
-                &S::schema_container();
-            get_declaration_packed_len(declaration, definitions)
+            static PACKED_LEN_CACHE: crate::sync::LazyLock<crate::borsh::PackedLenCache> =
+                crate::sync::LazyLock::new(crate::borsh::PackedLenCache::default);
+            PACKED_LEN_CACHE.get_or_insert_with::<S>(|| {
+                let $borsh::schema::BorshSchemaContainer { declaration, definitions } =
+                    &S::schema_container();
+                get_declaration_packed_len(declaration, definitions)
+            })
         }
 
         /// Get packed length for the given BorshSchema Declaration
         fn get_declaration_packed_len(
             declaration: &str,
-            definitions: &std::collections::HashMap<$borsh::schema::Declaration, $borsh::schema::Definition>,
+            definitions: &$borsh::maybestd::collections::HashMap<
+                $borsh::schema::Declaration,
+                $borsh::schema::Definition,
+            >,
         ) -> usize {
             match definitions.get(declaration) {
                 Some($borsh::schema::Definition::Array { length, elements }) => {
@@ -116,6 +168,7 @@ macro_rules! impl_get_packed_len_v0 {
         }
     }
 }
+#[cfg(any(feature = "borsh09", feature = "borsh010"))]
 pub(crate) use impl_get_packed_len_v0;
 
 macro_rules! impl_get_packed_len_v1 {
@@ -126,8 +179,12 @@ macro_rules! impl_get_packed_len_v1 {
         /// be used on-chain in the Solana SBF execution environment.
         $(#[$meta])?
         pub fn get_packed_len<S: $borsh::BorshSchema>() -> usize {
-            let container = $borsh::schema_container_of::<S>();
-            get_declaration_packed_len(container.declaration(), &container)
+            static PACKED_LEN_CACHE: crate::sync::LazyLock<crate::borsh::PackedLenCache> =
+                crate::sync::LazyLock::new(crate::borsh::PackedLenCache::default);
+            PACKED_LEN_CACHE.get_or_insert_with::<S>(|| {
+                let container = $borsh::schema_container_of::<S>();
+                get_declaration_packed_len(container.declaration(), &container)
+            })
         }
 
         /// Get packed length for the given BorshSchema Declaration
@@ -278,6 +335,9 @@ macro_rules! impl_tests {
         fn packed_len() {
             assert_eq!(get_packed_len::<u64>(), size_of::<u64>());
             assert_eq!(get_packed_len::<Child>(), size_of::<u8>() * 64);
+            // Served from the cache the second time
+            assert_eq!(get_packed_len::<Child>(), size_of::<u8>() * 64);
+            assert_eq!(get_packed_len::<[u16; 64]>(), size_of::<u16>() * 64);
         }
 
         #[test]
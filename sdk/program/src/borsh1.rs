@@ -3,8 +3,12 @@
 //!
 //! [borsh]: https://borsh.io/
 use {
-    crate::borsh::{
-        impl_get_instance_packed_len, impl_get_packed_len_v1, impl_try_from_slice_unchecked,
+    crate::{
+        borsh::{
+            impl_get_instance_packed_len, impl_get_packed_len_v1, impl_try_from_slice_unchecked,
+        },
+        hash::{Hash, HASH_BYTES},
+        pubkey::{Pubkey, PUBKEY_BYTES},
     },
     borsh::io,
 };
@@ -13,8 +17,162 @@ impl_get_packed_len_v1!(borsh);
 impl_try_from_slice_unchecked!(borsh, io);
 impl_get_instance_packed_len!(borsh, io);
 
+/// Derives [`ConstPackedLen`] for a struct or enum whose fields all implement it
+pub use solana_sdk_macro::ConstPackedLen;
+
+/// A type whose worst-case packed length is known at compile time.
+///
+/// This is the same value [`get_packed_len`] computes by walking the type's
+/// schema, so types with a fixed layout, such as those without `Vec`s or
+/// `String`s, can use `PACKED_LEN` instead, including in const contexts.
+/// Implement it with `#[derive(ConstPackedLen)]`.
+pub trait ConstPackedLen {
+    const PACKED_LEN: usize;
+}
+
+#[doc(hidden)]
+pub const fn const_max(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+macro_rules! impl_const_packed_len {
+    ($($ty:ty => $len:expr),* $(,)?) => {
+        $(impl ConstPackedLen for $ty {
+            const PACKED_LEN: usize = $len;
+        })*
+    };
+}
+impl_const_packed_len!(
+    () => 0,
+    bool => 1,
+    u8 => 1,
+    i8 => 1,
+    u16 => 2,
+    i16 => 2,
+    u32 => 4,
+    i32 => 4,
+    u64 => 8,
+    i64 => 8,
+    u128 => 16,
+    i128 => 16,
+    f32 => 4,
+    f64 => 8,
+    Pubkey => PUBKEY_BYTES,
+    Hash => HASH_BYTES,
+);
+
+impl<T: ConstPackedLen, const N: usize> ConstPackedLen for [T; N] {
+    const PACKED_LEN: usize = N * T::PACKED_LEN;
+}
+
+impl<T: ConstPackedLen> ConstPackedLen for Option<T> {
+    const PACKED_LEN: usize = 1 + T::PACKED_LEN;
+}
+
+macro_rules! impl_const_packed_len_tuple {
+    ($($name:ident)+) => {
+        impl<$($name: ConstPackedLen),+> ConstPackedLen for ($($name,)+) {
+            const PACKED_LEN: usize = 0 $(+ $name::PACKED_LEN)+;
+        }
+    };
+}
+impl_const_packed_len_tuple!(T0);
+impl_const_packed_len_tuple!(T0 T1);
+impl_const_packed_len_tuple!(T0 T1 T2);
+impl_const_packed_len_tuple!(T0 T1 T2 T3);
+impl_const_packed_len_tuple!(T0 T1 T2 T3 T4);
+impl_const_packed_len_tuple!(T0 T1 T2 T3 T4 T5);
+
 #[cfg(test)]
 mod tests {
-    use {crate::borsh::impl_tests, borsh::io};
+    use {
+        super::{get_packed_len, ConstPackedLen},
+        crate::{borsh::impl_tests, hash::Hash, pubkey::Pubkey},
+        borsh::{io, BorshSchema},
+    };
     impl_tests!(borsh, io);
+
+    #[derive(BorshSchema, ConstPackedLen)]
+    #[borsh(crate = "borsh")]
+    struct Named {
+        _key: Pubkey,
+        _hash: Hash,
+        _amount: u64,
+        _flag: Option<u32>,
+        _bytes: [u8; 5],
+        _pair: (bool, i16),
+    }
+
+    #[allow(dead_code)]
+    #[derive(BorshSchema, ConstPackedLen)]
+    #[borsh(crate = "borsh")]
+    struct Unnamed(u8, [Named; 2]);
+
+    #[derive(BorshSchema, ConstPackedLen)]
+    #[borsh(crate = "borsh")]
+    enum Choice {
+        _Empty,
+        _Small(u8),
+        _Large { _named: Named, _float: f64 },
+    }
+
+    #[derive(BorshSchema, ConstPackedLen)]
+    #[borsh(crate = "borsh")]
+    struct Generic<T> {
+        _value: T,
+        _choice: Choice,
+    }
+
+    #[test]
+    fn test_packed_len_same_type_name() {
+        mod a {
+            #[derive(borsh::BorshSchema)]
+            #[borsh(crate = "borsh")]
+            pub struct State {
+                _x: u8,
+            }
+        }
+        mod b {
+            #[derive(borsh::BorshSchema)]
+            #[borsh(crate = "borsh")]
+            pub struct State {
+                _x: [u64; 4],
+            }
+        }
+        assert_eq!(get_packed_len::<a::State>(), 1);
+        assert_eq!(get_packed_len::<b::State>(), 32);
+    }
+
+    #[test]
+    fn test_packed_len_borrowed_type() {
+        #[derive(borsh::BorshSchema)]
+        #[borsh(crate = "borsh")]
+        struct Borrowed<'a> {
+            _x: u16,
+            _marker: std::marker::PhantomData<&'a ()>,
+        }
+        fn packed_len<'a>(_: &'a ()) -> usize {
+            get_packed_len::<Borrowed<'a>>()
+        }
+        let local = ();
+        assert_eq!(packed_len(&local), 2);
+        assert_eq!(packed_len(&local), 2);
+    }
+
+    #[test]
+    fn test_const_packed_len() {
+        const LEN: usize = Named::PACKED_LEN;
+        assert_eq!(LEN, 32 + 32 + 8 + 5 + 5 + 3);
+        assert_eq!(Named::PACKED_LEN, get_packed_len::<Named>());
+        assert_eq!(Unnamed::PACKED_LEN, get_packed_len::<Unnamed>());
+        assert_eq!(Choice::PACKED_LEN, get_packed_len::<Choice>());
+        assert_eq!(
+            Generic::<u128>::PACKED_LEN,
+            get_packed_len::<Generic<u128>>()
+        );
+    }
 }
@@ -0,0 +1,193 @@
+//! Conversions between the [borsh] versions supported by this crate.
+//!
+//! Types in the wild implement the traits of different borsh versions, but
+//! the encoding of most layouts did not change between versions. The adapters
+//! here name a type together with the borsh version to use for it, so that
+//! bytes written with one version can be read and written with another:
+//!
+//! ```
+//! use solana_program::{
+//!     borsh_compat::{reencode, Borsh09, Borsh1},
+//!     pubkey::Pubkey,
+//! };
+//!
+//! let pubkey = Pubkey::new_unique();
+//! let old_bytes = borsh0_9::BorshSerialize::try_to_vec(&pubkey).unwrap();
+//! let new_bytes = reencode::<Borsh09<Pubkey>, Borsh1<Pubkey>>(&old_bytes).unwrap();
+//! assert_eq!(new_bytes, borsh::to_vec(&pubkey).unwrap());
+//! ```
+//!
+//! The old and new types may differ, as long as the new one implements
+//! `From` the old one.
+//!
+//! [borsh]: https://borsh.io/
+use std::{io, marker::PhantomData};
+
+/// A type that can be deserialized with a particular borsh version.
+pub trait Decode {
+    type Value;
+
+    /// Deserialize a value, checking that all of `bytes` were consumed.
+    fn decode(bytes: &[u8]) -> io::Result<Self::Value>;
+}
+
+/// A type that can be serialized with a particular borsh version.
+pub trait Encode {
+    type Value;
+
+    fn encode(value: &Self::Value) -> io::Result<Vec<u8>>;
+}
+
+/// Deserialize `bytes` as `Old` and serialize the result as `New`.
+pub fn reencode<Old: Decode, New: Encode>(bytes: &[u8]) -> io::Result<Vec<u8>>
+where
+    New::Value: From<Old::Value>,
+{
+    New::encode(&Old::decode(bytes)?.into())
+}
+
+macro_rules! impl_borsh_adapter {
+    ($(#[$attr:meta])* $adapter:ident, $borsh:ident, $version:literal) => {
+        #[doc = concat!("Adapts `T` to [`Decode`] and [`Encode`] with borsh ", $version, ".")]
+        $(#[$attr])*
+        pub struct $adapter<T>(PhantomData<T>);
+
+        $(#[$attr])*
+        impl<T: $borsh::BorshDeserialize> Decode for $adapter<T> {
+            type Value = T;
+
+            fn decode(bytes: &[u8]) -> io::Result<T> {
+                T::try_from_slice(bytes)
+            }
+        }
+
+        $(#[$attr])*
+        impl<T: $borsh::BorshSerialize> Encode for $adapter<T> {
+            type Value = T;
+
+            fn encode(value: &T) -> io::Result<Vec<u8>> {
+                let mut bytes = Vec::new();
+                value.serialize(&mut bytes)?;
+                Ok(bytes)
+            }
+        }
+    };
+}
+
+impl_borsh_adapter!(
+    #[cfg(feature = "borsh09")]
+    Borsh09,
+    borsh0_9,
+    "0.9"
+);
+impl_borsh_adapter!(
+    #[cfg(feature = "borsh010")]
+    Borsh010,
+    borsh0_10,
+    "0.10"
+);
+impl_borsh_adapter!(Borsh1, borsh, "1");
+
+#[cfg(all(test, feature = "borsh09", feature = "borsh010"))]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            pubkey::Pubkey,
+            stake::{
+                stake_flags::StakeFlags,
+                state::{Authorized, Delegation, Lockup, Meta, Stake, StakeStateV2},
+            },
+        },
+        std::fmt::Debug,
+    };
+
+    /// Check that `value` encodes to the same bytes with both versions, and
+    /// that those bytes reencode to themselves in both directions.
+    fn check_compatible<A, B, T>(value: T)
+    where
+        A: Decode<Value = T> + Encode<Value = T>,
+        B: Decode<Value = T> + Encode<Value = T>,
+        T: Debug + PartialEq,
+    {
+        let a_bytes = A::encode(&value).unwrap();
+        let b_bytes = B::encode(&value).unwrap();
+        assert_eq!(a_bytes, b_bytes);
+        assert_eq!(reencode::<A, B>(&a_bytes).unwrap(), b_bytes);
+        assert_eq!(reencode::<B, A>(&b_bytes).unwrap(), a_bytes);
+        assert_eq!(A::decode(&b_bytes).unwrap(), value);
+        assert_eq!(B::decode(&a_bytes).unwrap(), value);
+    }
+
+    fn stake_state() -> StakeStateV2 {
+        StakeStateV2::Stake(
+            Meta {
+                rent_exempt_reserve: 1,
+                authorized: Authorized {
+                    staker: Pubkey::new_unique(),
+                    withdrawer: Pubkey::new_unique(),
+                },
+                lockup: Lockup {
+                    unix_timestamp: -2,
+                    epoch: 3,
+                    custodian: Pubkey::new_unique(),
+                },
+            },
+            Stake {
+                delegation: Delegation {
+                    voter_pubkey: Pubkey::new_unique(),
+                    stake: u64::MAX,
+                    activation_epoch: 4,
+                    deactivation_epoch: u64::MAX,
+                    ..Delegation::default()
+                },
+                credits_observed: 5,
+            },
+            StakeFlags::empty(),
+        )
+    }
+
+    #[test]
+    fn test_borsh1_and_borsh010() {
+        check_compatible::<Borsh010<_>, Borsh1<_>, _>(Pubkey::new_unique());
+        check_compatible::<Borsh010<_>, Borsh1<_>, _>(stake_state());
+        check_compatible::<Borsh010<_>, Borsh1<_>, _>(StakeStateV2::RewardsPool);
+        check_compatible::<Borsh010<_>, Borsh1<_>, _>((
+            1u8,
+            -2i64,
+            Some(u128::MAX),
+            "borsh".to_string(),
+            vec![[4u16; 3]; 2],
+        ));
+    }
+
+    #[test]
+    fn test_borsh1_and_borsh09() {
+        check_compatible::<Borsh09<_>, Borsh1<_>, _>(Pubkey::new_unique());
+        check_compatible::<Borsh09<_>, Borsh1<_>, _>((
+            1u8,
+            -2i64,
+            Some(u128::MAX),
+            "borsh".to_string(),
+            vec![[4u16; 3]; 2],
+        ));
+    }
+
+    #[test]
+    fn test_reencode_into_other_type() {
+        let bytes = Borsh09::<u32>::encode(&7).unwrap();
+        assert_eq!(
+            reencode::<Borsh09<u32>, Borsh1<u64>>(&bytes).unwrap(),
+            borsh::to_vec(&7u64).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_reencode_rejects_bad_input() {
+        let mut bytes = Borsh09::<Pubkey>::encode(&Pubkey::new_unique()).unwrap();
+        assert!(reencode::<Borsh09<Pubkey>, Borsh1<Pubkey>>(&bytes[1..]).is_err());
+        bytes.push(0);
+        assert!(reencode::<Borsh09<Pubkey>, Borsh1<Pubkey>>(&bytes).is_err());
+        assert!(reencode::<Borsh010<StakeStateV2>, Borsh1<StakeStateV2>>(&[4]).is_err());
+    }
+}
@@ -20,7 +20,7 @@
 //!
 //! [oracle]: https://docs.solanalabs.com/implemented-proposals/validator-timestamp-oracle
 
-use solana_sdk_macro::CloneZeroed;
+use {crate::epoch_schedule::EpochSchedule, solana_sdk_macro::CloneZeroed};
 
 /// The default tick rate that the cluster attempts to achieve (160 per second).
 ///
@@ -199,10 +199,120 @@ pub struct Clock {
     pub unix_timestamp: UnixTimestamp,
 }
 
+impl Clock {
+    /// Estimate the unix timestamp of `slot`, assuming the network produces a
+    /// slot every `ms_per_slot` milliseconds, e.g. [`DEFAULT_MS_PER_SLOT`].
+    ///
+    /// The estimate is anchored at the current slot and timestamp, so it
+    /// drifts further from the real timestamp the further away `slot` is.
+    pub fn estimated_unix_timestamp_at_slot(&self, slot: Slot, ms_per_slot: u64) -> UnixTimestamp {
+        let seconds_between = |slots: u64| {
+            i64::try_from(slots.saturating_mul(ms_per_slot) / 1_000).unwrap_or(i64::MAX)
+        };
+        if slot >= self.slot {
+            self.unix_timestamp
+                .saturating_add(seconds_between(slot.saturating_sub(self.slot)))
+        } else {
+            self.unix_timestamp
+                .saturating_sub(seconds_between(self.slot.saturating_sub(slot)))
+        }
+    }
+
+    /// Estimate the slot that the network will be at, or was at, at
+    /// `unix_timestamp`, assuming a slot every `ms_per_slot` milliseconds.
+    pub fn estimated_slot_at_unix_timestamp(
+        &self,
+        unix_timestamp: UnixTimestamp,
+        ms_per_slot: u64,
+    ) -> Slot {
+        let slots_between = |seconds: u64| {
+            seconds
+                .saturating_mul(1_000)
+                .checked_div(ms_per_slot)
+                .unwrap_or(0)
+        };
+        let seconds = unix_timestamp.abs_diff(self.unix_timestamp);
+        if unix_timestamp >= self.unix_timestamp {
+            self.slot.saturating_add(slots_between(seconds))
+        } else {
+            self.slot.saturating_sub(slots_between(seconds))
+        }
+    }
+
+    /// Estimate the epoch the network will be in, or was in, at
+    /// `unix_timestamp`, assuming a slot every `ms_per_slot` milliseconds.
+    pub fn estimated_epoch_at_unix_timestamp(
+        &self,
+        unix_timestamp: UnixTimestamp,
+        epoch_schedule: &EpochSchedule,
+        ms_per_slot: u64,
+    ) -> Epoch {
+        epoch_schedule.get_epoch(self.estimated_slot_at_unix_timestamp(unix_timestamp, ms_per_slot))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_estimates() {
+        let clock = Clock {
+            slot: 1_000,
+            unix_timestamp: 1_700_000_000,
+            ..Clock::default()
+        };
+
+        assert_eq!(
+            clock.estimated_unix_timestamp_at_slot(1_000, DEFAULT_MS_PER_SLOT),
+            1_700_000_000
+        );
+        assert_eq!(
+            clock.estimated_unix_timestamp_at_slot(1_250, DEFAULT_MS_PER_SLOT),
+            1_700_000_100
+        );
+        assert_eq!(
+            clock.estimated_unix_timestamp_at_slot(750, DEFAULT_MS_PER_SLOT),
+            1_699_999_900
+        );
+        let late_clock = Clock {
+            unix_timestamp: i64::MAX - 1,
+            ..clock
+        };
+        assert_eq!(
+            late_clock.estimated_unix_timestamp_at_slot(u64::MAX, u64::MAX),
+            i64::MAX
+        );
+
+        assert_eq!(
+            clock.estimated_slot_at_unix_timestamp(1_700_000_100, DEFAULT_MS_PER_SLOT),
+            1_250
+        );
+        assert_eq!(
+            clock.estimated_slot_at_unix_timestamp(1_699_999_900, DEFAULT_MS_PER_SLOT),
+            750
+        );
+        assert_eq!(
+            clock.estimated_slot_at_unix_timestamp(0, DEFAULT_MS_PER_SLOT),
+            0
+        );
+        assert_eq!(
+            clock.estimated_slot_at_unix_timestamp(1_700_000_100, 0),
+            1_000
+        );
+
+        let epoch_schedule = EpochSchedule::without_warmup();
+        let three_days_later = clock.unix_timestamp + 3 * SECONDS_PER_DAY as i64;
+        assert_eq!(
+            clock.estimated_epoch_at_unix_timestamp(
+                three_days_later,
+                &epoch_schedule,
+                DEFAULT_MS_PER_SLOT
+            ),
+            1
+        );
+    }
+
     #[test]
     fn test_clone() {
         let clock = Clock {
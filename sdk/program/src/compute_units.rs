@@ -11,3 +11,19 @@ pub fn sol_remaining_compute_units() -> u64 {
         crate::program_stubs::sol_remaining_compute_units()
     }
 }
+
+/// The compute units a builtin program charges for its instructions.
+///
+/// Builtin programs charge a flat amount for every instruction, which
+/// transaction builders can use to set the compute budget of transactions
+/// that include them. The processors charge `DEFAULT_COMPUTE_UNITS` from the
+/// implementation for their instruction type, so the two cannot drift apart.
+pub trait BuiltinComputeUnits {
+    /// Compute units the program charges for every instruction
+    const DEFAULT_COMPUTE_UNITS: u64;
+
+    /// Compute units the program consumes processing this instruction
+    fn estimated_compute_units(&self) -> u64 {
+        Self::DEFAULT_COMPUTE_UNITS
+    }
+}
@@ -1,6 +1,14 @@
 //! Converting custom error codes to enums.
+//!
+//! With the `error-registry` feature, [`decode_custom_error`] also describes
+//! the custom errors of any program whose decoder has been registered.
 
 use num_traits::FromPrimitive;
+#[cfg(all(feature = "error-registry", not(target_os = "solana")))]
+use {
+    crate::{pubkey::Pubkey, sync::LazyLock},
+    std::{collections::HashMap, fmt::Debug, sync::RwLock},
+};
 
 /// Allows custom errors to be decoded back to their original enum.
 ///
@@ -31,6 +39,64 @@ pub trait DecodeError<E> {
     fn type_of() -> &'static str;
 }
 
+/// Decodes a custom error code of some program into a readable description.
+#[cfg(all(feature = "error-registry", not(target_os = "solana")))]
+pub type CustomErrorDecoder = fn(u32) -> Option<String>;
+
+#[cfg(all(feature = "error-registry", not(target_os = "solana")))]
+static CUSTOM_ERROR_DECODERS: LazyLock<RwLock<HashMap<Pubkey, CustomErrorDecoder>>> =
+    LazyLock::new(|| {
+        RwLock::new(HashMap::from([
+            (
+                crate::system_program::id(),
+                describe_custom_error::<crate::system_instruction::SystemError>
+                    as CustomErrorDecoder,
+            ),
+            (
+                crate::stake::program::id(),
+                describe_custom_error::<crate::stake::instruction::StakeError>,
+            ),
+            (
+                crate::vote::program::id(),
+                describe_custom_error::<crate::vote::error::VoteError>,
+            ),
+        ]))
+    });
+
+/// Describes `custom` as a variant of `E`, e.g. `"StakeError::NoCreditsToRedeem"`.
+///
+/// Suitable for use as a [`CustomErrorDecoder`].
+#[cfg(all(feature = "error-registry", not(target_os = "solana")))]
+pub fn describe_custom_error<E>(custom: u32) -> Option<String>
+where
+    E: DecodeError<E> + FromPrimitive + Debug,
+{
+    E::decode_custom_error_to_enum(custom).map(|error| format!("{}::{error:?}", E::type_of()))
+}
+
+/// Registers the decoder used by [`decode_custom_error`] for `program_id`,
+/// returning the previously registered decoder, if any.
+///
+/// Decoders for the system, stake and vote programs are registered by default.
+#[cfg(all(feature = "error-registry", not(target_os = "solana")))]
+pub fn register_custom_error_decoder(
+    program_id: Pubkey,
+    decoder: CustomErrorDecoder,
+) -> Option<CustomErrorDecoder> {
+    CUSTOM_ERROR_DECODERS
+        .write()
+        .unwrap()
+        .insert(program_id, decoder)
+}
+
+/// Describes the custom error `code` returned by `program_id`, if a decoder
+/// is registered for that program and recognizes the code.
+#[cfg(all(feature = "error-registry", not(target_os = "solana")))]
+pub fn decode_custom_error(program_id: &Pubkey, code: u32) -> Option<String> {
+    let decoder = *CUSTOM_ERROR_DECODERS.read().unwrap().get(program_id)?;
+    decoder(code)
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, num_derive::FromPrimitive};
@@ -54,4 +120,40 @@ mod tests {
         let option: Option<TestEnum> = TestEnum::decode_custom_error_to_enum(3);
         assert_eq!(option, None);
     }
+
+    #[test]
+    #[cfg(feature = "error-registry")]
+    fn test_decode_custom_error() {
+        assert_eq!(
+            decode_custom_error(&crate::stake::program::id(), 0).as_deref(),
+            Some("StakeError::NoCreditsToRedeem")
+        );
+        assert_eq!(
+            decode_custom_error(&crate::system_program::id(), 0).as_deref(),
+            Some("SystemError::AccountAlreadyInUse")
+        );
+        assert_eq!(
+            decode_custom_error(&crate::stake::program::id(), 1000),
+            None
+        );
+
+        #[derive(Debug, FromPrimitive)]
+        enum TestError {
+            Oops,
+        }
+        impl<T> DecodeError<T> for TestError {
+            fn type_of() -> &'static str {
+                "TestError"
+            }
+        }
+        let program_id = Pubkey::new_unique();
+        assert_eq!(decode_custom_error(&program_id, 0), None);
+        assert!(
+            register_custom_error_decoder(program_id, describe_custom_error::<TestError>).is_none()
+        );
+        assert_eq!(
+            decode_custom_error(&program_id, 0).as_deref(),
+            Some("TestError::Oops")
+        );
+    }
 }
@@ -0,0 +1,247 @@
+//! Anchor-compatible account and instruction discriminators.
+//!
+//! Programs written with the [Anchor] framework prefix account data and
+//! instruction data with an 8-byte discriminator: the first 8 bytes of the
+//! SHA-256 hash of `"account:<AccountName>"` or `"global:<instruction_name>"`.
+//! Computing the same tags allows tools and programs built on this crate to
+//! read and construct data for Anchor programs.
+//!
+//! The helpers are `const fn`s, so discriminators can be computed at compile
+//! time, and [`AccountDiscriminator`] can be derived for state types.
+//!
+//! [Anchor]: https://www.anchor-lang.com
+
+/// Derives [`AccountDiscriminator`] with the type's own name as the account name
+pub use solana_sdk_macro::AccountDiscriminator;
+
+/// Number of bytes in a discriminator
+pub const DISCRIMINATOR_LENGTH: usize = 8;
+
+const SHA256_INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const SHA256_ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+#[allow(clippy::arithmetic_side_effects)]
+const fn sha256_compress(mut state: [u32; 8], block: &[u8; 64]) -> [u32; 8] {
+    let mut w = [0u32; 64];
+    let mut i = 0;
+    while i < 16 {
+        w[i] = u32::from_be_bytes([
+            block[4 * i],
+            block[4 * i + 1],
+            block[4 * i + 2],
+            block[4 * i + 3],
+        ]);
+        i += 1;
+    }
+    while i < 64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+        i += 1;
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+    let mut i = 0;
+    while i < 64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(SHA256_ROUND_CONSTANTS[i])
+            .wrapping_add(w[i]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+        i += 1;
+    }
+
+    let working = [a, b, c, d, e, f, g, h];
+    let mut i = 0;
+    while i < 8 {
+        state[i] = state[i].wrapping_add(working[i]);
+        i += 1;
+    }
+    state
+}
+
+/// The first [`DISCRIMINATOR_LENGTH`] bytes of the SHA-256 hash of the
+/// concatenation of `parts`, the same as a prefix of [`hashv`], but usable in
+/// constant expressions.
+///
+/// [`hashv`]: crate::hash::hashv
+#[allow(clippy::arithmetic_side_effects)]
+pub const fn sha256_prefix(parts: &[&[u8]]) -> [u8; DISCRIMINATOR_LENGTH] {
+    let mut state = SHA256_INITIAL_STATE;
+    let mut block = [0u8; 64];
+    let mut block_len = 0;
+    let mut message_len: u64 = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        let part = parts[i];
+        let mut j = 0;
+        while j < part.len() {
+            block[block_len] = part[j];
+            block_len += 1;
+            if block_len == block.len() {
+                state = sha256_compress(state, &block);
+                block_len = 0;
+            }
+            j += 1;
+        }
+        message_len = message_len.wrapping_add(part.len() as u64);
+        i += 1;
+    }
+
+    // Padding: a one bit, zeros, then the message length in bits
+    block[block_len] = 0x80;
+    block_len += 1;
+    if block_len > 56 {
+        while block_len < block.len() {
+            block[block_len] = 0;
+            block_len += 1;
+        }
+        state = sha256_compress(state, &block);
+        block_len = 0;
+    }
+    while block_len < 56 {
+        block[block_len] = 0;
+        block_len += 1;
+    }
+    let bit_len = message_len.wrapping_mul(8).to_be_bytes();
+    let mut i = 0;
+    while i < bit_len.len() {
+        block[56 + i] = bit_len[i];
+        i += 1;
+    }
+    state = sha256_compress(state, &block);
+
+    let [first, second, ..] = state;
+    let [a, b, c, d] = first.to_be_bytes();
+    let [e, f, g, h] = second.to_be_bytes();
+    [a, b, c, d, e, f, g, h]
+}
+
+/// The discriminator of accounts holding the type named `account_name`
+pub const fn account_discriminator(account_name: &str) -> [u8; DISCRIMINATOR_LENGTH] {
+    sha256_prefix(&[b"account:", account_name.as_bytes()])
+}
+
+/// The discriminator of the instruction handled by `instruction_name`
+pub const fn instruction_discriminator(instruction_name: &str) -> [u8; DISCRIMINATOR_LENGTH] {
+    sha256_prefix(&[b"global:", instruction_name.as_bytes()])
+}
+
+/// Types whose serialized accounts are tagged with an Anchor-compatible
+/// discriminator.
+///
+/// Implement it with `#[derive(AccountDiscriminator)]`.
+pub trait AccountDiscriminator {
+    /// The name of the type as declared in the Anchor program
+    const ACCOUNT_NAME: &'static str;
+
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LENGTH] = account_discriminator(Self::ACCOUNT_NAME);
+
+    /// Whether `data` starts with this type's discriminator
+    fn has_discriminator(data: &[u8]) -> bool {
+        data.get(..DISCRIMINATOR_LENGTH) == Some(&Self::DISCRIMINATOR[..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::hash::hashv};
+
+    #[test]
+    fn test_sha256_prefix() {
+        // Messages that need one and two blocks after padding, split across
+        // parts in different places
+        for len in [0, 1, 55, 56, 63, 64, 65, 200] {
+            let message: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let (head, tail) = message.split_at(len / 3);
+            assert_eq!(
+                sha256_prefix(&[head, tail]),
+                hashv(&[&message]).to_bytes()[..DISCRIMINATOR_LENGTH],
+                "{len}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sha256_prefix_random() {
+        // Random messages of up to four blocks, split into a random number of
+        // parts
+        for _ in 0..1000 {
+            let len = rand::random::<usize>() % 256;
+            let message: Vec<u8> = (0..len).map(|_| rand::random()).collect();
+            let mut splits: Vec<usize> = (0..rand::random::<usize>() % 4)
+                .map(|_| rand::random::<usize>() % (len + 1))
+                .collect();
+            splits.push(0);
+            splits.push(len);
+            splits.sort_unstable();
+            let parts: Vec<&[u8]> = splits
+                .windows(2)
+                .map(|range| &message[range[0]..range[1]])
+                .collect();
+            assert_eq!(
+                sha256_prefix(&parts),
+                hashv(&[&message]).to_bytes()[..DISCRIMINATOR_LENGTH],
+                "{message:?} split at {splits:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_discriminators() {
+        assert_eq!(
+            instruction_discriminator("initialize"),
+            [175, 175, 109, 31, 13, 152, 155, 237]
+        );
+        assert_eq!(
+            account_discriminator("Counter"),
+            [255, 176, 4, 245, 188, 253, 124, 25]
+        );
+    }
+
+    #[test]
+    fn test_account_discriminator_trait() {
+        #[derive(AccountDiscriminator)]
+        struct Counter;
+
+        const COUNTER_DISCRIMINATOR: [u8; DISCRIMINATOR_LENGTH] = Counter::DISCRIMINATOR;
+        assert_eq!(Counter::ACCOUNT_NAME, "Counter");
+        assert_eq!(COUNTER_DISCRIMINATOR, account_discriminator("Counter"));
+
+        let mut data = Counter::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&42u64.to_le_bytes());
+        assert!(Counter::has_discriminator(&data));
+        assert!(!Counter::has_discriminator(
+            &data[..DISCRIMINATOR_LENGTH - 1]
+        ));
+        assert!(!Counter::has_discriminator(&[0; 16]));
+    }
+}
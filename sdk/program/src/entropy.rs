@@ -0,0 +1,117 @@
+//! The entropy source behind [`Pubkey::new_rand`] and other random values.
+//!
+//! Where randomness comes from depends on the target:
+//!
+//! - Native hosts use the operating system's generator through `rand`.
+//! - `wasm32` in a browser or Node.js uses `crypto.getRandomValues` through
+//!   `getrandom`'s `js` backend.
+//! - SBF programs, and `wasm32` hosts without JavaScript, have no source of
+//!   entropy, so one must be supplied with [`register_entropy_backend!`].
+//!
+//! Supplying a backend requires the `custom-entropy` feature, which replaces
+//! the default backend on every target:
+//!
+//! ```ignore
+//! fn fill_from_hardware(dest: &mut [u8]) {
+//!     // ...
+//! }
+//!
+//! solana_program::register_entropy_backend!(fill_from_hardware);
+//! ```
+//!
+//! Without the feature, SBF builds have no [`fill`] at all, so that code
+//! relying on it fails to compile rather than returning predictable bytes.
+//! With the feature but no registered backend, linking fails on the missing
+//! `__solana_program_entropy_fill` symbol.
+//!
+//! [`Pubkey::new_rand`]: crate::pubkey::Pubkey::new_rand
+//! [`register_entropy_backend!`]: crate::register_entropy_backend
+
+#![cfg(any(not(target_os = "solana"), feature = "custom-entropy"))]
+
+/// Fills a buffer with random bytes.
+pub type FillFn = fn(&mut [u8]);
+
+#[cfg(feature = "custom-entropy")]
+extern "Rust" {
+    fn __solana_program_entropy_fill(dest: &mut [u8]);
+}
+
+/// Fills `dest` with random bytes from the configured backend.
+pub fn fill(dest: &mut [u8]) {
+    // Safety: the symbol is only defined by `register_entropy_backend!`, as a
+    // safe function of this signature
+    #[cfg(feature = "custom-entropy")]
+    unsafe {
+        __solana_program_entropy_fill(dest);
+    }
+    #[cfg(not(feature = "custom-entropy"))]
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), dest);
+}
+
+/// Returns an array of random bytes from the configured backend.
+pub fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+    fill(&mut bytes);
+    bytes
+}
+
+/// Registers a [`FillFn`] as the backend of [`fill`].
+///
+/// Requires the `custom-entropy` feature, and may be used at most once in the
+/// final binary.
+#[cfg(feature = "custom-entropy")]
+#[macro_export]
+macro_rules! register_entropy_backend {
+    ($fill:path) => {
+        #[no_mangle]
+        extern "Rust" fn __solana_program_entropy_fill(dest: &mut [u8]) {
+            let fill: $crate::entropy::FillFn = $fill;
+            fill(dest)
+        }
+    };
+}
+
+/// Registers a [`FillFn`] as the backend of [`fill`].
+///
+/// Requires the `custom-entropy` feature, and may be used at most once in the
+/// final binary.
+#[cfg(not(feature = "custom-entropy"))]
+#[macro_export]
+macro_rules! register_entropy_backend {
+    ($fill:path) => {
+        compile_error!(
+            "register_entropy_backend! requires the `custom-entropy` feature of solana-program"
+        );
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "custom-entropy")]
+    fn fill_counting(dest: &mut [u8]) {
+        for (i, byte) in dest.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+    }
+
+    #[cfg(feature = "custom-entropy")]
+    register_entropy_backend!(fill_counting);
+
+    #[test]
+    #[cfg(not(feature = "custom-entropy"))]
+    fn test_default_backend() {
+        let a = random_bytes::<32>();
+        let b = random_bytes::<32>();
+        assert_ne!(a, b);
+        assert_ne!(a, [0; 32]);
+    }
+
+    #[test]
+    #[cfg(feature = "custom-entropy")]
+    fn test_custom_backend() {
+        assert_eq!(random_bytes::<4>(), [0, 1, 2, 3]);
+    }
+}
@@ -11,7 +11,7 @@ use {
     std::{
         alloc::Layout,
         cell::RefCell,
-        mem::size_of,
+        mem::{size_of, MaybeUninit},
         ptr::null_mut,
         rc::Rc,
         result::Result as ResultGeneric,
@@ -39,6 +39,11 @@ pub const HEAP_LENGTH: usize = 32 * 1024;
 /// Value used to indicate that a serialized account is not a duplicate
 pub const NON_DUP_MARKER: u8 = u8::MAX;
 
+/// Maximum number of accounts passed to the program by [`entrypoint_no_alloc!`].
+///
+/// [`entrypoint_no_alloc!`]: crate::entrypoint_no_alloc!
+pub const MAX_ENTRYPOINT_ACCOUNT_INFOS: usize = 64;
+
 /// Declare the program entrypoint and set up global handlers.
 ///
 /// This macro emits the common boilerplate necessary to begin program
@@ -139,6 +144,74 @@ macro_rules! entrypoint {
     };
 }
 
+/// Declare the program entrypoint and set up global handlers, without
+/// allocating the accounts on the heap.
+///
+/// This is the same as [`entrypoint!`], except that up to
+/// [`MAX_ENTRYPOINT_ACCOUNT_INFOS`] accounts are deserialized into an array on
+/// the stack with [`deserialize_into`] rather than into a heap-allocated
+/// vector. Instructions with more accounts fail with
+/// [`ProgramError::InvalidArgument`] before the program is called.
+///
+/// This saves the compute units spent on the allocation, and allows programs
+/// that never allocate to provide a [custom heap][custom_heap_default] that
+/// does nothing.
+///
+/// # Targets
+///
+/// The same program source builds for each target, with the loader input
+/// layout read the same way:
+///
+/// - On SBF, `entrypoint` is exported for the loader, and the default heap
+///   and panic handler are defined unless the program's `custom-heap` and
+///   `custom-panic` features are enabled.
+/// - On wasm32, `entrypoint` is exported for the host to call with a pointer
+///   to the serialized input in the module's linear memory. The host provides
+///   the heap with [`custom_heap!`], or the target's own allocator is used.
+/// - In native builds, `entrypoint` is an ordinary function that is not
+///   exported, so several programs can be linked into one test binary, and
+///   tests can call it with input they serialized themselves.
+///
+/// [`entrypoint!`]: crate::entrypoint!
+/// [`custom_heap!`]: crate::custom_heap!
+/// [`MAX_ENTRYPOINT_ACCOUNT_INFOS`]: crate::entrypoint::MAX_ENTRYPOINT_ACCOUNT_INFOS
+/// [`deserialize_into`]: crate::entrypoint::deserialize_into
+/// [`ProgramError::InvalidArgument`]: crate::program_error::ProgramError::InvalidArgument
+/// [custom_heap_default]: crate::custom_heap_default
+#[macro_export]
+macro_rules! entrypoint_no_alloc {
+    ($process_instruction:ident) => {
+        /// # Safety
+        #[cfg_attr(any(target_os = "solana", target_arch = "wasm32"), no_mangle)]
+        pub unsafe extern "C" fn entrypoint(input: *mut u8) -> u64 {
+            use {std::mem::MaybeUninit, $crate::account_info::AccountInfo};
+            // A `const` is the only way to repeat a non-`Copy` value in an
+            // array expression; it is never mutated.
+            #[allow(clippy::declare_interior_mutable_const)]
+            const UNINIT_ACCOUNT_INFO: MaybeUninit<AccountInfo> =
+                MaybeUninit::<AccountInfo>::uninit();
+            let mut accounts =
+                [UNINIT_ACCOUNT_INFO; $crate::entrypoint::MAX_ENTRYPOINT_ACCOUNT_INFOS];
+            let (program_id, num_accounts, instruction_data) =
+                match unsafe { $crate::entrypoint::deserialize_into(input, &mut accounts) } {
+                    Ok(input) => input,
+                    Err(error) => return error.into(),
+                };
+            // The first `num_accounts` entries were initialized by `deserialize_into`
+            let accounts = unsafe {
+                &*(&accounts[..num_accounts] as *const [MaybeUninit<AccountInfo<'_>>]
+                    as *const [AccountInfo<'_>])
+            };
+            match $process_instruction(&program_id, accounts, &instruction_data) {
+                Ok(()) => $crate::entrypoint::SUCCESS,
+                Err(error) => error.into(),
+            }
+        }
+        $crate::custom_heap_default!();
+        $crate::custom_panic_default!();
+    };
+}
+
 /// Define the default global allocator.
 ///
 /// The default global allocator is enabled only if the calling crate has not
@@ -170,6 +243,43 @@ macro_rules! custom_heap_default {
     };
 }
 
+/// Define a [`BumpAllocator`] over a given heap region as the global allocator.
+///
+/// This is for hosts other than the Solana runtime, such as wasm32 hosts, that
+/// place the program heap somewhere other than [`HEAP_START_ADDRESS`] or give
+/// it a different size. The host must reserve `len` bytes starting at `start`
+/// for the heap, with `start` aligned to a `usize`, and zero the first `usize`
+/// before the program runs. Programs on those hosts should enable their
+/// `custom-heap` feature so that [`entrypoint`] does not also define the
+/// default allocator.
+///
+/// The allocator is defined as a static with the given name, through which the
+/// program can check how much of the heap is left with
+/// [`BumpAllocator::remaining`].
+///
+/// To use the target's own allocator instead, such as dlmalloc on wasm32,
+/// enable `custom-heap` and don't call this macro.
+///
+/// # Examples
+///
+/// ```ignore
+/// solana_program::custom_heap!(HEAP, start = 0x1_0000, len = 64 * 1024);
+///
+/// fn heap_remaining() -> usize {
+///     unsafe { HEAP.remaining() }
+/// }
+/// ```
+#[macro_export]
+macro_rules! custom_heap {
+    ($name:ident, start = $start:expr, len = $len:expr $(,)?) => {
+        #[global_allocator]
+        static $name: $crate::entrypoint::BumpAllocator = $crate::entrypoint::BumpAllocator {
+            start: $start,
+            len: $len,
+        };
+    };
+}
+
 /// Define the default global panic handler.
 ///
 /// This must be used if the [`entrypoint`] macro is not used, and no other
@@ -231,6 +341,30 @@ pub struct BumpAllocator {
     pub start: usize,
     pub len: usize,
 }
+
+impl BumpAllocator {
+    /// The number of bytes left in the heap.
+    ///
+    /// An allocation of this size may still fail if it needs more alignment
+    /// than the heap's current position has.
+    ///
+    /// # Safety
+    ///
+    /// `start` and `len` must describe the heap this allocator allocates from,
+    /// as they do for one defined by [`custom_heap_default`] or
+    /// [`custom_heap`].
+    pub unsafe fn remaining(&self) -> usize {
+        // The first word of the heap holds the position of the last
+        // allocation, or zero before the first one
+        let pos = *(self.start as *const usize);
+        let end = if pos == 0 {
+            self.start.saturating_add(self.len)
+        } else {
+            pos
+        };
+        end.saturating_sub(self.start.saturating_add(size_of::<*mut u8>()))
+    }
+}
 /// Integer arithmetic in this global allocator implementation is safe when
 /// operating on the prescribed `HEAP_START_ADDRESS` and `HEAP_LENGTH`. Any
 /// other use may overflow and is thus unsupported and at one's own risk.
@@ -265,6 +399,103 @@ pub const MAX_PERMITTED_DATA_INCREASE: usize = 1_024 * 10;
 /// `assert_eq(std::mem::align_of::<u128>(), 8)` is true for BPF but not for some host machines
 pub const BPF_ALIGN_OF_U128: usize = 8;
 
+/// Deserialize the next non-duplicate account at `offset`, returning it and the
+/// offset following it.
+///
+/// # Safety
+#[allow(clippy::arithmetic_side_effects)]
+#[inline(always)]
+unsafe fn deserialize_account_info_no_dup<'a>(
+    input: *mut u8,
+    mut offset: usize,
+) -> (AccountInfo<'a>, usize) {
+    #[allow(clippy::cast_ptr_alignment)]
+    let is_signer = *(input.add(offset) as *const u8) != 0;
+    offset += size_of::<u8>();
+
+    #[allow(clippy::cast_ptr_alignment)]
+    let is_writable = *(input.add(offset) as *const u8) != 0;
+    offset += size_of::<u8>();
+
+    #[allow(clippy::cast_ptr_alignment)]
+    let executable = *(input.add(offset) as *const u8) != 0;
+    offset += size_of::<u8>();
+
+    // The original data length is stored here because these 4 bytes were
+    // originally only used for padding and served as a good location to
+    // track the original size of the account data in a compatible way.
+    let original_data_len_offset = offset;
+    offset += size_of::<u32>();
+
+    let key: &Pubkey = &*(input.add(offset) as *const Pubkey);
+    offset += size_of::<Pubkey>();
+
+    let owner: &Pubkey = &*(input.add(offset) as *const Pubkey);
+    offset += size_of::<Pubkey>();
+
+    #[allow(clippy::cast_ptr_alignment)]
+    let lamports = Rc::new(RefCell::new(&mut *(input.add(offset) as *mut u64)));
+    offset += size_of::<u64>();
+
+    #[allow(clippy::cast_ptr_alignment)]
+    let data_len = *(input.add(offset) as *const u64) as usize;
+    offset += size_of::<u64>();
+
+    // Store the original data length for detecting invalid reallocations and
+    // requires that MAX_PERMITTED_DATA_LENGTH fits in a u32
+    *(input.add(original_data_len_offset) as *mut u32) = data_len as u32;
+
+    let data = Rc::new(RefCell::new({
+        from_raw_parts_mut(input.add(offset), data_len)
+    }));
+    offset += data_len + MAX_PERMITTED_DATA_INCREASE;
+    offset += (offset as *const u8).align_offset(BPF_ALIGN_OF_U128); // padding
+
+    #[allow(clippy::cast_ptr_alignment)]
+    let rent_epoch = *(input.add(offset) as *const u64);
+    offset += size_of::<u64>();
+
+    (
+        AccountInfo {
+            key,
+            is_signer,
+            is_writable,
+            lamports,
+            data,
+            owner,
+            executable,
+            rent_epoch,
+        },
+        offset,
+    )
+}
+
+/// Deserialize the instruction data and program id that follow the accounts
+/// at `offset`.
+///
+/// # Safety
+#[allow(clippy::arithmetic_side_effects)]
+#[inline(always)]
+unsafe fn deserialize_instruction_data_and_program_id<'a>(
+    input: *mut u8,
+    mut offset: usize,
+) -> (&'a Pubkey, &'a [u8]) {
+    // Instruction data
+
+    #[allow(clippy::cast_ptr_alignment)]
+    let instruction_data_len = *(input.add(offset) as *const u64) as usize;
+    offset += size_of::<u64>();
+
+    let instruction_data = { from_raw_parts(input.add(offset), instruction_data_len) };
+    offset += instruction_data_len;
+
+    // Program Id
+
+    let program_id: &Pubkey = &*(input.add(offset) as *const Pubkey);
+
+    (program_id, instruction_data)
+}
+
 /// Deserialize the input arguments
 ///
 /// The integer arithmetic in this method is safe when called on a buffer that was
@@ -290,62 +521,9 @@ pub unsafe fn deserialize<'a>(input: *mut u8) -> (&'a Pubkey, Vec<AccountInfo<'a
         let dup_info = *(input.add(offset) as *const u8);
         offset += size_of::<u8>();
         if dup_info == NON_DUP_MARKER {
-            #[allow(clippy::cast_ptr_alignment)]
-            let is_signer = *(input.add(offset) as *const u8) != 0;
-            offset += size_of::<u8>();
-
-            #[allow(clippy::cast_ptr_alignment)]
-            let is_writable = *(input.add(offset) as *const u8) != 0;
-            offset += size_of::<u8>();
-
-            #[allow(clippy::cast_ptr_alignment)]
-            let executable = *(input.add(offset) as *const u8) != 0;
-            offset += size_of::<u8>();
-
-            // The original data length is stored here because these 4 bytes were
-            // originally only used for padding and served as a good location to
-            // track the original size of the account data in a compatible way.
-            let original_data_len_offset = offset;
-            offset += size_of::<u32>();
-
-            let key: &Pubkey = &*(input.add(offset) as *const Pubkey);
-            offset += size_of::<Pubkey>();
-
-            let owner: &Pubkey = &*(input.add(offset) as *const Pubkey);
-            offset += size_of::<Pubkey>();
-
-            #[allow(clippy::cast_ptr_alignment)]
-            let lamports = Rc::new(RefCell::new(&mut *(input.add(offset) as *mut u64)));
-            offset += size_of::<u64>();
-
-            #[allow(clippy::cast_ptr_alignment)]
-            let data_len = *(input.add(offset) as *const u64) as usize;
-            offset += size_of::<u64>();
-
-            // Store the original data length for detecting invalid reallocations and
-            // requires that MAX_PERMITTED_DATA_LENGTH fits in a u32
-            *(input.add(original_data_len_offset) as *mut u32) = data_len as u32;
-
-            let data = Rc::new(RefCell::new({
-                from_raw_parts_mut(input.add(offset), data_len)
-            }));
-            offset += data_len + MAX_PERMITTED_DATA_INCREASE;
-            offset += (offset as *const u8).align_offset(BPF_ALIGN_OF_U128); // padding
-
-            #[allow(clippy::cast_ptr_alignment)]
-            let rent_epoch = *(input.add(offset) as *const u64);
-            offset += size_of::<u64>();
-
-            accounts.push(AccountInfo {
-                key,
-                is_signer,
-                is_writable,
-                lamports,
-                data,
-                owner,
-                executable,
-                rent_epoch,
-            });
+            let (account_info, new_offset) = deserialize_account_info_no_dup(input, offset);
+            offset = new_offset;
+            accounts.push(account_info);
         } else {
             offset += 7; // padding
 
@@ -354,20 +532,60 @@ pub unsafe fn deserialize<'a>(input: *mut u8) -> (&'a Pubkey, Vec<AccountInfo<'a
         }
     }
 
-    // Instruction data
+    let (program_id, instruction_data) = deserialize_instruction_data_and_program_id(input, offset);
+
+    (program_id, accounts, instruction_data)
+}
+
+/// Deserialize the input arguments into a caller-provided array of accounts
+///
+/// Behaves like [`deserialize`], but writes the accounts into `accounts`
+/// instead of allocating a vector, and returns the number of accounts
+/// written. Fails with [`ProgramError::InvalidArgument`], without writing any
+/// accounts, if there are more accounts than `accounts` can hold.
+///
+/// The integer arithmetic in this method is safe when called on a buffer that was
+/// serialized by runtime. Use with buffers serialized otherwise is unsupported and
+/// done at one's own risk.
+///
+/// # Safety
+#[allow(clippy::arithmetic_side_effects)]
+pub unsafe fn deserialize_into<'a>(
+    input: *mut u8,
+    accounts: &mut [MaybeUninit<AccountInfo<'a>>],
+) -> Result<(&'a Pubkey, usize, &'a [u8]), ProgramError> {
+    let mut offset: usize = 0;
+
+    // Number of accounts present
 
     #[allow(clippy::cast_ptr_alignment)]
-    let instruction_data_len = *(input.add(offset) as *const u64) as usize;
+    let num_accounts = *(input.add(offset) as *const u64) as usize;
     offset += size_of::<u64>();
+    if num_accounts > accounts.len() {
+        return Err(ProgramError::InvalidArgument);
+    }
 
-    let instruction_data = { from_raw_parts(input.add(offset), instruction_data_len) };
-    offset += instruction_data_len;
+    // Account Infos
 
-    // Program Id
+    for i in 0..num_accounts {
+        let dup_info = *(input.add(offset) as *const u8);
+        offset += size_of::<u8>();
+        if dup_info == NON_DUP_MARKER {
+            let (account_info, new_offset) = deserialize_account_info_no_dup(input, offset);
+            offset = new_offset;
+            accounts[i].write(account_info);
+        } else {
+            offset += 7; // padding
 
-    let program_id: &Pubkey = &*(input.add(offset) as *const Pubkey);
+            // Duplicate account, clone the original
+            let account_info = accounts[dup_info as usize].assume_init_ref().clone();
+            accounts[i].write(account_info);
+        }
+    }
 
-    (program_id, accounts, instruction_data)
+    let (program_id, instruction_data) = deserialize_instruction_data_and_program_id(input, offset);
+
+    Ok((program_id, num_accounts, instruction_data))
 }
 
 #[cfg(test)]
@@ -421,6 +639,24 @@ mod test {
             let ptr = unsafe { allocator.alloc(Layout::from_size_align(1, 64).unwrap()) };
             assert_eq!(0, ptr.align_offset(64));
         }
+        // track the remaining space
+        {
+            let heap = [0u64; 16];
+            let allocator = BumpAllocator {
+                start: heap.as_ptr() as *const _ as usize,
+                len: 128,
+            };
+            assert_eq!(unsafe { allocator.remaining() }, 128 - size_of::<*mut u8>());
+            unsafe { allocator.alloc(Layout::from_size_align(3, 1).unwrap()) };
+            assert_eq!(unsafe { allocator.remaining() }, 125 - size_of::<*mut u8>());
+            unsafe { allocator.alloc(Layout::from_size_align(8, 8).unwrap()) };
+            assert_eq!(unsafe { allocator.remaining() }, 112 - size_of::<*mut u8>());
+            let ptr = unsafe {
+                allocator.alloc(Layout::from_size_align(allocator.remaining(), 1).unwrap())
+            };
+            assert_ne!(ptr, null_mut());
+            assert_eq!(unsafe { allocator.remaining() }, 0);
+        }
         // alloc entire block (minus the pos ptr)
         {
             let heap = [0u8; 128];
@@ -434,4 +670,135 @@ mod test {
             assert_eq!(0, ptr.align_offset(size_of::<u64>()));
         }
     }
+
+    // Serializes accounts in the loader input layout, `None` entries being
+    // duplicates of the first account.
+    fn serialize_input(
+        accounts: &[Option<(Pubkey, u64, &[u8])>],
+        instruction_data: &[u8],
+        program_id: &Pubkey,
+    ) -> Vec<u64> {
+        let mut bytes = (accounts.len() as u64).to_le_bytes().to_vec();
+        for account in accounts {
+            match account {
+                Some((key, lamports, data)) => {
+                    bytes.extend_from_slice(&[NON_DUP_MARKER, 1, 0, 0, 0, 0, 0, 0]);
+                    bytes.extend_from_slice(key.as_ref());
+                    bytes.extend_from_slice(program_id.as_ref());
+                    bytes.extend_from_slice(&lamports.to_le_bytes());
+                    bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+                    bytes.extend_from_slice(data);
+                    bytes.resize(bytes.len().saturating_add(MAX_PERMITTED_DATA_INCREASE), 0);
+                    bytes.resize(bytes.len().next_multiple_of(BPF_ALIGN_OF_U128), 0);
+                    bytes.extend_from_slice(&42u64.to_le_bytes());
+                }
+                None => bytes.extend_from_slice(&[0; 8]),
+            }
+        }
+        bytes.extend_from_slice(&(instruction_data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(instruction_data);
+        bytes.extend_from_slice(program_id.as_ref());
+        bytes.resize(bytes.len().next_multiple_of(size_of::<u64>()), 0);
+        bytes
+            .chunks(size_of::<u64>())
+            .map(|chunk| u64::from_ne_bytes(chunk.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_deserialize_into() {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const UNINIT_ACCOUNT_INFO: MaybeUninit<AccountInfo> = MaybeUninit::uninit();
+        let program_id = Pubkey::new_unique();
+        let (key0, key1) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let accounts = [
+            Some((key0, 10, &[1, 2, 3][..])),
+            Some((key1, 20, &[][..])),
+            None,
+        ];
+        let mut input = serialize_input(&accounts, &[7, 8], &program_id);
+        let (expected_program_id, expected_accounts, expected_data) =
+            unsafe { deserialize(input.as_mut_ptr() as *mut u8) };
+        assert_eq!(expected_program_id, &program_id);
+        assert_eq!(expected_data, &[7, 8]);
+        assert_eq!(expected_accounts.len(), 3);
+        assert_eq!(expected_accounts[0].key, &key0);
+        assert_eq!(*expected_accounts[0].data.borrow(), &[1, 2, 3]);
+        assert_eq!(expected_accounts[1].lamports(), 20);
+        assert_eq!(expected_accounts[2].key, &key0);
+        assert_eq!(expected_accounts[2].rent_epoch, 42);
+
+        let mut input = serialize_input(&accounts, &[7, 8], &program_id);
+        let mut account_infos = [UNINIT_ACCOUNT_INFO; 4];
+        let (deserialized_program_id, num_accounts, instruction_data) =
+            unsafe { deserialize_into(input.as_mut_ptr() as *mut u8, &mut account_infos) }.unwrap();
+        assert_eq!(deserialized_program_id, &program_id);
+        assert_eq!(instruction_data, &[7, 8]);
+        assert_eq!(num_accounts, 3);
+        for (account_info, expected) in account_infos.iter().zip(&expected_accounts) {
+            let account_info = unsafe { account_info.assume_init_ref() };
+            assert_eq!(account_info.key, expected.key);
+            assert_eq!(account_info.lamports(), expected.lamports());
+            assert_eq!(*account_info.data.borrow(), *expected.data.borrow());
+            assert_eq!(account_info.rent_epoch, expected.rent_epoch);
+        }
+
+        // More accounts than the capacity are rejected
+        let mut input = serialize_input(&accounts, &[7, 8], &program_id);
+        let mut account_infos = [UNINIT_ACCOUNT_INFO; 2];
+        assert_eq!(
+            unsafe { deserialize_into(input.as_mut_ptr() as *mut u8, &mut account_infos) },
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    mod no_alloc_program {
+        use crate::{
+            account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+            pubkey::Pubkey,
+        };
+
+        crate::entrypoint_no_alloc!(process_instruction);
+
+        // Moves the instruction's first byte of lamports from the first
+        // account to the second
+        fn process_instruction(
+            _program_id: &Pubkey,
+            accounts: &[AccountInfo],
+            instruction_data: &[u8],
+        ) -> ProgramResult {
+            let lamports = u64::from(instruction_data[0]);
+            let mut from = accounts[0].try_borrow_mut_lamports()?;
+            **from = from
+                .checked_sub(lamports)
+                .ok_or(ProgramError::InsufficientFunds)?;
+            let mut to = accounts[1].try_borrow_mut_lamports()?;
+            **to = to
+                .checked_add(lamports)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_entrypoint_no_alloc() {
+        let program_id = Pubkey::new_unique();
+        let accounts = [
+            Some((Pubkey::new_unique(), 10, &[][..])),
+            Some((Pubkey::new_unique(), 20, &[][..])),
+        ];
+        let mut input = serialize_input(&accounts, &[3], &program_id);
+        let input = input.as_mut_ptr() as *mut u8;
+        assert_eq!(unsafe { no_alloc_program::entrypoint(input) }, SUCCESS);
+        let (_program_id, accounts, _instruction_data) = unsafe { deserialize(input) };
+        assert_eq!(accounts[0].lamports(), 7);
+        assert_eq!(accounts[1].lamports(), 23);
+
+        let too_many_accounts = vec![None; MAX_ENTRYPOINT_ACCOUNT_INFOS.saturating_add(1)];
+        let mut input = serialize_input(&too_many_accounts, &[3], &program_id);
+        assert_eq!(
+            unsafe { no_alloc_program::entrypoint(input.as_mut_ptr() as *mut u8) },
+            u64::from(ProgramError::InvalidArgument)
+        );
+    }
 }
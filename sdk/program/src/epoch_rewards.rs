@@ -28,6 +28,18 @@ impl EpochRewards {
 
         self.distributed_rewards.add_assign(amount);
     }
+
+    /// Rewards for the current epoch that have not been distributed yet, in
+    /// lamports.
+    pub fn remaining_rewards(&self) -> u64 {
+        self.total_rewards.saturating_sub(self.distributed_rewards)
+    }
+
+    /// Whether partitioned reward distribution is still in progress at
+    /// `block_height`.
+    pub fn is_active(&self, block_height: u64) -> bool {
+        self.remaining_rewards() > 0 && block_height <= self.distribution_complete_block_height
+    }
 }
 
 #[cfg(test)]
@@ -66,6 +78,24 @@ mod tests {
         assert_eq!(epoch_rewards.distributed_rewards, 100);
     }
 
+    #[test]
+    fn test_epoch_rewards_is_active() {
+        let mut epoch_rewards = EpochRewards::new(100, 0, 64);
+        assert_eq!(epoch_rewards.remaining_rewards(), 100);
+        assert!(epoch_rewards.is_active(63));
+        assert!(epoch_rewards.is_active(64));
+        assert!(!epoch_rewards.is_active(65));
+
+        epoch_rewards.distribute(60);
+        assert_eq!(epoch_rewards.remaining_rewards(), 40);
+        assert!(epoch_rewards.is_active(64));
+
+        epoch_rewards.distribute(40);
+        assert_eq!(epoch_rewards.remaining_rewards(), 0);
+        assert!(!epoch_rewards.is_active(63));
+        assert!(!EpochRewards::default().is_active(0));
+    }
+
     #[test]
     #[should_panic(
         expected = "self.distributed_rewards.saturating_add(amount) <= self.total_rewards"
@@ -182,6 +182,12 @@ impl EpochSchedule {
             .saturating_add(self.get_slots_in_epoch(epoch))
             .saturating_sub(1)
     }
+
+    /// get the number of slots after the given slot until the end of its epoch
+    pub fn get_slots_remaining_in_epoch(&self, slot: Slot) -> u64 {
+        self.get_last_slot_in_epoch(self.get_epoch(slot))
+            .saturating_sub(slot)
+    }
 }
 
 #[cfg(test)]
@@ -246,6 +252,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_slots_remaining_in_epoch() {
+        let epoch_schedule = EpochSchedule::custom(64, 64, true);
+        // warmup epochs are 32 and 64 slots long
+        assert_eq!(epoch_schedule.get_slots_remaining_in_epoch(0), 31);
+        assert_eq!(epoch_schedule.get_slots_remaining_in_epoch(31), 0);
+        assert_eq!(epoch_schedule.get_slots_remaining_in_epoch(32), 63);
+        let first_normal_slot = epoch_schedule.first_normal_slot;
+        assert_eq!(
+            epoch_schedule.get_slots_remaining_in_epoch(first_normal_slot + 10),
+            53
+        );
+    }
+
     #[test]
     fn test_clone() {
         let epoch_schedule = EpochSchedule {
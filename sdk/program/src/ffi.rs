@@ -0,0 +1,855 @@
+//! C bindings for hosts written in other languages.
+//!
+//! Pubkeys and hashes cross the boundary by value as [`SolanaPubkey`] and
+//! [`SolanaHash`], and byte strings as [`SolanaBytes`] pointing into memory
+//! owned by the caller. Instructions are opaque [`SolanaInstruction`] handles
+//! allocated here, which must be released with [`solana_instruction_free`].
+//!
+//! Functions report failure with a [`SolanaStatus`] rather than panicking.
+//! Flags and enums read from the caller are passed as plain integers and
+//! validated here, since an out-of-range `bool` or enum is undefined behavior
+//! in Rust.
+//! Those producing variable length output take a caller-supplied buffer, and
+//! always store the length of the full output so that a caller can retry with
+//! a large enough buffer after [`SolanaStatus::BufferTooSmall`].
+//!
+//! With the `ffi` feature, the build script writes a C header declaring these
+//! items to `solana_program.h` in its output directory, using the settings in
+//! `cbindgen.toml`.
+//!
+//! # Safety
+//!
+//! Every function here has the same requirements: non-null pointers must be
+//! valid for reads, or writes for out-parameters, of their pointee type, and
+//! `SolanaBytes` must describe readable memory. Strings must be
+//! NUL-terminated. Instruction handles must come from this library and not be
+//! used after being freed.
+
+#![cfg(all(feature = "ffi", not(target_os = "solana")))]
+// The shared safety requirements are documented once, above
+#![allow(clippy::missing_safety_doc)]
+
+use {
+    crate::{
+        hash::{hashv, Hash},
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+        pubkey::{Pubkey, MAX_SEEDS},
+        stake::{
+            self,
+            state::{Authorized, Lockup, StakeAuthorize},
+        },
+    },
+    std::{collections::BTreeSet, ffi::CStr, os::raw::c_char, ptr, slice},
+};
+
+/// Outcome of a call into this library.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolanaStatus {
+    Ok = 0,
+    /// A required pointer was null, or a string was not valid UTF-8
+    InvalidArgument,
+    /// A pubkey string was not valid base58 of the right length
+    InvalidPubkey,
+    /// Seeds were too long or too many, or derived an address on the curve
+    InvalidSeeds,
+    /// An output buffer was too small for the result, whose length was stored
+    BufferTooSmall,
+}
+
+/// A 32-byte account address.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolanaPubkey {
+    pub bytes: [u8; 32],
+}
+
+/// A 32-byte SHA-256 hash.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolanaHash {
+    pub bytes: [u8; 32],
+}
+
+/// A borrowed byte string. `data` may be null if `len` is 0.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SolanaBytes {
+    pub data: *const u8,
+    pub len: usize,
+}
+
+/// An account passed to an instruction. The flags must be 0 or 1.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolanaAccountMeta {
+    pub pubkey: SolanaPubkey,
+    pub is_signer: u8,
+    pub is_writable: u8,
+}
+
+/// Which authority of a stake account to change, passed to functions as a
+/// `uint32_t`.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolanaStakeAuthorize {
+    Staker = 0,
+    Withdrawer = 1,
+}
+
+/// An opaque instruction allocated by this library.
+pub struct SolanaInstruction(Instruction);
+
+impl From<Pubkey> for SolanaPubkey {
+    fn from(pubkey: Pubkey) -> Self {
+        Self {
+            bytes: pubkey.to_bytes(),
+        }
+    }
+}
+
+impl From<SolanaPubkey> for Pubkey {
+    fn from(pubkey: SolanaPubkey) -> Self {
+        Pubkey::from(pubkey.bytes)
+    }
+}
+
+impl From<Hash> for SolanaHash {
+    fn from(hash: Hash) -> Self {
+        Self {
+            bytes: hash.to_bytes(),
+        }
+    }
+}
+
+impl From<SolanaHash> for Hash {
+    fn from(hash: SolanaHash) -> Self {
+        Hash::new_from_array(hash.bytes)
+    }
+}
+
+fn bool_from_raw(flag: u8) -> Option<bool> {
+    match flag {
+        0 => Some(false),
+        1 => Some(true),
+        _ => None,
+    }
+}
+
+impl TryFrom<SolanaAccountMeta> for AccountMeta {
+    type Error = SolanaStatus;
+
+    fn try_from(meta: SolanaAccountMeta) -> Result<Self, Self::Error> {
+        Ok(AccountMeta {
+            pubkey: meta.pubkey.into(),
+            is_signer: bool_from_raw(meta.is_signer).ok_or(SolanaStatus::InvalidArgument)?,
+            is_writable: bool_from_raw(meta.is_writable).ok_or(SolanaStatus::InvalidArgument)?,
+        })
+    }
+}
+
+impl From<AccountMeta> for SolanaAccountMeta {
+    fn from(meta: AccountMeta) -> Self {
+        Self {
+            pubkey: meta.pubkey.into(),
+            is_signer: meta.is_signer.into(),
+            is_writable: meta.is_writable.into(),
+        }
+    }
+}
+
+/// Reads a [`SolanaStakeAuthorize`] value.
+fn stake_authorize_from_raw(stake_authorize: u32) -> Option<StakeAuthorize> {
+    match stake_authorize {
+        x if x == SolanaStakeAuthorize::Staker as u32 => Some(StakeAuthorize::Staker),
+        x if x == SolanaStakeAuthorize::Withdrawer as u32 => Some(StakeAuthorize::Withdrawer),
+        _ => None,
+    }
+}
+
+/// Reads `len` values starting at `data`, which may be null if `len` is 0.
+unsafe fn slice_from_raw<'a, T>(data: *const T, len: usize) -> Option<&'a [T]> {
+    if len == 0 {
+        Some(&[])
+    } else if data.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(data, len))
+    }
+}
+
+unsafe fn bytes_from_raw<'a>(bytes: &SolanaBytes) -> Option<&'a [u8]> {
+    slice_from_raw(bytes.data, bytes.len)
+}
+
+unsafe fn seeds_from_raw<'a>(seeds: *const SolanaBytes, seeds_len: usize) -> Option<Vec<&'a [u8]>> {
+    slice_from_raw(seeds, seeds_len)?
+        .iter()
+        .map(|seed| bytes_from_raw(seed))
+        .collect()
+}
+
+/// Copies `output` to the caller's buffer, storing its length in `out_len`.
+unsafe fn write_output(
+    output: &[u8],
+    out: *mut u8,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> SolanaStatus {
+    if out_len.is_null() {
+        return SolanaStatus::InvalidArgument;
+    }
+    *out_len = output.len();
+    if output.len() > out_cap {
+        return SolanaStatus::BufferTooSmall;
+    }
+    if !output.is_empty() {
+        if out.is_null() {
+            return SolanaStatus::InvalidArgument;
+        }
+        ptr::copy_nonoverlapping(output.as_ptr(), out, output.len());
+    }
+    SolanaStatus::Ok
+}
+
+/// Parses a NUL-terminated base58 string into `out`.
+#[no_mangle]
+pub unsafe extern "C" fn solana_pubkey_from_str(
+    s: *const c_char,
+    out: *mut SolanaPubkey,
+) -> SolanaStatus {
+    if s.is_null() || out.is_null() {
+        return SolanaStatus::InvalidArgument;
+    }
+    let Ok(s) = CStr::from_ptr(s).to_str() else {
+        return SolanaStatus::InvalidArgument;
+    };
+    match s.parse::<Pubkey>() {
+        Ok(pubkey) => {
+            *out = pubkey.into();
+            SolanaStatus::Ok
+        }
+        Err(_) => SolanaStatus::InvalidPubkey,
+    }
+}
+
+/// Writes `pubkey` as a NUL-terminated base58 string to `out`.
+///
+/// `out_len` receives the length of the string, excluding the terminator.
+/// Buffers of 45 bytes fit any pubkey.
+#[no_mangle]
+pub unsafe extern "C" fn solana_pubkey_to_str(
+    pubkey: *const SolanaPubkey,
+    out: *mut c_char,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> SolanaStatus {
+    if pubkey.is_null() {
+        return SolanaStatus::InvalidArgument;
+    }
+    let mut s = Pubkey::from(*pubkey).to_string().into_bytes();
+    s.push(0);
+    let status = write_output(&s, out.cast(), out_cap, out_len);
+    if !out_len.is_null() {
+        *out_len = (*out_len).saturating_sub(1);
+    }
+    status
+}
+
+/// Like `Pubkey::create_program_address`.
+#[no_mangle]
+pub unsafe extern "C" fn solana_pubkey_create_program_address(
+    seeds: *const SolanaBytes,
+    seeds_len: usize,
+    program_id: *const SolanaPubkey,
+    out: *mut SolanaPubkey,
+) -> SolanaStatus {
+    if program_id.is_null() || out.is_null() {
+        return SolanaStatus::InvalidArgument;
+    }
+    let Some(seeds) = seeds_from_raw(seeds, seeds_len) else {
+        return SolanaStatus::InvalidArgument;
+    };
+    match Pubkey::create_program_address(&seeds, &(*program_id).into()) {
+        Ok(pubkey) => {
+            *out = pubkey.into();
+            SolanaStatus::Ok
+        }
+        Err(_) => SolanaStatus::InvalidSeeds,
+    }
+}
+
+/// Like `Pubkey::try_find_program_address`, storing the bump seed in `bump`.
+#[no_mangle]
+pub unsafe extern "C" fn solana_pubkey_find_program_address(
+    seeds: *const SolanaBytes,
+    seeds_len: usize,
+    program_id: *const SolanaPubkey,
+    out: *mut SolanaPubkey,
+    bump: *mut u8,
+) -> SolanaStatus {
+    if program_id.is_null() || out.is_null() || bump.is_null() {
+        return SolanaStatus::InvalidArgument;
+    }
+    let Some(seeds) = seeds_from_raw(seeds, seeds_len) else {
+        return SolanaStatus::InvalidArgument;
+    };
+    // Leave room for the bump seed
+    if seeds.len() >= MAX_SEEDS {
+        return SolanaStatus::InvalidSeeds;
+    }
+    match Pubkey::try_find_program_address(&seeds, &(*program_id).into()) {
+        Some((pubkey, bump_seed)) => {
+            *out = pubkey.into();
+            *bump = bump_seed;
+            SolanaStatus::Ok
+        }
+        None => SolanaStatus::InvalidSeeds,
+    }
+}
+
+/// Like `Pubkey::create_with_seed`. `seed` must be UTF-8.
+#[no_mangle]
+pub unsafe extern "C" fn solana_pubkey_create_with_seed(
+    base: *const SolanaPubkey,
+    seed: SolanaBytes,
+    owner: *const SolanaPubkey,
+    out: *mut SolanaPubkey,
+) -> SolanaStatus {
+    if base.is_null() || owner.is_null() || out.is_null() {
+        return SolanaStatus::InvalidArgument;
+    }
+    let Some(Ok(seed)) = bytes_from_raw(&seed).map(std::str::from_utf8) else {
+        return SolanaStatus::InvalidArgument;
+    };
+    match Pubkey::create_with_seed(&(*base).into(), seed, &(*owner).into()) {
+        Ok(pubkey) => {
+            *out = pubkey.into();
+            SolanaStatus::Ok
+        }
+        Err(_) => SolanaStatus::InvalidSeeds,
+    }
+}
+
+/// Hashes the concatenation of `vals`, like `hashv`.
+#[no_mangle]
+pub unsafe extern "C" fn solana_hashv(
+    vals: *const SolanaBytes,
+    vals_len: usize,
+    out: *mut SolanaHash,
+) -> SolanaStatus {
+    if out.is_null() {
+        return SolanaStatus::InvalidArgument;
+    }
+    let Some(vals) = seeds_from_raw(vals, vals_len) else {
+        return SolanaStatus::InvalidArgument;
+    };
+    *out = hashv(&vals).into();
+    SolanaStatus::Ok
+}
+
+/// Allocates an instruction, or returns null if an argument is invalid,
+/// including an account flag other than 0 or 1.
+#[no_mangle]
+pub unsafe extern "C" fn solana_instruction_new(
+    program_id: *const SolanaPubkey,
+    accounts: *const SolanaAccountMeta,
+    accounts_len: usize,
+    data: SolanaBytes,
+) -> *mut SolanaInstruction {
+    if program_id.is_null() {
+        return ptr::null_mut();
+    }
+    let (Some(accounts), Some(data)) = (
+        slice_from_raw(accounts, accounts_len),
+        bytes_from_raw(&data),
+    ) else {
+        return ptr::null_mut();
+    };
+    let Ok(accounts) = accounts
+        .iter()
+        .copied()
+        .map(AccountMeta::try_from)
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return ptr::null_mut();
+    };
+    into_raw(Instruction::new_with_bytes(
+        (*program_id).into(),
+        data,
+        accounts,
+    ))
+}
+
+/// Releases an instruction allocated by this library. Ignores null.
+#[no_mangle]
+pub unsafe extern "C" fn solana_instruction_free(instruction: *mut SolanaInstruction) {
+    if !instruction.is_null() {
+        drop(Box::from_raw(instruction));
+    }
+}
+
+/// Serializes a legacy message of `instructions`, paid for by `payer` if not
+/// null, into `out`. Fails with [`SolanaStatus::InvalidArgument`] if the
+/// message would reference more than 255 distinct accounts.
+#[no_mangle]
+pub unsafe extern "C" fn solana_message_serialize(
+    instructions: *const *const SolanaInstruction,
+    instructions_len: usize,
+    payer: *const SolanaPubkey,
+    recent_blockhash: *const SolanaHash,
+    out: *mut u8,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> SolanaStatus {
+    if recent_blockhash.is_null() {
+        return SolanaStatus::InvalidArgument;
+    }
+    let Some(instructions) = slice_from_raw(instructions, instructions_len) else {
+        return SolanaStatus::InvalidArgument;
+    };
+    if instructions.iter().any(|instruction| instruction.is_null()) {
+        return SolanaStatus::InvalidArgument;
+    }
+    let instructions: Vec<Instruction> = instructions
+        .iter()
+        .map(|instruction| (**instruction).0.clone())
+        .collect();
+    let payer = (!payer.is_null()).then(|| Pubkey::from(*payer));
+    // `Message::new` panics if an account index or header count overflows a
+    // `u8`, which must not unwind into the caller. No transaction this large
+    // fits in a packet anyway.
+    let unique_keys: BTreeSet<&Pubkey> = instructions
+        .iter()
+        .flat_map(|instruction| {
+            std::iter::once(&instruction.program_id)
+                .chain(instruction.accounts.iter().map(|meta| &meta.pubkey))
+        })
+        .chain(payer.as_ref())
+        .collect();
+    if unique_keys.len() > usize::from(u8::MAX) {
+        return SolanaStatus::InvalidArgument;
+    }
+    let mut message = Message::new(&instructions, payer.as_ref());
+    message.recent_blockhash = (*recent_blockhash).into();
+    write_output(&message.serialize(), out, out_cap, out_len)
+}
+
+fn into_raw(instruction: Instruction) -> *mut SolanaInstruction {
+    Box::into_raw(Box::new(SolanaInstruction(instruction)))
+}
+
+/// Returns `None` if any of the pointers is null.
+unsafe fn pubkeys<const N: usize>(pubkeys: [*const SolanaPubkey; N]) -> Option<[Pubkey; N]> {
+    if pubkeys.iter().any(|pubkey| pubkey.is_null()) {
+        return None;
+    }
+    Some(pubkeys.map(|pubkey| Pubkey::from(*pubkey)))
+}
+
+/// Like `stake::instruction::initialize`, without a lockup. Returns null if
+/// a pointer is null.
+#[no_mangle]
+pub unsafe extern "C" fn solana_stake_initialize(
+    stake: *const SolanaPubkey,
+    staker: *const SolanaPubkey,
+    withdrawer: *const SolanaPubkey,
+) -> *mut SolanaInstruction {
+    let Some([stake, staker, withdrawer]) = pubkeys([stake, staker, withdrawer]) else {
+        return ptr::null_mut();
+    };
+    into_raw(stake::instruction::initialize(
+        &stake,
+        &Authorized { staker, withdrawer },
+        &Lockup::default(),
+    ))
+}
+
+/// Like `stake::instruction::delegate_stake`. Returns null if a pointer is
+/// null.
+#[no_mangle]
+pub unsafe extern "C" fn solana_stake_delegate(
+    stake: *const SolanaPubkey,
+    authorized: *const SolanaPubkey,
+    vote: *const SolanaPubkey,
+) -> *mut SolanaInstruction {
+    let Some([stake, authorized, vote]) = pubkeys([stake, authorized, vote]) else {
+        return ptr::null_mut();
+    };
+    into_raw(stake::instruction::delegate_stake(
+        &stake,
+        &authorized,
+        &vote,
+    ))
+}
+
+/// Like `stake::instruction::deactivate_stake`. Returns null if a pointer is
+/// null.
+#[no_mangle]
+pub unsafe extern "C" fn solana_stake_deactivate(
+    stake: *const SolanaPubkey,
+    authorized: *const SolanaPubkey,
+) -> *mut SolanaInstruction {
+    let Some([stake, authorized]) = pubkeys([stake, authorized]) else {
+        return ptr::null_mut();
+    };
+    into_raw(stake::instruction::deactivate_stake(&stake, &authorized))
+}
+
+/// Like `stake::instruction::withdraw`, with an optional `custodian`.
+/// Returns null if any other pointer is null.
+#[no_mangle]
+pub unsafe extern "C" fn solana_stake_withdraw(
+    stake: *const SolanaPubkey,
+    withdrawer: *const SolanaPubkey,
+    to: *const SolanaPubkey,
+    lamports: u64,
+    custodian: *const SolanaPubkey,
+) -> *mut SolanaInstruction {
+    let Some([stake, withdrawer, to]) = pubkeys([stake, withdrawer, to]) else {
+        return ptr::null_mut();
+    };
+    let custodian = (!custodian.is_null()).then(|| Pubkey::from(*custodian));
+    into_raw(stake::instruction::withdraw(
+        &stake,
+        &withdrawer,
+        &to,
+        lamports,
+        custodian.as_ref(),
+    ))
+}
+
+/// Like `stake::instruction::authorize`, with an optional `custodian`.
+/// `stake_authorize` is a [`SolanaStakeAuthorize`] value. Returns null if it
+/// is out of range or any other pointer is null.
+#[no_mangle]
+pub unsafe extern "C" fn solana_stake_authorize(
+    stake: *const SolanaPubkey,
+    authorized: *const SolanaPubkey,
+    new_authorized: *const SolanaPubkey,
+    stake_authorize: u32,
+    custodian: *const SolanaPubkey,
+) -> *mut SolanaInstruction {
+    let Some([stake, authorized, new_authorized]) = pubkeys([stake, authorized, new_authorized])
+    else {
+        return ptr::null_mut();
+    };
+    let Some(stake_authorize) = stake_authorize_from_raw(stake_authorize) else {
+        return ptr::null_mut();
+    };
+    let custodian = (!custodian.is_null()).then(|| Pubkey::from(*custodian));
+    into_raw(stake::instruction::authorize(
+        &stake,
+        &authorized,
+        &new_authorized,
+        stake_authorize,
+        custodian.as_ref(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::hash::hash, std::ffi::CString};
+
+    fn bytes(data: &[u8]) -> SolanaBytes {
+        SolanaBytes {
+            data: data.as_ptr(),
+            len: data.len(),
+        }
+    }
+
+    #[test]
+    fn test_pubkey_str_round_trip() {
+        let pubkey = Pubkey::new_unique();
+        let mut buf = [0 as c_char; 45];
+        let mut len = 0;
+        unsafe {
+            assert_eq!(
+                solana_pubkey_to_str(&pubkey.into(), buf.as_mut_ptr(), 3, &mut len),
+                SolanaStatus::BufferTooSmall
+            );
+            assert_eq!(len, pubkey.to_string().len());
+            assert_eq!(
+                solana_pubkey_to_str(&pubkey.into(), buf.as_mut_ptr(), buf.len(), &mut len),
+                SolanaStatus::Ok
+            );
+            let mut out = SolanaPubkey { bytes: [0; 32] };
+            assert_eq!(
+                solana_pubkey_from_str(buf.as_ptr(), &mut out),
+                SolanaStatus::Ok
+            );
+            assert_eq!(Pubkey::from(out), pubkey);
+
+            let invalid = CString::new("not a pubkey").unwrap();
+            assert_eq!(
+                solana_pubkey_from_str(invalid.as_ptr(), &mut out),
+                SolanaStatus::InvalidPubkey
+            );
+            assert_eq!(
+                solana_pubkey_from_str(ptr::null(), &mut out),
+                SolanaStatus::InvalidArgument
+            );
+        }
+    }
+
+    #[test]
+    fn test_derivation() {
+        let program_id = Pubkey::new_unique();
+        let seeds = [bytes(b"vault"), bytes(&[1, 2, 3])];
+        let (expected, expected_bump) =
+            Pubkey::find_program_address(&[b"vault", &[1, 2, 3]], &program_id);
+        let mut out = SolanaPubkey { bytes: [0; 32] };
+        let mut bump = 0;
+        unsafe {
+            assert_eq!(
+                solana_pubkey_find_program_address(
+                    seeds.as_ptr(),
+                    seeds.len(),
+                    &program_id.into(),
+                    &mut out,
+                    &mut bump,
+                ),
+                SolanaStatus::Ok
+            );
+            assert_eq!((Pubkey::from(out), bump), (expected, expected_bump));
+
+            let seeds = [bytes(b"vault"), bytes(&[1, 2, 3]), bytes(&[bump])];
+            out = SolanaPubkey { bytes: [0; 32] };
+            assert_eq!(
+                solana_pubkey_create_program_address(
+                    seeds.as_ptr(),
+                    seeds.len(),
+                    &program_id.into(),
+                    &mut out,
+                ),
+                SolanaStatus::Ok
+            );
+            assert_eq!(Pubkey::from(out), expected);
+
+            let base = Pubkey::new_unique();
+            assert_eq!(
+                solana_pubkey_create_with_seed(
+                    &base.into(),
+                    bytes(b"seed"),
+                    &program_id.into(),
+                    &mut out,
+                ),
+                SolanaStatus::Ok
+            );
+            assert_eq!(
+                Pubkey::from(out),
+                Pubkey::create_with_seed(&base, "seed", &program_id).unwrap()
+            );
+            assert_eq!(
+                solana_pubkey_create_with_seed(
+                    &base.into(),
+                    bytes(&[0xff]),
+                    &program_id.into(),
+                    &mut out,
+                ),
+                SolanaStatus::InvalidArgument
+            );
+        }
+    }
+
+    #[test]
+    fn test_hashv() {
+        let vals = [bytes(b"hello"), bytes(&[]), bytes(b" world")];
+        let mut out = SolanaHash { bytes: [0; 32] };
+        unsafe {
+            assert_eq!(
+                solana_hashv(vals.as_ptr(), vals.len(), &mut out),
+                SolanaStatus::Ok
+            );
+        }
+        assert_eq!(Hash::from(out), hash(b"hello world"));
+    }
+
+    #[test]
+    fn test_message_serialize() {
+        let payer = Pubkey::new_unique();
+        let stake_pubkey = Pubkey::new_unique();
+        let vote = Pubkey::new_unique();
+        let blockhash = hash(b"blockhash");
+        let expected = {
+            let mut message = Message::new(
+                &[
+                    stake::instruction::delegate_stake(&stake_pubkey, &payer, &vote),
+                    Instruction::new_with_bytes(vote, &[1, 2], vec![]),
+                ],
+                Some(&payer),
+            );
+            message.recent_blockhash = blockhash;
+            message.serialize()
+        };
+
+        unsafe {
+            let delegate = solana_stake_delegate(&stake_pubkey.into(), &payer.into(), &vote.into());
+            let other = solana_instruction_new(&vote.into(), ptr::null(), 0, bytes(&[1, 2]));
+            assert!(!delegate.is_null() && !other.is_null());
+            let instructions = [delegate.cast_const(), other.cast_const()];
+
+            let mut len = 0;
+            assert_eq!(
+                solana_message_serialize(
+                    instructions.as_ptr(),
+                    instructions.len(),
+                    &payer.into(),
+                    &blockhash.into(),
+                    ptr::null_mut(),
+                    0,
+                    &mut len,
+                ),
+                SolanaStatus::BufferTooSmall
+            );
+            let mut out = vec![0; len];
+            assert_eq!(
+                solana_message_serialize(
+                    instructions.as_ptr(),
+                    instructions.len(),
+                    &payer.into(),
+                    &blockhash.into(),
+                    out.as_mut_ptr(),
+                    out.len(),
+                    &mut len,
+                ),
+                SolanaStatus::Ok
+            );
+            assert_eq!(out, expected);
+
+            solana_instruction_free(delegate);
+            solana_instruction_free(other);
+            solana_instruction_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_message_serialize_too_many_keys() {
+        let program_id = Pubkey::new_unique();
+        let blockhash = hash(b"blockhash");
+        let accounts: Vec<SolanaAccountMeta> = (0..u8::MAX)
+            .map(|_| AccountMeta::new_readonly(Pubkey::new_unique(), false).into())
+            .collect();
+        unsafe {
+            let instruction = solana_instruction_new(
+                &program_id.into(),
+                accounts.as_ptr(),
+                accounts.len(),
+                bytes(&[]),
+            );
+            assert!(!instruction.is_null());
+            let instructions = [instruction.cast_const()];
+            let mut len = 0;
+            assert_eq!(
+                solana_message_serialize(
+                    instructions.as_ptr(),
+                    instructions.len(),
+                    ptr::null(),
+                    &blockhash.into(),
+                    ptr::null_mut(),
+                    0,
+                    &mut len,
+                ),
+                SolanaStatus::InvalidArgument
+            );
+
+            (*instruction).0.accounts.pop();
+            assert_eq!(
+                solana_message_serialize(
+                    instructions.as_ptr(),
+                    instructions.len(),
+                    ptr::null(),
+                    &blockhash.into(),
+                    ptr::null_mut(),
+                    0,
+                    &mut len,
+                ),
+                SolanaStatus::BufferTooSmall
+            );
+            solana_instruction_free(instruction);
+        }
+    }
+
+    #[test]
+    fn test_instruction_new_invalid_flags() {
+        let program_id = Pubkey::new_unique();
+        let meta = AccountMeta::new(Pubkey::new_unique(), true);
+        let mut accounts = [SolanaAccountMeta::from(meta.clone())];
+        unsafe {
+            let instruction =
+                solana_instruction_new(&program_id.into(), accounts.as_ptr(), 1, bytes(&[]));
+            assert!(!instruction.is_null());
+            assert_eq!((*instruction).0.accounts, vec![meta]);
+            solana_instruction_free(instruction);
+
+            accounts[0].is_writable = 2;
+            assert!(
+                solana_instruction_new(&program_id.into(), accounts.as_ptr(), 1, bytes(&[]))
+                    .is_null()
+            );
+        }
+    }
+
+    #[test]
+    fn test_stake_instructions() {
+        let [stake_pubkey, authority, other] = [(); 3].map(|_| Pubkey::new_unique());
+        unsafe {
+            let check = |instruction: *mut SolanaInstruction, expected: Instruction| {
+                assert!(!instruction.is_null());
+                assert_eq!((*instruction).0, expected);
+                solana_instruction_free(instruction);
+            };
+            check(
+                solana_stake_initialize(&stake_pubkey.into(), &authority.into(), &other.into()),
+                stake::instruction::initialize(
+                    &stake_pubkey,
+                    &Authorized {
+                        staker: authority,
+                        withdrawer: other,
+                    },
+                    &Lockup::default(),
+                ),
+            );
+            check(
+                solana_stake_deactivate(&stake_pubkey.into(), &authority.into()),
+                stake::instruction::deactivate_stake(&stake_pubkey, &authority),
+            );
+            check(
+                solana_stake_withdraw(
+                    &stake_pubkey.into(),
+                    &authority.into(),
+                    &other.into(),
+                    42,
+                    ptr::null(),
+                ),
+                stake::instruction::withdraw(&stake_pubkey, &authority, &other, 42, None),
+            );
+            check(
+                solana_stake_authorize(
+                    &stake_pubkey.into(),
+                    &authority.into(),
+                    &other.into(),
+                    SolanaStakeAuthorize::Withdrawer as u32,
+                    &authority.into(),
+                ),
+                stake::instruction::authorize(
+                    &stake_pubkey,
+                    &authority,
+                    &other,
+                    StakeAuthorize::Withdrawer,
+                    Some(&authority),
+                ),
+            );
+            assert!(solana_stake_deactivate(&stake_pubkey.into(), ptr::null()).is_null());
+            assert!(solana_stake_authorize(
+                &stake_pubkey.into(),
+                &authority.into(),
+                &other.into(),
+                2,
+                ptr::null(),
+            )
+            .is_null());
+        }
+    }
+}
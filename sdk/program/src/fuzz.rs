@@ -0,0 +1,152 @@
+//! Structured input generation for fuzzing and property tests.
+//!
+//! With the `fuzz` feature enabled, [`Pubkey`], [`Hash`], [`AccountMeta`],
+//! [`Instruction`] and the stake state types implement
+//! [`arbitrary::Arbitrary`], for use with cargo-fuzz and similar fuzzers. This
+//! module provides the equivalent [`proptest`] strategies.
+
+#![cfg(all(feature = "fuzz", not(target_os = "solana")))]
+
+use {
+    crate::{
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        stake::{
+            stake_flags::StakeFlags,
+            state::{Authorized, Delegation, Lockup, Meta, Stake, StakeStateV2},
+        },
+    },
+    proptest::{collection::vec, prelude::*},
+};
+
+/// Most accounts generated for a single [`instruction`]
+pub const MAX_INSTRUCTION_ACCOUNTS: usize = 16;
+/// Most bytes of instruction data generated for a single [`instruction`]
+pub const MAX_INSTRUCTION_DATA_LEN: usize = 1024;
+
+pub fn pubkey() -> impl Strategy<Value = Pubkey> {
+    any::<[u8; 32]>().prop_map(Pubkey::new_from_array)
+}
+
+pub fn hash() -> impl Strategy<Value = Hash> {
+    any::<[u8; 32]>().prop_map(Hash::new_from_array)
+}
+
+pub fn account_meta() -> impl Strategy<Value = AccountMeta> {
+    (pubkey(), any::<bool>(), any::<bool>()).prop_map(|(pubkey, is_signer, is_writable)| {
+        AccountMeta {
+            pubkey,
+            is_signer,
+            is_writable,
+        }
+    })
+}
+
+pub fn instruction() -> impl Strategy<Value = Instruction> {
+    (
+        pubkey(),
+        vec(account_meta(), 0..=MAX_INSTRUCTION_ACCOUNTS),
+        vec(any::<u8>(), 0..=MAX_INSTRUCTION_DATA_LEN),
+    )
+        .prop_map(|(program_id, accounts, data)| Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+}
+
+pub fn lockup() -> impl Strategy<Value = Lockup> {
+    (any::<i64>(), any::<u64>(), pubkey()).prop_map(|(unix_timestamp, epoch, custodian)| Lockup {
+        unix_timestamp,
+        epoch,
+        custodian,
+    })
+}
+
+pub fn authorized() -> impl Strategy<Value = Authorized> {
+    (pubkey(), pubkey()).prop_map(|(staker, withdrawer)| Authorized { staker, withdrawer })
+}
+
+pub fn meta() -> impl Strategy<Value = Meta> {
+    (any::<u64>(), authorized(), lockup()).prop_map(|(rent_exempt_reserve, authorized, lockup)| {
+        Meta {
+            rent_exempt_reserve,
+            authorized,
+            lockup,
+        }
+    })
+}
+
+pub fn delegation() -> impl Strategy<Value = Delegation> {
+    (pubkey(), any::<u64>(), any::<u64>(), any::<u64>()).prop_map(
+        |(voter_pubkey, stake, activation_epoch, deactivation_epoch)| Delegation {
+            voter_pubkey,
+            stake,
+            activation_epoch,
+            deactivation_epoch,
+            ..Delegation::default()
+        },
+    )
+}
+
+pub fn stake() -> impl Strategy<Value = Stake> {
+    (delegation(), any::<u64>()).prop_map(|(delegation, credits_observed)| Stake {
+        delegation,
+        credits_observed,
+    })
+}
+
+pub fn stake_flags() -> impl Strategy<Value = StakeFlags> {
+    prop_oneof![
+        Just(StakeFlags::empty()),
+        Just(StakeFlags::MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED),
+    ]
+}
+
+pub fn stake_state() -> impl Strategy<Value = StakeStateV2> {
+    prop_oneof![
+        Just(StakeStateV2::Uninitialized),
+        meta().prop_map(StakeStateV2::Initialized),
+        (meta(), stake(), stake_flags())
+            .prop_map(|(meta, stake, stake_flags)| StakeStateV2::Stake(meta, stake, stake_flags)),
+        Just(StakeStateV2::RewardsPool),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, arbitrary::Unstructured};
+
+    proptest! {
+        #[test]
+        fn test_stake_state_round_trip(stake_state in stake_state()) {
+            let serialized = bincode::serialize(&stake_state).unwrap();
+            prop_assert!(serialized.len() <= StakeStateV2::size_of());
+            prop_assert_eq!(
+                bincode::deserialize::<StakeStateV2>(&serialized).unwrap(),
+                stake_state
+            );
+        }
+
+        #[test]
+        fn test_instruction_round_trip(instruction in instruction()) {
+            let serialized = bincode::serialize(&instruction).unwrap();
+            prop_assert_eq!(
+                bincode::deserialize::<Instruction>(&serialized).unwrap(),
+                instruction
+            );
+        }
+
+        #[test]
+        fn test_arbitrary_instruction(bytes in vec(any::<u8>(), 0..256)) {
+            let mut unstructured = Unstructured::new(&bytes);
+            let instruction: Instruction = unstructured.arbitrary().unwrap();
+            let serialized = bincode::serialize(&instruction).unwrap();
+            prop_assert_eq!(
+                bincode::deserialize::<Instruction>(&serialized).unwrap(),
+                instruction
+            );
+        }
+    }
+}
@@ -4,11 +4,11 @@
 //! [`Hash`]: struct@Hash
 
 use {
-    crate::{sanitize::Sanitize, wasm_bindgen},
+    crate::{base58, sanitize::Sanitize, wasm_bindgen},
     borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
     bytemuck::{Pod, Zeroable},
     sha2::{Digest, Sha256},
-    std::{convert::TryFrom, fmt, mem, str::FromStr},
+    std::{convert::TryFrom, fmt, str::FromStr},
     thiserror::Error,
 };
 
@@ -47,6 +47,7 @@ const MAX_BASE58_LEN: usize = 44;
     Zeroable,
 )]
 #[borsh(crate = "borsh")]
+#[cfg_attr(any(test, feature = "fuzz"), derive(arbitrary::Arbitrary))]
 #[repr(transparent)]
 pub struct Hash(pub(crate) [u8; HASH_BYTES]);
 
@@ -85,13 +86,13 @@ impl AsRef<[u8]> for Hash {
 
 impl fmt::Debug for Hash {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", bs58::encode(self.0).into_string())
+        self.write_as_base58(f)
     }
 }
 
 impl fmt::Display for Hash {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", bs58::encode(self.0).into_string())
+        self.write_as_base58(f)
     }
 }
 
@@ -110,14 +111,12 @@ impl FromStr for Hash {
         if s.len() > MAX_BASE58_LEN {
             return Err(ParseHashError::WrongSize);
         }
-        let bytes = bs58::decode(s)
-            .into_vec()
-            .map_err(|_| ParseHashError::Invalid)?;
-        if bytes.len() != mem::size_of::<Hash>() {
-            Err(ParseHashError::WrongSize)
-        } else {
-            Ok(Hash::new(&bytes))
-        }
+        base58::decode_32(s)
+            .map(Hash::new_from_array)
+            .map_err(|err| match err {
+                base58::DecodeError::InvalidCharacter => ParseHashError::Invalid,
+                base58::DecodeError::WrongSize => ParseHashError::WrongSize,
+            })
     }
 }
 
@@ -144,9 +143,27 @@ impl Hash {
     pub fn to_bytes(self) -> [u8; HASH_BYTES] {
         self.0
     }
+
+    /// Compare with `other` in constant time.
+    ///
+    /// `==` returns at the first differing byte, so its timing can leak how
+    /// much of a secret-derived hash an attacker has guessed.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        subtle::ConstantTimeEq::ct_eq(&self.0[..], &other.0[..]).into()
+    }
+
+    /// Write the base58 encoding of this hash to `f`, without allocating.
+    pub fn write_as_base58(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let mut buf = [0; base58::MAX_ENCODED_LEN];
+        f.write_str(base58::encode_32(&self.0, &mut buf))
+    }
 }
 
 /// Return a Sha256 hash for the given data.
+///
+/// On native targets this uses the CPU's SHA extensions when they are
+/// available at runtime (SHA-NI on x86, the ARMv8 cryptography extensions on
+/// aarch64), and a portable implementation otherwise, including on wasm32.
 pub fn hashv(vals: &[&[u8]]) -> Hash {
     // Perform the calculation inline, calling this from within a program is
     // not supported
@@ -192,6 +209,13 @@ mod tests {
         assert!(Hash::new_unique() != Hash::new_unique());
     }
 
+    #[test]
+    fn test_ct_eq() {
+        let hash = Hash::new_unique();
+        assert!(hash.ct_eq(&Hash::new_from_array(hash.to_bytes())));
+        assert!(!hash.ct_eq(&Hash::new_unique()));
+    }
+
     #[test]
     fn test_hash_fromstr() {
         let hash = hash(&[1u8]);
@@ -18,6 +18,8 @@ use {
     bincode::serialize,
     borsh::BorshSerialize,
     serde::Serialize,
+    smallvec::SmallVec,
+    std::borrow::Cow,
     thiserror::Error,
 };
 
@@ -326,6 +328,7 @@ pub enum InstructionError {
 /// program must still validate during execution that the account is a signer.
 #[wasm_bindgen]
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "fuzz"), derive(arbitrary::Arbitrary))]
 pub struct Instruction {
     /// Pubkey of the program that executes this instruction.
     #[wasm_bindgen(skip)]
@@ -512,6 +515,120 @@ impl Instruction {
     }
 }
 
+/// An [`Instruction`] under construction that avoids heap allocation.
+///
+/// Up to `N` [`AccountMeta`]s are stored inline, spilling to the heap only if
+/// more are added, and the instruction data may be borrowed rather than
+/// copied. This makes it cheap to build large numbers of instructions that are
+/// only inspected, or serialized, before being discarded.
+///
+/// Convert to an [`Instruction`] with [`InstructionBuilder::build`] or
+/// [`From`], which allocates the account list and, if borrowed, the data.
+///
+/// # Examples
+///
+/// ```
+/// # use solana_program::{
+/// #     pubkey::Pubkey,
+/// #     instruction::{AccountMeta, Instruction, InstructionBuilder},
+/// # };
+/// # let program_id = Pubkey::new_unique();
+/// # let from = Pubkey::new_unique();
+/// # let to = Pubkey::new_unique();
+/// let data = [1, 2, 3];
+/// let builder = InstructionBuilder::<2>::new(program_id)
+///     .account(AccountMeta::new(from, true))
+///     .account(AccountMeta::new(to, false))
+///     .data(&data[..]);
+/// assert!(!builder.spilled());
+///
+/// assert_eq!(
+///     builder.build(),
+///     Instruction::new_with_bytes(
+///         program_id,
+///         &data,
+///         vec![AccountMeta::new(from, true), AccountMeta::new(to, false)],
+///     ),
+/// );
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InstructionBuilder<'a, const N: usize = 8> {
+    program_id: Pubkey,
+    accounts: SmallVec<[AccountMeta; N]>,
+    data: Cow<'a, [u8]>,
+}
+
+impl<'a, const N: usize> InstructionBuilder<'a, N> {
+    /// Start an instruction for `program_id`, with no accounts and no data.
+    pub fn new(program_id: Pubkey) -> Self {
+        Self {
+            program_id,
+            accounts: SmallVec::new(),
+            data: Cow::Borrowed(&[]),
+        }
+    }
+
+    /// Append an account.
+    pub fn account(mut self, account_meta: AccountMeta) -> Self {
+        self.accounts.push(account_meta);
+        self
+    }
+
+    /// Append several accounts.
+    pub fn accounts(mut self, account_metas: &[AccountMeta]) -> Self {
+        self.accounts.extend(account_metas.iter().cloned());
+        self
+    }
+
+    /// Set the instruction data, either borrowed or owned.
+    pub fn data(mut self, data: impl Into<Cow<'a, [u8]>>) -> Self {
+        self.data = data.into();
+        self
+    }
+
+    pub fn get_program_id(&self) -> &Pubkey {
+        &self.program_id
+    }
+
+    pub fn get_accounts(&self) -> &[AccountMeta] {
+        &self.accounts
+    }
+
+    pub fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Whether the account list outgrew its inline storage and was moved to
+    /// the heap.
+    pub fn spilled(&self) -> bool {
+        self.accounts.spilled()
+    }
+
+    /// Convert into an [`Instruction`].
+    pub fn build(self) -> Instruction {
+        Instruction {
+            program_id: self.program_id,
+            accounts: self.accounts.into_vec(),
+            data: self.data.into_owned(),
+        }
+    }
+}
+
+impl<'a, const N: usize> From<InstructionBuilder<'a, N>> for Instruction {
+    fn from(builder: InstructionBuilder<'a, N>) -> Self {
+        builder.build()
+    }
+}
+
+impl<'a, const N: usize> From<&'a Instruction> for InstructionBuilder<'a, N> {
+    /// Borrows the instruction's data; the accounts are copied.
+    fn from(instruction: &'a Instruction) -> Self {
+        Self::new(instruction.program_id)
+            .accounts(&instruction.accounts)
+            .data(instruction.data.as_slice())
+    }
+}
+
 /// Addition that returns [`InstructionError::InsufficientFunds`] on overflow.
 ///
 /// This is an internal utility function.
@@ -536,6 +653,7 @@ pub fn checked_add(a: u64, b: u64) -> Result<u64, InstructionError> {
 /// is not writable.
 #[repr(C)]
 #[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "fuzz"), derive(arbitrary::Arbitrary))]
 pub struct AccountMeta {
     /// An account's public key.
     pub pubkey: Pubkey,
@@ -470,15 +470,21 @@
 extern crate self as solana_program;
 
 pub mod account_info;
+pub mod account_spec;
 pub mod address_lookup_table;
 pub mod alt_bn128;
+pub mod arena;
 pub(crate) mod atomic_u64;
+pub(crate) mod base58;
 pub mod big_mod_exp;
 pub mod blake3;
 pub mod borsh;
+#[cfg(feature = "borsh010")]
 pub mod borsh0_10;
+#[cfg(feature = "borsh09")]
 pub mod borsh0_9;
 pub mod borsh1;
+pub mod borsh_compat;
 pub mod bpf_loader;
 pub mod bpf_loader_deprecated;
 pub mod bpf_loader_upgradeable;
@@ -486,13 +492,17 @@ pub mod clock;
 pub mod compute_units;
 pub mod debug_account_data;
 pub mod decode_error;
+pub mod discriminator;
 pub mod ed25519_program;
+pub mod entropy;
 pub mod entrypoint;
 pub mod entrypoint_deprecated;
 pub mod epoch_rewards;
 pub mod epoch_schedule;
 pub mod feature;
 pub mod fee_calculator;
+pub mod ffi;
+pub mod fuzz;
 pub mod hash;
 pub mod incinerator;
 pub mod instruction;
@@ -504,9 +514,12 @@ pub mod loader_upgradeable_instruction;
 pub mod loader_v4;
 pub mod loader_v4_instruction;
 pub mod log;
+pub mod memo;
 pub mod message;
 pub mod native_token;
 pub mod nonce;
+pub mod pay;
+pub mod pda_cache;
 pub mod poseidon;
 pub mod program;
 pub mod program_error;
@@ -523,16 +536,21 @@ pub mod secp256k1_recover;
 pub mod serde_varint;
 pub mod serialize_utils;
 pub mod short_vec;
+pub mod signature;
 pub mod slot_hashes;
 pub mod slot_history;
 pub mod stable_layout;
 pub mod stake;
 pub mod stake_history;
+pub mod sync;
 pub mod syscalls;
 pub mod system_instruction;
 pub mod system_program;
 pub mod sysvar;
+pub mod test_vectors;
+pub mod time_source;
 pub mod vote;
+pub mod wasi;
 pub mod wasm;
 
 #[deprecated(
@@ -543,12 +561,13 @@ pub mod address_lookup_table_account {
     pub use crate::address_lookup_table::AddressLookupTableAccount;
 }
 
-#[cfg(target_os = "solana")]
+// Neither SBF nor WASI hosts provide the JavaScript glue the bindings import
+#[cfg(any(target_os = "solana", target_os = "wasi"))]
 pub use solana_sdk_macro::wasm_bindgen_stub as wasm_bindgen;
 /// Re-export of [wasm-bindgen].
 ///
 /// [wasm-bindgen]: https://rustwasm.github.io/docs/wasm-bindgen/
-#[cfg(not(target_os = "solana"))]
+#[cfg(not(any(target_os = "solana", target_os = "wasi")))]
 pub use wasm_bindgen::prelude::wasm_bindgen;
 
 /// The [config native program][np].
@@ -560,41 +579,49 @@ pub mod config {
     }
 }
 
-/// A vector of Solana SDK IDs.
+/// The Solana SDK IDs.
 pub mod sdk_ids {
-    use {
-        crate::{
-            address_lookup_table, bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable,
-            config, ed25519_program, feature, incinerator, loader_v4, secp256k1_program,
-            solana_program::pubkey::Pubkey, stake, system_program, sysvar, vote,
-        },
-        lazy_static::lazy_static,
+    #![allow(clippy::arithmetic_side_effects)]
+    use crate::{
+        address_lookup_table, bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable, config,
+        ed25519_program, feature, incinerator, loader_v4, secp256k1_program,
+        solana_program::pubkey::Pubkey, stake, system_program, sysvar, vote,
     };
 
-    lazy_static! {
-        pub static ref SDK_IDS: Vec<Pubkey> = {
-            let mut sdk_ids = vec![
-                ed25519_program::id(),
-                secp256k1_program::id(),
-                system_program::id(),
-                sysvar::id(),
-                bpf_loader::id(),
-                bpf_loader_upgradeable::id(),
-                incinerator::id(),
-                config::program::id(),
-                vote::program::id(),
-                feature::id(),
-                bpf_loader_deprecated::id(),
-                address_lookup_table::program::id(),
-                loader_v4::id(),
-                stake::program::id(),
-                #[allow(deprecated)]
-                stake::config::id(),
-            ];
-            sdk_ids.extend(sysvar::ALL_IDS.iter());
-            sdk_ids
-        };
-    }
+    const PROGRAM_IDS: [Pubkey; 15] = [
+        ed25519_program::ID,
+        secp256k1_program::ID,
+        system_program::ID,
+        sysvar::ID,
+        bpf_loader::ID,
+        bpf_loader_upgradeable::ID,
+        incinerator::ID,
+        config::program::ID,
+        vote::program::ID,
+        feature::ID,
+        bpf_loader_deprecated::ID,
+        address_lookup_table::program::ID,
+        loader_v4::ID,
+        stake::program::ID,
+        #[allow(deprecated)]
+        stake::config::ID,
+    ];
+
+    pub const SDK_IDS: [Pubkey; PROGRAM_IDS.len() + sysvar::ALL_IDS.len()] = {
+        let mut sdk_ids =
+            [Pubkey::new_from_array([0; 32]); PROGRAM_IDS.len() + sysvar::ALL_IDS.len()];
+        let mut i = 0;
+        while i < PROGRAM_IDS.len() {
+            sdk_ids[i] = PROGRAM_IDS[i];
+            i += 1;
+        }
+        let mut j = 0;
+        while j < sysvar::ALL_IDS.len() {
+            sdk_ids[i + j] = sysvar::ALL_IDS[j];
+            j += 1;
+        }
+        sdk_ids
+    };
 }
 
 /// Same as [`declare_id`] except that it reports that this ID has been deprecated.
@@ -33,7 +33,13 @@
 //! [`Pubkey`]: crate::pubkey::Pubkey
 //! [`Pubkey::log`]: crate::pubkey::Pubkey::log
 
-use crate::account_info::AccountInfo;
+use {
+    crate::account_info::AccountInfo,
+    std::fmt::{self, Write},
+};
+
+/// Size of the stack buffer [`sol_log_fmt`] formats into
+pub const LOG_FMT_BUFFER_LEN: usize = 256;
 
 /// Print a message to the log.
 #[macro_export]
@@ -60,10 +66,11 @@ macro_rules! info {
 /// must have type `&str`, and is typically used for logging static strings.
 /// When passed something other than an expression, particularly
 /// a sequence of expressions, the tokens will be passed through the
-/// [`format!`] macro before being logged with `sol_log`.
+/// [`format_args!`] macro and logged with [`sol_log_fmt`], which formats into
+/// a stack buffer instead of allocating a `String`.
 ///
 /// [fs]: https://doc.rust-lang.org/std/fmt/
-/// [`format!`]: https://doc.rust-lang.org/std/fmt/fn.format.html
+/// [`format_args!`]: https://doc.rust-lang.org/std/macro.format_args.html
 ///
 /// Note that Rust's formatting machinery is relatively CPU-intensive
 /// for constrained environments like the Solana VM.
@@ -85,7 +92,7 @@ macro_rules! msg {
     ($msg:expr) => {
         $crate::log::sol_log($msg)
     };
-    ($($arg:tt)*) => ($crate::log::sol_log(&format!($($arg)*)));
+    ($($arg:tt)*) => ($crate::log::sol_log_fmt(format_args!($($arg)*)));
 }
 
 /// Print a string to the log.
@@ -100,6 +107,55 @@ pub fn sol_log(message: &str) {
     crate::program_stubs::sol_log(message);
 }
 
+/// Print formatted arguments to the log.
+///
+/// Arguments without any formatting to do are logged directly. Otherwise the
+/// message is formatted into a [`LOG_FMT_BUFFER_LEN`] byte buffer on the
+/// stack, and only messages too long to fit it are formatted into a `String`.
+pub fn sol_log_fmt(args: fmt::Arguments) {
+    if let Some(message) = args.as_str() {
+        return sol_log(message);
+    }
+    let mut buffer = StackBuffer::new();
+    if buffer.write_fmt(args).is_ok() {
+        sol_log(buffer.as_str());
+    } else {
+        sol_log(&fmt::format(args));
+    }
+}
+
+/// A fixed-size buffer that fails writes which don't fit
+struct StackBuffer {
+    buf: [u8; LOG_FMT_BUFFER_LEN],
+    len: usize,
+}
+
+impl StackBuffer {
+    fn new() -> Self {
+        Self {
+            buf: [0; LOG_FMT_BUFFER_LEN],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // Only whole `&str`s are ever copied in
+        std::str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+}
+
+impl Write for StackBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len.checked_add(s.len()).ok_or(fmt::Error)?;
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
 /// Print 64-bit values represented as hexadecimal to the log.
 #[inline]
 pub fn sol_log_64(arg1: u64, arg2: u64, arg3: u64, arg4: u64, arg5: u64) {
@@ -165,3 +221,23 @@ pub fn sol_log_compute_units() {
     #[cfg(not(target_os = "solana"))]
     crate::program_stubs::sol_log_compute_units();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stack_buffer() {
+        let mut buffer = StackBuffer::new();
+        write!(buffer, "compute units: {}", 42).unwrap();
+        assert_eq!(buffer.as_str(), "compute units: 42");
+
+        let long = "x".repeat(LOG_FMT_BUFFER_LEN);
+        assert!(write!(buffer, "{long}").is_err());
+        assert_eq!(buffer.as_str(), "compute units: 42");
+
+        let mut buffer = StackBuffer::new();
+        write!(buffer, "{long}").unwrap();
+        assert_eq!(buffer.as_str(), long);
+    }
+}
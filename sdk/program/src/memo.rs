@@ -0,0 +1,56 @@
+//! The [SPL Memo program][mp].
+//!
+//! [mp]: https://spl.solana.com/memo
+//!
+//! The memo program validates that the instruction data is UTF-8 and records
+//! it in the transaction log. Any accounts passed to it must be signers of the
+//! transaction, so a memo can also be used to prove that a set of keys
+//! approved the transaction.
+
+use crate::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+crate::declare_id!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// The original version of the memo program, which takes no signers.
+pub mod v1 {
+    crate::declare_id!("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+}
+
+/// Build a memo instruction, possibly signed
+///
+/// Each of `signer_pubkeys` must sign the transaction for the memo instruction
+/// to succeed.
+pub fn build_memo(memo: &[u8], signer_pubkeys: &[&Pubkey]) -> Instruction {
+    Instruction {
+        program_id: id(),
+        accounts: signer_pubkeys
+            .iter()
+            .map(|&pubkey| AccountMeta::new_readonly(*pubkey, true))
+            .collect(),
+        data: memo.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_memo() {
+        let signer_pubkey = Pubkey::new_unique();
+        let instruction = build_memo(b"hello", &[&signer_pubkey]);
+        assert_eq!(instruction.program_id, id());
+        assert_eq!(instruction.data, b"hello");
+        assert_eq!(
+            instruction.accounts,
+            vec![AccountMeta::new_readonly(signer_pubkey, true)]
+        );
+
+        let instruction = build_memo(b"", &[]);
+        assert!(instruction.accounts.is_empty());
+        assert!(instruction.data.is_empty());
+    }
+}
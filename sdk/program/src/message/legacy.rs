@@ -19,44 +19,46 @@ use {
         message::{compiled_keys::CompiledKeys, MessageHeader},
         pubkey::Pubkey,
         sanitize::{Sanitize, SanitizeError},
-        short_vec, system_instruction, system_program, sysvar, wasm_bindgen,
+        short_vec,
+        signature::SIGNATURE_BYTES,
+        system_instruction, system_program, sysvar, wasm_bindgen,
     },
-    lazy_static::lazy_static,
-    std::{convert::TryFrom, str::FromStr},
+    std::convert::TryFrom,
 };
 
-lazy_static! {
-    // This will be deprecated and so this list shouldn't be modified
-    pub static ref BUILTIN_PROGRAMS_KEYS: [Pubkey; 10] = {
-        let parse = |s| Pubkey::from_str(s).unwrap();
-        [
-            parse("Config1111111111111111111111111111111111111"),
-            parse("Feature111111111111111111111111111111111111"),
-            parse("NativeLoader1111111111111111111111111111111"),
-            parse("Stake11111111111111111111111111111111111111"),
-            parse("StakeConfig11111111111111111111111111111111"),
-            parse("Vote111111111111111111111111111111111111111"),
-            system_program::id(),
-            bpf_loader::id(),
-            bpf_loader_deprecated::id(),
-            bpf_loader_upgradeable::id(),
-        ]
-    };
-}
-
-lazy_static! {
-    // Each element of a key is a u8. We use key[0] as an index into this table of 256 boolean
-    // elements, to store whether or not the first element of any key is present in the static
-    // lists of built-in-program keys or system ids. By using this lookup table, we can very
-    // quickly determine that a key under consideration cannot be in either of these lists (if
-    // the value is "false"), or might be in one of these lists (if the value is "true")
-    pub static ref MAYBE_BUILTIN_KEY_OR_SYSVAR: [bool; 256] = {
-        let mut temp_table: [bool; 256] = [false; 256];
-        BUILTIN_PROGRAMS_KEYS.iter().for_each(|key| temp_table[key.0[0] as usize] = true);
-        sysvar::ALL_IDS.iter().for_each(|key| temp_table[key.0[0] as usize] = true);
-        temp_table
-    };
-}
+// This will be deprecated and so this list shouldn't be modified
+pub const BUILTIN_PROGRAMS_KEYS: [Pubkey; 10] = [
+    crate::pubkey!("Config1111111111111111111111111111111111111"),
+    crate::pubkey!("Feature111111111111111111111111111111111111"),
+    crate::pubkey!("NativeLoader1111111111111111111111111111111"),
+    crate::pubkey!("Stake11111111111111111111111111111111111111"),
+    crate::pubkey!("StakeConfig11111111111111111111111111111111"),
+    crate::pubkey!("Vote111111111111111111111111111111111111111"),
+    system_program::ID,
+    bpf_loader::ID,
+    bpf_loader_deprecated::ID,
+    bpf_loader_upgradeable::ID,
+];
+
+// Each element of a key is a u8. We use key[0] as an index into this table of 256 boolean
+// elements, to store whether or not the first element of any key is present in the static
+// lists of built-in-program keys or system ids. By using this lookup table, we can very
+// quickly determine that a key under consideration cannot be in either of these lists (if
+// the value is "false"), or might be in one of these lists (if the value is "true")
+pub const MAYBE_BUILTIN_KEY_OR_SYSVAR: [bool; 256] = {
+    let mut temp_table: [bool; 256] = [false; 256];
+    let mut i = 0;
+    while i < BUILTIN_PROGRAMS_KEYS.len() {
+        temp_table[BUILTIN_PROGRAMS_KEYS[i].0[0] as usize] = true;
+        i += 1;
+    }
+    let mut i = 0;
+    while i < sysvar::ALL_IDS.len() {
+        temp_table[sysvar::ALL_IDS[i].0[0] as usize] = true;
+        i += 1;
+    }
+    temp_table
+};
 
 pub fn is_builtin_key_or_sysvar(key: &Pubkey) -> bool {
     if MAYBE_BUILTIN_KEY_OR_SYSVAR[key.0[0] as usize] {
@@ -492,6 +494,17 @@ impl Message {
         bincode::serialize(self).unwrap()
     }
 
+    /// The serialized size, in bytes, of a transaction carrying this message
+    /// and `num_signatures` signatures.
+    pub fn serialized_size_with_signatures(&self, num_signatures: usize) -> usize {
+        let signatures_len = short_vec::ShortU16(u16::try_from(num_signatures).unwrap_or(u16::MAX));
+        let signatures_len_size = bincode::serialized_size(&signatures_len).unwrap() as usize;
+        let message_size = bincode::serialized_size(self).unwrap() as usize;
+        signatures_len_size
+            .saturating_add(num_signatures.saturating_mul(SIGNATURE_BYTES))
+            .saturating_add(message_size)
+    }
+
     pub fn program_id(&self, instruction_index: usize) -> Option<&Pubkey> {
         Some(
             &self.account_keys[self.instructions.get(instruction_index)?.program_id_index as usize],
@@ -641,8 +654,10 @@ mod tests {
     #![allow(deprecated)]
     use {
         super::*,
-        crate::{hash, instruction::AccountMeta, message::MESSAGE_HEADER_LENGTH},
-        std::collections::HashSet,
+        crate::{
+            hash, instruction::AccountMeta, message::MESSAGE_HEADER_LENGTH, signature::Signature,
+        },
+        std::{collections::HashSet, str::FromStr},
     };
 
     #[test]
@@ -659,7 +674,7 @@ mod tests {
     fn test_builtin_program_keys_abi_freeze() {
         // Once the feature is flipped on, we can't further modify
         // BUILTIN_PROGRAMS_KEYS without the risk of breaking consensus.
-        let builtins = format!("{:?}", *BUILTIN_PROGRAMS_KEYS);
+        let builtins = format!("{:?}", BUILTIN_PROGRAMS_KEYS);
         assert_eq!(
             format!("{}", hash::hash(builtins.as_bytes())),
             "ACqmMkYbo9eqK6QrRSrB3HLyR6uHhLf31SCfGUAJjiWj"
@@ -869,6 +884,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_serialized_size_with_signatures() {
+        let payer = Pubkey::new_unique();
+        let message = Message::new(
+            &[system_instruction::transfer(
+                &payer,
+                &Pubkey::new_unique(),
+                1,
+            )],
+            Some(&payer),
+        );
+        for num_signatures in [0, 1, 127, 128] {
+            let transaction_bytes = bincode::serialize(&(
+                short_vec::ShortVec(vec![Signature::default(); num_signatures]),
+                &message,
+            ))
+            .unwrap();
+            assert_eq!(
+                message.serialized_size_with_signatures(num_signatures),
+                transaction_bytes.len()
+            );
+        }
+    }
+
     #[test]
     fn test_message_hash() {
         // when this test fails, it's most likely due to a new serialized format of a message.
@@ -12,7 +12,7 @@ use {
         ser::{SerializeTuple, Serializer},
         Deserialize, Serialize,
     },
-    std::fmt,
+    std::{collections::HashSet, fmt},
 };
 
 mod sanitized;
@@ -23,6 +23,12 @@ pub use sanitized::*;
 /// Bit mask that indicates whether a serialized message is versioned.
 pub const MESSAGE_VERSION_PREFIX: u8 = 0x80;
 
+/// Maximum length of the data of an instruction in a [`VersionedMessage`].
+///
+/// A transaction has to fit in a packet, so this is the packet data size,
+/// `solana_sdk::packet::PACKET_DATA_SIZE`.
+pub const MAX_COMPILED_INSTRUCTION_DATA_LEN: usize = 1280 - 40 - 8;
+
 /// Either a legacy message or a v0 message.
 ///
 /// # Serialization
@@ -46,6 +52,34 @@ impl VersionedMessage {
         }
     }
 
+    /// Sanitize a message decoded from untrusted wire bytes.
+    ///
+    /// Runs the checks in [`VersionedMessage::sanitize`], then rejects static
+    /// account keys, including signers, that appear more than once and
+    /// instruction data longer than [`MAX_COMPILED_INSTRUCTION_DATA_LEN`].
+    ///
+    /// This is an opt-in check for embedders. The runtime does not call it,
+    /// since the bank reports duplicate keys as `AccountLoadedTwice` when it
+    /// loads accounts.
+    pub fn sanitize_untrusted(&self) -> Result<(), SanitizeError> {
+        self.sanitize()?;
+
+        let account_keys = self.static_account_keys();
+        let unique_account_keys: HashSet<&Pubkey> = account_keys.iter().collect();
+        if unique_account_keys.len() != account_keys.len() {
+            return Err(SanitizeError::InvalidValue);
+        }
+
+        if self
+            .instructions()
+            .iter()
+            .any(|instruction| instruction.data.len() > MAX_COMPILED_INSTRUCTION_DATA_LEN)
+        {
+            return Err(SanitizeError::ValueOutOfBounds);
+        }
+        Ok(())
+    }
+
     pub fn header(&self) -> &MessageHeader {
         match self {
             Self::Legacy(message) => &message.header,
@@ -152,6 +186,12 @@ impl VersionedMessage {
     }
 }
 
+impl Sanitize for VersionedMessage {
+    fn sanitize(&self) -> Result<(), SanitizeError> {
+        VersionedMessage::sanitize(self)
+    }
+}
+
 impl Default for VersionedMessage {
     fn default() -> Self {
         Self::Legacy(LegacyMessage::default())
@@ -400,4 +440,108 @@ mod tests {
         let message_from_string: VersionedMessage = serde_json::from_str(&string).unwrap();
         assert_eq!(message, message_from_string);
     }
+
+    #[test]
+    fn test_sanitize_trait() {
+        let payer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let valid = VersionedMessage::V0(v0::Message {
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![payer, program_id],
+            instructions: vec![CompiledInstruction::new_from_raw_parts(1, vec![], vec![0])],
+            ..v0::Message::default()
+        });
+
+        let mut out_of_bounds = valid.clone();
+        if let VersionedMessage::V0(message) = &mut out_of_bounds {
+            message.instructions[0].accounts = vec![2];
+        }
+
+        // generic callers, such as `Vec<T: Sanitize>`, see the same checks
+        // as the inherent methods
+        fn sanitize_all<T: Sanitize>(messages: &Vec<T>) -> Result<(), SanitizeError> {
+            messages.sanitize()
+        }
+        assert_eq!(sanitize_all(&vec![valid.clone()]), Ok(()));
+        assert_eq!(
+            sanitize_all(&vec![valid.clone(), out_of_bounds.clone()]),
+            Err(SanitizeError::IndexOutOfBounds)
+        );
+        if let VersionedMessage::V0(message) = out_of_bounds {
+            assert_eq!(
+                Sanitize::sanitize(&message),
+                Err(SanitizeError::IndexOutOfBounds)
+            );
+        }
+    }
+
+    #[test]
+    fn test_sanitize_untrusted() {
+        let payer = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let legacy = LegacyMessage {
+            header: MessageHeader {
+                num_required_signatures: 2,
+                num_readonly_signed_accounts: 1,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![payer, signer, program_id],
+            instructions: vec![CompiledInstruction::new_from_raw_parts(
+                2,
+                vec![0; MAX_COMPILED_INSTRUCTION_DATA_LEN],
+                vec![0, 1],
+            )],
+            ..LegacyMessage::default()
+        };
+        let v0 = v0::Message {
+            header: legacy.header,
+            account_keys: legacy.account_keys.clone(),
+            instructions: legacy.instructions.clone(),
+            ..v0::Message::default()
+        };
+        for message in [VersionedMessage::Legacy(legacy), VersionedMessage::V0(v0)] {
+            assert_eq!(message.sanitize_untrusted(), Ok(()));
+
+            // duplicate signers
+            let mut duplicate_signer = message.clone();
+            match &mut duplicate_signer {
+                VersionedMessage::Legacy(message) => message.account_keys[1] = payer,
+                VersionedMessage::V0(message) => message.account_keys[1] = payer,
+            }
+            assert_eq!(
+                duplicate_signer.sanitize_untrusted(),
+                Err(SanitizeError::InvalidValue)
+            );
+            // the consensus checks leave duplicates to account loading
+            assert_eq!(duplicate_signer.sanitize(), Ok(()));
+
+            // a signer repeated as a non-signer
+            let mut duplicate_key = message.clone();
+            match &mut duplicate_key {
+                VersionedMessage::Legacy(message) => message.account_keys.push(signer),
+                VersionedMessage::V0(message) => message.account_keys.push(signer),
+            }
+            assert_eq!(
+                duplicate_key.sanitize_untrusted(),
+                Err(SanitizeError::InvalidValue)
+            );
+            assert_eq!(duplicate_key.sanitize(), Ok(()));
+
+            let mut oversized_data = message.clone();
+            match &mut oversized_data {
+                VersionedMessage::Legacy(message) => message.instructions[0].data.push(0),
+                VersionedMessage::V0(message) => message.instructions[0].data.push(0),
+            }
+            assert_eq!(
+                oversized_data.sanitize_untrusted(),
+                Err(SanitizeError::ValueOutOfBounds)
+            );
+            assert_eq!(oversized_data.sanitize(), Ok(()));
+        }
+    }
 }
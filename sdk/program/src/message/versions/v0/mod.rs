@@ -20,7 +20,7 @@ use crate::{
         AccountKeys, MessageHeader, MESSAGE_VERSION_PREFIX,
     },
     pubkey::Pubkey,
-    sanitize::SanitizeError,
+    sanitize::{Sanitize, SanitizeError},
     short_vec,
 };
 pub use loaded::*;
@@ -87,6 +87,12 @@ pub struct Message {
     pub address_table_lookups: Vec<MessageAddressTableLookup>,
 }
 
+impl Sanitize for Message {
+    fn sanitize(&self) -> Result<(), SanitizeError> {
+        Message::sanitize(self)
+    }
+}
+
 impl Message {
     /// Sanitize message fields and compiled instruction indexes
     pub fn sanitize(&self) -> Result<(), SanitizeError> {
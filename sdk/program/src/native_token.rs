@@ -15,6 +15,39 @@ pub fn sol_to_lamports(sol: f64) -> u64 {
     (sol * LAMPORTS_PER_SOL as f64) as u64
 }
 
+/// Number of decimal places of a SOL amount
+const SOL_DECIMALS: usize = 9;
+
+/// Exactly convert a decimal SOL string, such as `"1.5"`, into lamports
+///
+/// Unlike [`sol_to_lamports`] no floating point arithmetic is involved, so the
+/// result is exact. Returns `None` if the string is not a plain non-negative
+/// decimal number, has more than nine fractional digits, or the amount does
+/// not fit in a `u64`.
+pub fn sol_str_to_lamports(sol_str: &str) -> Option<u64> {
+    let (sol, fraction) = sol_str.split_once('.').unwrap_or((sol_str, ""));
+    if (sol.is_empty() && fraction.is_empty())
+        || fraction.len() > SOL_DECIMALS
+        || !sol
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let sol = if sol.is_empty() {
+        0
+    } else {
+        sol.parse::<u64>().ok()?
+    };
+    let lamports = if fraction.is_empty() {
+        0
+    } else {
+        format!("{fraction:0<SOL_DECIMALS$}").parse::<u64>().ok()?
+    };
+    sol.checked_mul(LAMPORTS_PER_SOL)?.checked_add(lamports)
+}
+
 use std::fmt::{Debug, Display, Formatter, Result};
 pub struct Sol(pub u64);
 
@@ -40,3 +73,42 @@ impl Debug for Sol {
         self.write_in_sol(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sol_str_to_lamports() {
+        assert_eq!(sol_str_to_lamports("0"), Some(0));
+        assert_eq!(sol_str_to_lamports("1"), Some(LAMPORTS_PER_SOL));
+        assert_eq!(sol_str_to_lamports("1.5"), Some(1_500_000_000));
+        assert_eq!(sol_str_to_lamports(".5"), Some(500_000_000));
+        assert_eq!(sol_str_to_lamports("2."), Some(2 * LAMPORTS_PER_SOL));
+        assert_eq!(sol_str_to_lamports("0.000000001"), Some(1));
+        // the f64 based conversion truncates this to 1_004_999_999
+        assert_eq!(sol_to_lamports(1.005), 1_004_999_999);
+        assert_eq!(sol_str_to_lamports("1.005"), Some(1_005_000_000));
+        assert_eq!(sol_str_to_lamports("18446744073.709551615"), Some(u64::MAX));
+
+        assert_eq!(sol_str_to_lamports(""), None);
+        assert_eq!(sol_str_to_lamports("."), None);
+        assert_eq!(sol_str_to_lamports("0.0000000001"), None);
+        assert_eq!(sol_str_to_lamports("-1"), None);
+        assert_eq!(sol_str_to_lamports("+1"), None);
+        assert_eq!(sol_str_to_lamports("1.+5"), None);
+        assert_eq!(sol_str_to_lamports("1e9"), None);
+        assert_eq!(sol_str_to_lamports("1.2.3"), None);
+        assert_eq!(sol_str_to_lamports("18446744073.709551616"), None);
+    }
+
+    #[test]
+    fn test_sol_display() {
+        assert_eq!(Sol(0).to_string(), "◎0.000000000");
+        assert_eq!(Sol(1_500_000_001).to_string(), "◎1.500000001");
+        assert_eq!(
+            Sol(sol_str_to_lamports("42.1").unwrap()).to_string(),
+            "◎42.100000000"
+        );
+    }
+}
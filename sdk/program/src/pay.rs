@@ -0,0 +1,439 @@
+//! Building and parsing [Solana Pay] URIs.
+//!
+//! [Solana Pay]: https://docs.solanapay.com/spec
+//!
+//! A Solana Pay URI is either a _transfer request_, which describes a
+//! transfer of SOL or an SPL token to a recipient, or a _transaction
+//! request_, which points a wallet at an HTTPS endpoint that will provide the
+//! transaction to sign.
+//!
+//! ```
+//! use solana_program::{
+//!     pay::{PayRequest, TransferRequest},
+//!     pubkey::Pubkey,
+//! };
+//!
+//! let mut request = TransferRequest::new(Pubkey::new_unique());
+//! request.amount = Some("1.5".to_string());
+//! request.label = Some("Coffee shop".to_string());
+//!
+//! let uri = request.to_string();
+//! assert_eq!(uri.parse::<PayRequest>(), Ok(PayRequest::Transfer(request)));
+//! ```
+
+#![cfg(feature = "pay")]
+
+use {
+    crate::pubkey::Pubkey,
+    std::{fmt, str::FromStr},
+    thiserror::Error,
+};
+
+/// The URI scheme of Solana Pay requests
+pub const SOLANA_PAY_SCHEME: &str = "solana";
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PayUriError {
+    #[error("URI does not use the solana: scheme")]
+    InvalidScheme,
+    #[error("invalid recipient")]
+    InvalidRecipient,
+    #[error("invalid amount")]
+    InvalidAmount,
+    #[error("invalid {0} pubkey")]
+    InvalidPubkey(&'static str),
+    #[error("invalid percent-encoding")]
+    InvalidEncoding,
+    #[error("parameter {0} is given more than once")]
+    DuplicateParameter(&'static str),
+    #[error("transaction request link must use https")]
+    InvalidLink,
+}
+
+/// A request to transfer SOL or an SPL token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferRequest {
+    /// The account to transfer to, a system account for SOL transfers
+    pub recipient: Pubkey,
+    /// The amount in SOL or in tokens, as a decimal string
+    pub amount: Option<String>,
+    /// The mint of the token to transfer; SOL is transferred if unset
+    pub spl_token: Option<Pubkey>,
+    /// Keys to add to the transfer instruction so it can be found on chain
+    pub references: Vec<Pubkey>,
+    /// The source of the request, e.g. a merchant name
+    pub label: Option<String>,
+    /// A description of the request, e.g. the item being paid for
+    pub message: Option<String>,
+    /// A memo to include in the transaction
+    pub memo: Option<String>,
+}
+
+/// A request for the wallet to fetch a transaction to sign from `link`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionRequest {
+    /// The HTTPS endpoint providing the transaction
+    pub link: String,
+    pub label: Option<String>,
+    pub message: Option<String>,
+}
+
+/// Either kind of Solana Pay request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayRequest {
+    Transfer(TransferRequest),
+    Transaction(TransactionRequest),
+}
+
+impl TransferRequest {
+    pub fn new(recipient: Pubkey) -> Self {
+        Self {
+            recipient,
+            amount: None,
+            spl_token: None,
+            references: vec![],
+            label: None,
+            message: None,
+            memo: None,
+        }
+    }
+}
+
+impl TransactionRequest {
+    pub fn new(link: String) -> Self {
+        Self {
+            link,
+            label: None,
+            message: None,
+        }
+    }
+}
+
+// Whether `amount` is a non-negative integer or decimal number, without a
+// leading or trailing decimal point
+fn is_valid_amount(amount: &str) -> bool {
+    let (integer, fraction) = match amount.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (amount, None),
+    };
+    let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    all_digits(integer) && fraction.map_or(true, all_digits)
+}
+
+// Percent-encodes `s` like JavaScript's `encodeURIComponent`
+fn encode(s: &str) -> String {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'!'
+            | b'~'
+            | b'*'
+            | b'\''
+            | b'('
+            | b')' => encoded.push(byte as char),
+            _ => {
+                encoded.push('%');
+                encoded.push(HEX[usize::from(byte >> 4)] as char);
+                encoded.push(HEX[usize::from(byte & 0xf)] as char);
+            }
+        }
+    }
+    encoded
+}
+
+fn decode(s: &str) -> Result<String, PayUriError> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [
+                iter.next().ok_or(PayUriError::InvalidEncoding)?,
+                iter.next().ok_or(PayUriError::InvalidEncoding)?,
+            ];
+            // `from_str_radix` would also accept a sign, as in `%+f`
+            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                return Err(PayUriError::InvalidEncoding);
+            }
+            let hex = std::str::from_utf8(&hex).map_err(|_| PayUriError::InvalidEncoding)?;
+            bytes.push(u8::from_str_radix(hex, 16).map_err(|_| PayUriError::InvalidEncoding)?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| PayUriError::InvalidEncoding)
+}
+
+// Writes the query parameters with a `?` before the first one
+struct QueryWriter<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    first: bool,
+}
+
+impl QueryWriter<'_, '_> {
+    fn param(&mut self, key: &str, value: Option<&str>) -> fmt::Result {
+        if let Some(value) = value {
+            let separator = if self.first { '?' } else { '&' };
+            self.first = false;
+            write!(self.f, "{separator}{key}={}", encode(value))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TransferRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{SOLANA_PAY_SCHEME}:{}", self.recipient)?;
+        let mut query = QueryWriter { f, first: true };
+        query.param("amount", self.amount.as_deref())?;
+        query.param(
+            "spl-token",
+            self.spl_token.map(|mint| mint.to_string()).as_deref(),
+        )?;
+        for reference in &self.references {
+            query.param("reference", Some(&reference.to_string()))?;
+        }
+        query.param("label", self.label.as_deref())?;
+        query.param("message", self.message.as_deref())?;
+        query.param("memo", self.memo.as_deref())
+    }
+}
+
+impl fmt::Display for TransactionRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Links with their own query must be encoded so that it is not read
+        // as the parameters of the request
+        if self.link.contains('?') {
+            write!(f, "{SOLANA_PAY_SCHEME}:{}", encode(&self.link))?;
+        } else {
+            write!(f, "{SOLANA_PAY_SCHEME}:{}", self.link)?;
+        }
+        let mut query = QueryWriter { f, first: true };
+        query.param("label", self.label.as_deref())?;
+        query.param("message", self.message.as_deref())
+    }
+}
+
+impl fmt::Display for PayRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Transfer(request) => request.fmt(f),
+            Self::Transaction(request) => request.fmt(f),
+        }
+    }
+}
+
+fn set_once(
+    field: &mut Option<String>,
+    key: &'static str,
+    value: String,
+) -> Result<(), PayUriError> {
+    if field.replace(value).is_some() {
+        return Err(PayUriError::DuplicateParameter(key));
+    }
+    Ok(())
+}
+
+impl FromStr for PayRequest {
+    type Err = PayUriError;
+
+    fn from_str(uri: &str) -> Result<Self, Self::Err> {
+        let rest = uri
+            .strip_prefix(SOLANA_PAY_SCHEME)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .ok_or(PayUriError::InvalidScheme)?;
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+        let mut amount = None;
+        let mut spl_token = None;
+        let mut references = vec![];
+        let mut label = None;
+        let mut message = None;
+        let mut memo = None;
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            let (key, value) = param.split_once('=').unwrap_or((param, ""));
+            let value = decode(value)?;
+            match decode(key)?.as_str() {
+                "amount" => set_once(&mut amount, "amount", value)?,
+                "spl-token" => set_once(&mut spl_token, "spl-token", value)?,
+                "reference" => references.push(
+                    Pubkey::from_str(&value)
+                        .map_err(|_| PayUriError::InvalidPubkey("reference"))?,
+                ),
+                "label" => set_once(&mut label, "label", value)?,
+                "message" => set_once(&mut message, "message", value)?,
+                "memo" => set_once(&mut memo, "memo", value)?,
+                // Unknown parameters are ignored for forward compatibility
+                _ => {}
+            }
+        }
+
+        let path = decode(path)?;
+        if path.contains(':') {
+            if !path.starts_with("https://") {
+                return Err(PayUriError::InvalidLink);
+            }
+            return Ok(Self::Transaction(TransactionRequest {
+                link: path,
+                label,
+                message,
+            }));
+        }
+
+        let recipient = Pubkey::from_str(&path).map_err(|_| PayUriError::InvalidRecipient)?;
+        if !amount.as_deref().map_or(true, is_valid_amount) {
+            return Err(PayUriError::InvalidAmount);
+        }
+        let spl_token = spl_token
+            .map(|mint| Pubkey::from_str(&mint))
+            .transpose()
+            .map_err(|_| PayUriError::InvalidPubkey("spl-token"))?;
+        Ok(Self::Transfer(TransferRequest {
+            recipient,
+            amount,
+            spl_token,
+            references,
+            label,
+            message,
+            memo,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_request() {
+        let recipient = Pubkey::from_str("mvines9iiHiQTysrwkJjGf2gb9Ex9jXJX8ns3qwf2kN").unwrap();
+        let uri = "solana:mvines9iiHiQTysrwkJjGf2gb9Ex9jXJX8ns3qwf2kN?amount=1&label=Michael\
+                   &message=Thanks%20for%20all%20the%20fish&memo=OrderId12345";
+        let request = TransferRequest {
+            amount: Some("1".to_string()),
+            label: Some("Michael".to_string()),
+            message: Some("Thanks for all the fish".to_string()),
+            memo: Some("OrderId12345".to_string()),
+            ..TransferRequest::new(recipient)
+        };
+        assert_eq!(uri.parse(), Ok(PayRequest::Transfer(request.clone())));
+        assert_eq!(request.to_string(), uri);
+
+        let mint = Pubkey::from_str("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").unwrap();
+        let reference = Pubkey::new_unique();
+        let request = TransferRequest {
+            amount: Some("0.01".to_string()),
+            spl_token: Some(mint),
+            references: vec![reference, Pubkey::new_unique()],
+            ..TransferRequest::new(recipient)
+        };
+        let uri = request.to_string();
+        assert!(uri.starts_with(&format!(
+            "solana:{recipient}?amount=0.01&spl-token={mint}&reference={reference}&"
+        )));
+        assert_eq!(uri.parse(), Ok(PayRequest::Transfer(request)));
+
+        let request = TransferRequest::new(recipient);
+        assert_eq!(request.to_string(), format!("solana:{recipient}"));
+        assert_eq!(
+            request.to_string().parse(),
+            Ok(PayRequest::Transfer(request))
+        );
+    }
+
+    #[test]
+    fn test_transaction_request() {
+        let uri = "solana:https://example.com/solana-pay";
+        let request = TransactionRequest::new("https://example.com/solana-pay".to_string());
+        assert_eq!(uri.parse(), Ok(PayRequest::Transaction(request.clone())));
+        assert_eq!(request.to_string(), uri);
+
+        let uri = "solana:https%3A%2F%2Fexample.com%2Fsolana-pay%3Forder%3D12345?label=Shop";
+        let request = TransactionRequest {
+            label: Some("Shop".to_string()),
+            ..TransactionRequest::new("https://example.com/solana-pay?order=12345".to_string())
+        };
+        assert_eq!(uri.parse(), Ok(PayRequest::Transaction(request.clone())));
+        assert_eq!(
+            request.to_string(),
+            "solana:https%3A%2F%2Fexample.com%2Fsolana-pay%3Forder%3D12345?label=Shop"
+        );
+        assert_eq!(
+            request.to_string().parse(),
+            Ok(PayRequest::Transaction(request))
+        );
+    }
+
+    #[test]
+    fn test_invalid_requests() {
+        let recipient = Pubkey::new_unique();
+        for (uri, error) in [
+            (format!("bitcoin:{recipient}"), PayUriError::InvalidScheme),
+            (
+                "solana:notapubkey".to_string(),
+                PayUriError::InvalidRecipient,
+            ),
+            (
+                "solana:http://example.com".to_string(),
+                PayUriError::InvalidLink,
+            ),
+            (
+                format!("solana:{recipient}?amount=.5"),
+                PayUriError::InvalidAmount,
+            ),
+            (
+                format!("solana:{recipient}?amount=1."),
+                PayUriError::InvalidAmount,
+            ),
+            (
+                format!("solana:{recipient}?amount=-1"),
+                PayUriError::InvalidAmount,
+            ),
+            (
+                format!("solana:{recipient}?amount=1&amount=2"),
+                PayUriError::DuplicateParameter("amount"),
+            ),
+            (
+                format!("solana:{recipient}?reference=x"),
+                PayUriError::InvalidPubkey("reference"),
+            ),
+            (
+                format!("solana:{recipient}?spl-token=x"),
+                PayUriError::InvalidPubkey("spl-token"),
+            ),
+            (
+                format!("solana:{recipient}?label=%E"),
+                PayUriError::InvalidEncoding,
+            ),
+            (
+                format!("solana:{recipient}?label=%FF"),
+                PayUriError::InvalidEncoding,
+            ),
+            (
+                format!("solana:{recipient}?label=%+f"),
+                PayUriError::InvalidEncoding,
+            ),
+        ] {
+            assert_eq!(uri.parse::<PayRequest>(), Err(error), "{uri}");
+        }
+
+        // Unknown parameters are ignored
+        assert_eq!(
+            format!("solana:{recipient}?foo=bar").parse(),
+            Ok(PayRequest::Transfer(TransferRequest::new(recipient)))
+        );
+    }
+
+    #[test]
+    fn test_encoding() {
+        let text = "a b&c=d/é?!~*'()";
+        assert_eq!(encode(text), "a%20b%26c%3Dd%2F%C3%A9%3F!~*'()");
+        assert_eq!(decode(&encode(text)).unwrap(), text);
+    }
+}
@@ -0,0 +1,171 @@
+//! A bounded cache of [program derived addresses][pda].
+//!
+//! [pda]: https://solana.com/docs/core/cpi#program-derived-addresses
+//!
+//! [`Pubkey::find_program_address`] searches for a bump seed by repeatedly
+//! hashing the seeds and checking the result against the ed25519 curve, which
+//! costs several microseconds even when the first bump seed succeeds. Clients
+//! tend to derive the same few addresses over and over, so this module keeps
+//! the most recently derived addresses in an LRU cache keyed by the seeds and
+//! the program id.
+//!
+//! Requires the `pda-cache` feature, and is not available on-chain, where
+//! derivation is done by a syscall.
+
+#![cfg(all(feature = "pda-cache", not(target_os = "solana")))]
+
+use {
+    crate::{
+        pubkey::{Pubkey, MAX_SEED_LEN, PUBKEY_BYTES},
+        sync::{LazyLock, Mutex},
+    },
+    lru::LruCache,
+};
+
+/// Number of addresses kept by the cache behind [`find_program_address`]
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+static PDA_CACHE: LazyLock<PdaCache> = LazyLock::new(|| PdaCache::new(DEFAULT_CAPACITY));
+
+/// Like [`Pubkey::find_program_address`], but served from a process-wide cache
+/// of the [`DEFAULT_CAPACITY`] most recently derived addresses.
+pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+    PDA_CACHE.find_program_address(seeds, program_id)
+}
+
+/// Like [`Pubkey::try_find_program_address`], but served from a process-wide
+/// cache of the [`DEFAULT_CAPACITY`] most recently derived addresses.
+pub fn try_find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Option<(Pubkey, u8)> {
+    PDA_CACHE.try_find_program_address(seeds, program_id)
+}
+
+/// An LRU cache of derived addresses and their bump seeds.
+///
+/// Only successful derivations are cached.
+pub struct PdaCache {
+    cache: Mutex<LruCache<Vec<u8>, (Pubkey, u8)>>,
+}
+
+impl PdaCache {
+    /// Create a cache that holds at most `capacity` addresses.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            cache: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Like [`Pubkey::find_program_address`], but consulting the cache first.
+    pub fn find_program_address(&self, seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+        self.try_find_program_address(seeds, program_id)
+            .unwrap_or_else(|| panic!("Unable to find a viable program address bump seed"))
+    }
+
+    /// Like [`Pubkey::try_find_program_address`], but consulting the cache
+    /// first.
+    pub fn try_find_program_address(
+        &self,
+        seeds: &[&[u8]],
+        program_id: &Pubkey,
+    ) -> Option<(Pubkey, u8)> {
+        let Some(key) = cache_key(seeds, program_id) else {
+            return Pubkey::try_find_program_address(seeds, program_id);
+        };
+        if let Some(entry) = self.cache.lock().get(&key) {
+            return Some(*entry);
+        }
+        // Derive without holding the lock so that concurrent misses don't
+        // serialize on the search
+        let entry = Pubkey::try_find_program_address(seeds, program_id)?;
+        self.cache.lock().put(key, entry);
+        Some(entry)
+    }
+
+    /// Number of addresses currently cached
+    pub fn len(&self) -> usize {
+        self.cache.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all cached addresses
+    pub fn clear(&self) {
+        self.cache.lock().clear()
+    }
+}
+
+/// The seeds, each prefixed by its length so that different splits of the same
+/// bytes don't collide, followed by the program id.
+///
+/// Returns `None` for seeds too long to derive an address from, which are not
+/// worth caching.
+fn cache_key(seeds: &[&[u8]], program_id: &Pubkey) -> Option<Vec<u8>> {
+    if seeds.iter().any(|seed| seed.len() > MAX_SEED_LEN) {
+        return None;
+    }
+    let len = seeds
+        .iter()
+        .map(|seed| seed.len())
+        .sum::<usize>()
+        .saturating_add(seeds.len())
+        .saturating_add(PUBKEY_BYTES);
+    let mut key = Vec::with_capacity(len);
+    for seed in seeds {
+        key.push(seed.len() as u8);
+        key.extend_from_slice(seed);
+    }
+    key.extend_from_slice(program_id.as_ref());
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pda_cache() {
+        let cache = PdaCache::new(2);
+        assert!(cache.is_empty());
+        let program_id = Pubkey::new_unique();
+        let other_program_id = Pubkey::new_unique();
+
+        for seeds in [&[b"ab".as_slice(), b"c"][..], &[b"a", b"bc"], &[b"abc"]] {
+            assert_eq!(
+                cache.find_program_address(seeds, &program_id),
+                Pubkey::find_program_address(seeds, &program_id)
+            );
+            // Served from the cache the second time
+            assert_eq!(
+                cache.find_program_address(seeds, &program_id),
+                Pubkey::find_program_address(seeds, &program_id)
+            );
+        }
+        assert_eq!(cache.len(), 2);
+        assert_eq!(
+            cache.find_program_address(&[b"abc"], &other_program_id),
+            Pubkey::find_program_address(&[b"abc"], &other_program_id)
+        );
+        assert_eq!(cache.len(), 2);
+
+        // Failed derivations are not cached
+        let too_long = [0; MAX_SEED_LEN + 1];
+        assert_eq!(
+            cache.try_find_program_address(&[&too_long], &program_id),
+            None
+        );
+        assert_eq!(cache.len(), 2);
+
+        cache.clear();
+        assert!(cache.is_empty());
+
+        assert_eq!(
+            find_program_address(&[b"abc"], &program_id),
+            Pubkey::find_program_address(&[b"abc"], &program_id)
+        );
+        assert_eq!(
+            try_find_program_address(&[b"abc"], &program_id),
+            Pubkey::try_find_program_address(&[b"abc"], &program_id)
+        );
+    }
+}
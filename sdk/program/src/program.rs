@@ -7,6 +7,16 @@
 //! [`invoke`]: invoke
 //! [`invoke_signed`]: invoke_signed
 //! [cpi]: https://solana.com/docs/core/cpi
+//!
+//! When built for non-SBF targets these functions call
+//! [`SyscallStubs::sol_invoke_signed`], which by default only logs that
+//! invocation is unavailable. `solana-program-test` installs stubs that
+//! dispatch to the processors registered with `ProgramTest::add_program`,
+//! deriving the signers of `invoke_signed` from the given seeds and the
+//! calling program's ID, and applying the same privilege checks as the
+//! runtime.
+//!
+//! [`SyscallStubs::sol_invoke_signed`]: crate::program_stubs::SyscallStubs::sol_invoke_signed
 
 use crate::{
     account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction, pubkey::Pubkey,
@@ -323,6 +333,10 @@ pub const MAX_RETURN_DATA: usize = 1024;
 ///
 /// The maximum size of return data is [`MAX_RETURN_DATA`]. Return data is
 /// retrieved by the caller with [`get_return_data`].
+///
+/// Off-chain, the default syscall stubs keep return data in a thread-local
+/// buffer attributed to the default [`Pubkey`], so that tests can read it back
+/// with [`get_return_data`].
 pub fn set_return_data(data: &[u8]) {
     #[cfg(target_os = "solana")]
     unsafe {
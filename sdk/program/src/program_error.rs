@@ -159,7 +159,7 @@ pub const IMMUTABLE: u64 = to_builtin!(25);
 pub const INCORRECT_AUTHORITY: u64 = to_builtin!(26);
 // Warning: Any new program errors added here must also be:
 // - Added to the below conversions
-// - Added as an equivalent to InstructionError
+// - Added as an equivalent to InstructionError, and to the conversions between the two
 // - Be featureized in the BPF loader to return `InstructionError::InvalidError`
 //   until the feature is activated
 
@@ -286,6 +286,45 @@ impl TryFrom<InstructionError> for ProgramError {
     }
 }
 
+impl From<ProgramError> for InstructionError {
+    fn from(error: ProgramError) -> Self {
+        match error {
+            ProgramError::Custom(err) => Self::Custom(err),
+            ProgramError::InvalidArgument => Self::InvalidArgument,
+            ProgramError::InvalidInstructionData => Self::InvalidInstructionData,
+            ProgramError::InvalidAccountData => Self::InvalidAccountData,
+            ProgramError::AccountDataTooSmall => Self::AccountDataTooSmall,
+            ProgramError::InsufficientFunds => Self::InsufficientFunds,
+            ProgramError::IncorrectProgramId => Self::IncorrectProgramId,
+            ProgramError::MissingRequiredSignature => Self::MissingRequiredSignature,
+            ProgramError::AccountAlreadyInitialized => Self::AccountAlreadyInitialized,
+            ProgramError::UninitializedAccount => Self::UninitializedAccount,
+            ProgramError::NotEnoughAccountKeys => Self::NotEnoughAccountKeys,
+            ProgramError::AccountBorrowFailed => Self::AccountBorrowFailed,
+            ProgramError::MaxSeedLengthExceeded => Self::MaxSeedLengthExceeded,
+            ProgramError::InvalidSeeds => Self::InvalidSeeds,
+            ProgramError::BorshIoError(err) => Self::BorshIoError(err),
+            ProgramError::AccountNotRentExempt => Self::AccountNotRentExempt,
+            ProgramError::UnsupportedSysvar => Self::UnsupportedSysvar,
+            ProgramError::IllegalOwner => Self::IllegalOwner,
+            ProgramError::MaxAccountsDataAllocationsExceeded => {
+                Self::MaxAccountsDataAllocationsExceeded
+            }
+            ProgramError::InvalidRealloc => Self::InvalidRealloc,
+            ProgramError::MaxInstructionTraceLengthExceeded => {
+                Self::MaxInstructionTraceLengthExceeded
+            }
+            ProgramError::BuiltinProgramsMustConsumeComputeUnits => {
+                Self::BuiltinProgramsMustConsumeComputeUnits
+            }
+            ProgramError::InvalidAccountOwner => Self::InvalidAccountOwner,
+            ProgramError::ArithmeticOverflow => Self::ArithmeticOverflow,
+            ProgramError::Immutable => Self::Immutable,
+            ProgramError::IncorrectAuthority => Self::IncorrectAuthority,
+        }
+    }
+}
+
 impl<T> From<T> for InstructionError
 where
     T: ToPrimitive,
@@ -348,3 +387,60 @@ impl From<BorshIoError> for ProgramError {
         Self::BorshIoError(format!("{error}"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instruction_error_conversions() {
+        let errors = [
+            ProgramError::Custom(0),
+            ProgramError::Custom(42),
+            ProgramError::InvalidArgument,
+            ProgramError::InvalidInstructionData,
+            ProgramError::InvalidAccountData,
+            ProgramError::AccountDataTooSmall,
+            ProgramError::InsufficientFunds,
+            ProgramError::IncorrectProgramId,
+            ProgramError::MissingRequiredSignature,
+            ProgramError::AccountAlreadyInitialized,
+            ProgramError::UninitializedAccount,
+            ProgramError::NotEnoughAccountKeys,
+            ProgramError::AccountBorrowFailed,
+            ProgramError::MaxSeedLengthExceeded,
+            ProgramError::InvalidSeeds,
+            ProgramError::BorshIoError("Unknown".to_string()),
+            ProgramError::AccountNotRentExempt,
+            ProgramError::UnsupportedSysvar,
+            ProgramError::IllegalOwner,
+            ProgramError::MaxAccountsDataAllocationsExceeded,
+            ProgramError::InvalidRealloc,
+            ProgramError::MaxInstructionTraceLengthExceeded,
+            ProgramError::BuiltinProgramsMustConsumeComputeUnits,
+            ProgramError::InvalidAccountOwner,
+            ProgramError::ArithmeticOverflow,
+            ProgramError::Immutable,
+            ProgramError::IncorrectAuthority,
+        ];
+        for error in errors {
+            let instruction_error = InstructionError::from(error.clone());
+            // Converting directly agrees with converting through the error code
+            assert_eq!(
+                instruction_error,
+                InstructionError::from(u64::from(error.clone()))
+            );
+            assert_eq!(ProgramError::try_from(instruction_error), Ok(error));
+        }
+
+        // Unlike the error code, the direct conversion keeps the message
+        assert_eq!(
+            InstructionError::from(ProgramError::BorshIoError("eof".to_string())),
+            InstructionError::BorshIoError("eof".to_string())
+        );
+        assert_eq!(
+            ProgramError::try_from(InstructionError::GenericError),
+            Err(InstructionError::GenericError)
+        );
+    }
+}
@@ -1,6 +1,10 @@
 //! The [`Pack`] serialization trait.
 
-use crate::program_error::ProgramError;
+use crate::{
+    program_error::ProgramError,
+    program_option::COption,
+    pubkey::{Pubkey, PUBKEY_BYTES},
+};
 
 /// Check if a program account state is initialized
 pub trait IsInitialized {
@@ -55,3 +59,281 @@ pub trait Pack: Sealed {
         Ok(())
     }
 }
+
+/// A fixed-size field of a [`Pack`] type, used by [`impl_pack!`](crate::impl_pack)
+///
+/// Integers are little-endian, `bool` is a single `0` or `1` byte, and
+/// [`COption`] is a 4-byte little-endian tag followed by the value, zeroed when
+/// `None`.
+pub trait PackField: Sized {
+    /// The length, in bytes, of the packed field
+    const LEN: usize;
+    /// Pack into `dst`, which is exactly `LEN` bytes long
+    fn pack_field(&self, dst: &mut [u8]);
+    /// Unpack from `src`, which is exactly `LEN` bytes long
+    fn unpack_field(src: &[u8]) -> Result<Self, ProgramError>;
+}
+
+macro_rules! impl_pack_field_for_int {
+    ($($ty:ty),*) => {
+        $(
+            impl PackField for $ty {
+                const LEN: usize = std::mem::size_of::<$ty>();
+                fn pack_field(&self, dst: &mut [u8]) {
+                    dst.copy_from_slice(&self.to_le_bytes());
+                }
+                fn unpack_field(src: &[u8]) -> Result<Self, ProgramError> {
+                    src.try_into()
+                        .map(<$ty>::from_le_bytes)
+                        .map_err(|_| ProgramError::InvalidAccountData)
+                }
+            }
+        )*
+    };
+}
+
+impl_pack_field_for_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl PackField for bool {
+    const LEN: usize = 1;
+    fn pack_field(&self, dst: &mut [u8]) {
+        dst[0] = u8::from(*self);
+    }
+    fn unpack_field(src: &[u8]) -> Result<Self, ProgramError> {
+        match src {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+impl<const N: usize> PackField for [u8; N] {
+    const LEN: usize = N;
+    fn pack_field(&self, dst: &mut [u8]) {
+        dst.copy_from_slice(self);
+    }
+    fn unpack_field(src: &[u8]) -> Result<Self, ProgramError> {
+        src.try_into().map_err(|_| ProgramError::InvalidAccountData)
+    }
+}
+
+impl PackField for Pubkey {
+    const LEN: usize = PUBKEY_BYTES;
+    fn pack_field(&self, dst: &mut [u8]) {
+        dst.copy_from_slice(self.as_ref());
+    }
+    fn unpack_field(src: &[u8]) -> Result<Self, ProgramError> {
+        Pubkey::try_from(src).map_err(|_| ProgramError::InvalidAccountData)
+    }
+}
+
+const COPTION_TAG_LEN: usize = 4;
+
+impl<T: PackField> PackField for COption<T> {
+    const LEN: usize = COPTION_TAG_LEN.saturating_add(T::LEN);
+    fn pack_field(&self, dst: &mut [u8]) {
+        let (tag, value) = dst.split_at_mut(COPTION_TAG_LEN);
+        match self {
+            COption::Some(inner) => {
+                tag.copy_from_slice(&[1, 0, 0, 0]);
+                inner.pack_field(value);
+            }
+            COption::None => {
+                tag.fill(0);
+                value.fill(0);
+            }
+        }
+    }
+    fn unpack_field(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let (tag, value) = src.split_at(COPTION_TAG_LEN);
+        match tag {
+            [0, 0, 0, 0] => Ok(COption::None),
+            [1, 0, 0, 0] => T::unpack_field(value).map(COption::Some),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn next_field<'a>(src: &mut &'a [u8], len: usize) -> &'a [u8] {
+    let (field, rest) = src.split_at(len);
+    *src = rest;
+    field
+}
+
+#[doc(hidden)]
+pub fn next_field_mut<'a>(dst: &mut &'a mut [u8], len: usize) -> &'a mut [u8] {
+    let (field, rest) = std::mem::take(dst).split_at_mut(len);
+    *dst = rest;
+    field
+}
+
+/// Implements [`Sealed`] and [`Pack`] for a struct laid out as its fields in
+/// declaration order, with no padding.
+///
+/// Every field type must implement [`PackField`], and every field of the
+/// struct must be listed.
+///
+/// # Example
+///
+/// ```
+/// use solana_program::{impl_pack, program_option::COption, program_pack::Pack, pubkey::Pubkey};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Escrow {
+///     owner: Pubkey,
+///     amount: u64,
+///     close_authority: COption<Pubkey>,
+///     is_initialized: bool,
+/// }
+///
+/// impl_pack!(Escrow {
+///     owner: Pubkey,
+///     amount: u64,
+///     close_authority: COption<Pubkey>,
+///     is_initialized: bool,
+/// });
+///
+/// assert_eq!(Escrow::LEN, 32 + 8 + 36 + 1);
+///
+/// let escrow = Escrow {
+///     owner: Pubkey::new_unique(),
+///     amount: 42,
+///     close_authority: COption::None,
+///     is_initialized: true,
+/// };
+/// let mut data = [0; Escrow::LEN];
+/// Escrow::pack(escrow, &mut data).unwrap();
+/// assert_eq!(Escrow::unpack_unchecked(&data).unwrap().amount, 42);
+/// ```
+#[macro_export]
+macro_rules! impl_pack {
+    ($name:ident { $($field:ident: $ty:ty),+ $(,)? }) => {
+        impl $crate::program_pack::Sealed for $name {}
+
+        impl $crate::program_pack::Pack for $name {
+            const LEN: usize =
+                0usize $(.saturating_add(<$ty as $crate::program_pack::PackField>::LEN))+;
+
+            fn pack_into_slice(&self, dst: &mut [u8]) {
+                let mut dst = dst;
+                $(
+                    $crate::program_pack::PackField::pack_field(
+                        &self.$field,
+                        $crate::program_pack::next_field_mut(
+                            &mut dst,
+                            <$ty as $crate::program_pack::PackField>::LEN,
+                        ),
+                    );
+                )+
+            }
+
+            fn unpack_from_slice(
+                src: &[u8],
+            ) -> Result<Self, $crate::program_error::ProgramError> {
+                let mut src = src;
+                $(
+                    let $field = <$ty as $crate::program_pack::PackField>::unpack_field(
+                        $crate::program_pack::next_field(
+                            &mut src,
+                            <$ty as $crate::program_pack::PackField>::LEN,
+                        ),
+                    )?;
+                )+
+                Ok(Self { $($field),+ })
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct TokenAccount {
+        mint: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+        delegate: COption<Pubkey>,
+        state: u8,
+        is_native: COption<u64>,
+        delegated_amount: u64,
+        close_authority: COption<Pubkey>,
+    }
+
+    impl IsInitialized for TokenAccount {
+        fn is_initialized(&self) -> bool {
+            self.state != 0
+        }
+    }
+
+    impl_pack!(TokenAccount {
+        mint: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+        delegate: COption<Pubkey>,
+        state: u8,
+        is_native: COption<u64>,
+        delegated_amount: u64,
+        close_authority: COption<Pubkey>,
+    });
+
+    #[test]
+    fn test_impl_pack() {
+        // Same layout as the SPL token account
+        assert_eq!(TokenAccount::LEN, 165);
+
+        let account = TokenAccount {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            amount: 0x0102_0304_0506_0708,
+            delegate: COption::Some(Pubkey::new_unique()),
+            state: 1,
+            is_native: COption::None,
+            delegated_amount: 7,
+            close_authority: COption::None,
+        };
+        let mut data = vec![0xff; TokenAccount::LEN];
+        TokenAccount::pack(account.clone(), &mut data).unwrap();
+
+        assert_eq!(&data[..32], account.mint.as_ref());
+        assert_eq!(&data[64..72], &[8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(&data[72..76], &[1, 0, 0, 0]);
+        assert_eq!(data[108], 1);
+        assert_eq!(&data[109..121], &[0; 12]);
+        assert_eq!(TokenAccount::unpack(&data).unwrap(), account);
+
+        assert_eq!(
+            TokenAccount::unpack(&data[1..]),
+            Err(ProgramError::InvalidAccountData)
+        );
+        let mut bad_tag = data.clone();
+        bad_tag[72] = 2;
+        assert_eq!(
+            TokenAccount::unpack(&bad_tag),
+            Err(ProgramError::InvalidAccountData)
+        );
+        let mut uninitialized = data;
+        uninitialized[108] = 0;
+        assert_eq!(
+            TokenAccount::unpack(&uninitialized),
+            Err(ProgramError::UninitializedAccount)
+        );
+        assert!(TokenAccount::unpack_unchecked(&uninitialized).is_ok());
+    }
+
+    #[test]
+    fn test_pack_field_bool() {
+        assert_eq!(bool::unpack_field(&[0]), Ok(false));
+        assert_eq!(bool::unpack_field(&[1]), Ok(true));
+        assert_eq!(
+            bool::unpack_field(&[2]),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+}
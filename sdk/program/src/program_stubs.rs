@@ -5,15 +5,25 @@
 use {
     crate::{
         account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction,
-        program_error::UNSUPPORTED_SYSVAR, pubkey::Pubkey,
+        program::MAX_RETURN_DATA, program_error::UNSUPPORTED_SYSVAR, pubkey::Pubkey,
+        sync::LazyLock,
     },
     base64::{prelude::BASE64_STANDARD, Engine},
     itertools::Itertools,
-    std::sync::{Arc, RwLock},
+    std::{
+        cell::RefCell,
+        sync::{Arc, RwLock},
+    },
 };
 
-lazy_static::lazy_static! {
-    static ref SYSCALL_STUBS: Arc<RwLock<Box<dyn SyscallStubs>>> = Arc::new(RwLock::new(Box::new(DefaultSyscallStubs {})));
+static SYSCALL_STUBS: LazyLock<Arc<RwLock<Box<dyn SyscallStubs>>>> =
+    LazyLock::new(|| Arc::new(RwLock::new(Box::new(DefaultSyscallStubs {}))));
+
+thread_local! {
+    // Return data recorded by the default `sol_set_return_data` stub. The
+    // stubs have no notion of the running program, so the data is attributed
+    // to the default pubkey.
+    static RETURN_DATA: RefCell<Option<(Pubkey, Vec<u8>)>> = const { RefCell::new(None) };
 }
 
 // The default syscall stubs may not do much, but `set_syscalls()` can be used
@@ -96,9 +106,17 @@ pub trait SyscallStubs: Sync + Send {
         }
     }
     fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
-        None
+        RETURN_DATA.with(|return_data| return_data.borrow().clone())
+    }
+    fn sol_set_return_data(&self, data: &[u8]) {
+        assert!(
+            data.len() <= MAX_RETURN_DATA,
+            "return data too large: {} > {MAX_RETURN_DATA}",
+            data.len()
+        );
+        let value = (!data.is_empty()).then(|| (Pubkey::default(), data.to_vec()));
+        RETURN_DATA.with(|return_data| *return_data.borrow_mut() = value);
     }
-    fn sol_set_return_data(&self, _data: &[u8]) {}
     fn sol_log_data(&self, fields: &[&[u8]]) {
         println!(
             "data: {}",
@@ -260,4 +278,30 @@ mod tests {
         assert!(is_nonoverlapping::<u8>(255, 3, 254, 1));
         assert!(!is_nonoverlapping::<u8>(255, 2, 254, 3));
     }
+
+    #[test]
+    fn test_default_return_data() {
+        let stubs = DefaultSyscallStubs {};
+        assert_eq!(stubs.sol_get_return_data(), None);
+
+        stubs.sol_set_return_data(&[1, 2, 3]);
+        assert_eq!(
+            stubs.sol_get_return_data(),
+            Some((Pubkey::default(), vec![1, 2, 3]))
+        );
+
+        // return data is per-thread
+        std::thread::spawn(|| assert_eq!(DefaultSyscallStubs {}.sol_get_return_data(), None))
+            .join()
+            .unwrap();
+
+        stubs.sol_set_return_data(&[]);
+        assert_eq!(stubs.sol_get_return_data(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "return data too large")]
+    fn test_default_return_data_too_large() {
+        DefaultSyscallStubs {}.sol_set_return_data(&[0; MAX_RETURN_DATA + 1]);
+    }
 }
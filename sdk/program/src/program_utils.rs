@@ -1,6 +1,8 @@
-//! Contains a single utility function for deserializing from [bincode].
+//! Utility functions for deserializing instruction data from [bincode] and
+//! [borsh].
 //!
 //! [bincode]: https://docs.rs/bincode
+//! [borsh]: https://borsh.io/
 
 use {crate::instruction::InstructionError, bincode::config::Options};
 
@@ -18,6 +20,40 @@ where
         .map_err(|_| InstructionError::InvalidInstructionData)
 }
 
+/// Like [`limited_deserialize`], but rejecting data with trailing bytes after
+/// the deserialized value.
+///
+/// Bincode's fixed-width integers and its checks of `bool`, `Option` and enum
+/// tags leave trailing bytes as the only way for untrusted data to encode the
+/// same value more than one way, so data accepted by this function is exactly
+/// the serialization of the value it returns.
+pub fn deserialize_checked<T>(instruction_data: &[u8], limit: u64) -> Result<T, InstructionError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    // All of the data is read, so the limit applies to its length
+    if instruction_data.len() as u64 > limit {
+        return Err(InstructionError::InvalidInstructionData);
+    }
+    bincode::options()
+        .with_limit(limit)
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+        .deserialize(instruction_data)
+        .map_err(|_| InstructionError::InvalidInstructionData)
+}
+
+/// Borsh counterpart of [`deserialize_checked`].
+///
+/// Borsh itself rejects non-canonical `bool`s, `Option` and enum tags, and
+/// out-of-order map and set entries, which leaves trailing bytes to check.
+pub fn borsh_deserialize_checked<T>(instruction_data: &[u8]) -> Result<T, InstructionError>
+where
+    T: borsh::BorshDeserialize,
+{
+    borsh::from_slice(instruction_data).map_err(|_| InstructionError::InvalidInstructionData)
+}
+
 #[cfg(test)]
 pub mod tests {
     use {super::*, solana_program::system_instruction::SystemInstruction};
@@ -39,4 +75,37 @@ pub mod tests {
         );
         assert!(limited_deserialize::<SystemInstruction>(&serialized, 3).is_err());
     }
+
+    #[test]
+    fn test_deserialize_checked() {
+        let item = SystemInstruction::Transfer { lamports: 42 };
+        let mut serialized = bincode::serialize(&item).unwrap();
+        assert_eq!(
+            deserialize_checked::<SystemInstruction>(&serialized, 12).as_ref(),
+            Ok(&item)
+        );
+        assert!(deserialize_checked::<SystemInstruction>(&serialized, 11).is_err());
+        assert!(deserialize_checked::<SystemInstruction>(&serialized[..11], 12).is_err());
+
+        serialized.push(0);
+        assert_eq!(
+            limited_deserialize::<SystemInstruction>(&serialized, 13).as_ref(),
+            Ok(&item)
+        );
+        assert!(deserialize_checked::<SystemInstruction>(&serialized, 13).is_err());
+    }
+
+    #[test]
+    fn test_borsh_deserialize_checked() {
+        let item = (Some(7u64), true);
+        let mut serialized = borsh::to_vec(&item).unwrap();
+        assert_eq!(borsh_deserialize_checked(&serialized), Ok(item));
+
+        serialized.push(0);
+        assert!(borsh_deserialize_checked::<(Option<u64>, bool)>(&serialized).is_err());
+        serialized.pop();
+        // A `bool` other than 0 or 1
+        *serialized.last_mut().unwrap() = 2;
+        assert!(borsh_deserialize_checked::<(Option<u64>, bool)>(&serialized).is_err());
+    }
 }
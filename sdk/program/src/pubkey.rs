@@ -2,16 +2,16 @@
 
 #![allow(clippy::arithmetic_side_effects)]
 
-#[cfg(test)]
+#[cfg(any(test, feature = "fuzz"))]
 use arbitrary::Arbitrary;
 use {
-    crate::{decode_error::DecodeError, hash::hashv, wasm_bindgen},
+    crate::{base58, decode_error::DecodeError, hash::hashv, wasm_bindgen},
     borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
     bytemuck::{Pod, Zeroable},
     num_derive::{FromPrimitive, ToPrimitive},
     std::{
         convert::{Infallible, TryFrom},
-        fmt, mem,
+        fmt,
         str::FromStr,
     },
     thiserror::Error,
@@ -28,6 +28,9 @@ const MAX_BASE58_LEN: usize = 44;
 
 const PDA_MARKER: &[u8; 21] = b"ProgramDerivedAddress";
 
+/// Number of base58 characters kept at each end by [`Pubkey::to_short_string`]
+const SHORT_STRING_AFFIX_LEN: usize = 4;
+
 #[derive(Error, Debug, Serialize, Clone, PartialEq, Eq, FromPrimitive, ToPrimitive)]
 pub enum PubkeyError {
     /// Length of the seed is too long for address generation
@@ -88,7 +91,7 @@ impl From<u64> for PubkeyError {
     Zeroable,
 )]
 #[borsh(crate = "borsh")]
-#[cfg_attr(test, derive(Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzz"), derive(Arbitrary))]
 pub struct Pubkey(pub(crate) [u8; 32]);
 
 impl crate::sanitize::Sanitize for Pubkey {}
@@ -120,14 +123,12 @@ impl FromStr for Pubkey {
         if s.len() > MAX_BASE58_LEN {
             return Err(ParsePubkeyError::WrongSize);
         }
-        let pubkey_vec = bs58::decode(s)
-            .into_vec()
-            .map_err(|_| ParsePubkeyError::Invalid)?;
-        if pubkey_vec.len() != mem::size_of::<Pubkey>() {
-            Err(ParsePubkeyError::WrongSize)
-        } else {
-            Pubkey::try_from(pubkey_vec).map_err(|_| ParsePubkeyError::Invalid)
-        }
+        base58::decode_32(s)
+            .map(Pubkey::from)
+            .map_err(|err| match err {
+                base58::DecodeError::InvalidCharacter => ParsePubkeyError::Invalid,
+                base58::DecodeError::WrongSize => ParsePubkeyError::WrongSize,
+            })
     }
 }
 
@@ -163,16 +164,37 @@ impl TryFrom<&str> for Pubkey {
     }
 }
 
-#[allow(clippy::used_underscore_binding)]
-pub fn bytes_are_curve_point<T: AsRef<[u8]>>(_bytes: T) -> bool {
+/// Check whether `bytes` is the compressed encoding of a point on the ed25519
+/// curve.
+///
+/// Off-chain the check is done in software, so it is available to wasm and
+/// native clients as well as to programs. Inputs that are not exactly
+/// [`PUBKEY_BYTES`] long are never curve points.
+pub fn bytes_are_curve_point<T: AsRef<[u8]>>(bytes: T) -> bool {
+    let bytes = bytes.as_ref();
+    if bytes.len() != PUBKEY_BYTES {
+        return false;
+    }
     #[cfg(not(target_os = "solana"))]
     {
-        curve25519_dalek::edwards::CompressedEdwardsY::from_slice(_bytes.as_ref())
+        curve25519_dalek::edwards::CompressedEdwardsY::from_slice(bytes)
             .decompress()
             .is_some()
     }
     #[cfg(target_os = "solana")]
-    unimplemented!();
+    {
+        // `0` is the curve id of curve25519 in its edwards representation
+        const CURVE25519_EDWARDS: u64 = 0;
+        let mut validate_result = 0u8;
+        let result = unsafe {
+            crate::syscalls::sol_curve_validate_point(
+                CURVE25519_EDWARDS,
+                bytes.as_ptr(),
+                &mut validate_result,
+            )
+        };
+        result == 0
+    }
 }
 
 impl Pubkey {
@@ -189,10 +211,10 @@ impl Pubkey {
     }
 
     #[deprecated(since = "1.3.9", note = "Please use 'Pubkey::new_unique' instead")]
-    #[cfg(not(target_os = "solana"))]
+    #[cfg(any(not(target_os = "solana"), feature = "custom-entropy"))]
     pub fn new_rand() -> Self {
         // Consider removing Pubkey::new_rand() entirely in the v1.5 or v1.6 timeframe
-        Pubkey::from(rand::random::<[u8; 32]>())
+        Pubkey::from(crate::entropy::random_bytes::<32>())
     }
 
     /// unique Pubkey for tests and benchmarks.
@@ -540,6 +562,54 @@ impl Pubkey {
         }
     }
 
+    /// Create a [program derived address][pda] from a known bump seed, or find
+    /// one if that bump seed does not produce a valid address.
+    ///
+    /// [pda]: https://solana.com/docs/core/cpi#program-derived-addresses
+    ///
+    /// Clients that already know the bump seed, for example because it was
+    /// stored in account data, can derive the address with a single hash
+    /// instead of repeating the search. If `bump_seed` does not produce a valid
+    /// address this falls back to [`find_program_address`].
+    ///
+    /// The result is only canonical if `bump_seed` is. A valid address from a
+    /// lower bump seed is returned as is, even though [`find_program_address`]
+    /// would have found a different one, so this must not be used to check
+    /// that an address is the canonical one. For that reason it is not
+    /// available on-chain.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`find_program_address`].
+    ///
+    /// [`find_program_address`]: Pubkey::find_program_address
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let program_id = Pubkey::new_unique();
+    /// let (pda, bump_seed) = Pubkey::find_program_address(&[b"vault"], &program_id);
+    /// assert_eq!(
+    ///     Pubkey::create_program_address_or_find(&[b"vault"], bump_seed, &program_id),
+    ///     (pda, bump_seed),
+    /// );
+    /// ```
+    #[cfg(not(target_os = "solana"))]
+    pub fn create_program_address_or_find(
+        seeds: &[&[u8]],
+        bump_seed: u8,
+        program_id: &Pubkey,
+    ) -> (Pubkey, u8) {
+        let bump_seed_slice = [bump_seed];
+        let mut seeds_with_bump = seeds.to_vec();
+        seeds_with_bump.push(&bump_seed_slice);
+        if let Ok(address) = Self::create_program_address(&seeds_with_bump, program_id) {
+            return (address, bump_seed);
+        }
+        Self::find_program_address(seeds, program_id)
+    }
+
     /// Create a valid [program derived address][pda] without searching for a bump seed.
     ///
     /// [pda]: https://solana.com/docs/core/cpi#program-derived-addresses
@@ -635,6 +705,48 @@ impl Pubkey {
         self.0
     }
 
+    /// Compare with `other` in constant time, for keys compared against
+    /// secret-derived values. See [`Hash::ct_eq`](crate::hash::Hash::ct_eq).
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        subtle::ConstantTimeEq::ct_eq(&self.0[..], &other.0[..]).into()
+    }
+
+    /// Write the base58 encoding of this key to `f`, without allocating.
+    ///
+    /// This is what the `Display` implementation uses; call it directly to
+    /// avoid building an intermediate `String` when writing into an existing
+    /// buffer.
+    pub fn write_as_base58(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let mut buf = [0; base58::MAX_ENCODED_LEN];
+        f.write_str(base58::encode_32(&self.0, &mut buf))
+    }
+
+    /// Write an abbreviated base58 encoding of this key to `f`, keeping the
+    /// first and last four characters, e.g. `4Nd1…Wxyz`.
+    ///
+    /// This is what the alternate `Display` format (`{:#}`) uses.
+    pub fn write_as_short_base58(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let mut buf = [0; base58::MAX_ENCODED_LEN];
+        let encoded = base58::encode_32(&self.0, &mut buf);
+        // a 32 byte key always encodes to at least 32 ASCII characters
+        let tail_start = encoded.len().saturating_sub(SHORT_STRING_AFFIX_LEN);
+        f.write_str(&encoded[..SHORT_STRING_AFFIX_LEN])?;
+        f.write_char('…')?;
+        f.write_str(&encoded[tail_start..])
+    }
+
+    /// Return the abbreviated base58 encoding of this key, for display in
+    /// UIs and logs. See [`Pubkey::write_as_short_base58`].
+    pub fn to_short_string(&self) -> String {
+        let mut s = String::new();
+        // writing to a `String` can't fail
+        let _ = self.write_as_short_base58(&mut s);
+        s
+    }
+
+    /// Returns `true` if this key is a point on the ed25519 curve.
+    ///
+    /// Program derived addresses are never on the curve.
     pub fn is_on_curve(&self) -> bool {
         bytes_are_curve_point(self)
     }
@@ -665,16 +777,23 @@ impl AsMut<[u8]> for Pubkey {
 
 impl fmt::Debug for Pubkey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", bs58::encode(self.0).into_string())
+        self.write_as_base58(f)
     }
 }
 
+/// The alternate format, `{:#}`, writes the abbreviated form produced by
+/// [`Pubkey::to_short_string`].
 impl fmt::Display for Pubkey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", bs58::encode(self.0).into_string())
+        if f.alternate() {
+            self.write_as_short_base58(f)
+        } else {
+            self.write_as_base58(f)
+        }
     }
 }
 
+#[cfg(feature = "borsh010")]
 impl borsh0_10::de::BorshDeserialize for Pubkey {
     fn deserialize_reader<R: borsh0_10::maybestd::io::Read>(
         reader: &mut R,
@@ -684,6 +803,7 @@ impl borsh0_10::de::BorshDeserialize for Pubkey {
         )?))
     }
 }
+#[cfg(feature = "borsh09")]
 impl borsh0_9::de::BorshDeserialize for Pubkey {
     fn deserialize(buf: &mut &[u8]) -> ::core::result::Result<Self, borsh0_9::maybestd::io::Error> {
         Ok(Self(borsh0_9::BorshDeserialize::deserialize(buf)?))
@@ -721,7 +841,9 @@ macro_rules! impl_borsh_schema {
         }
     };
 }
+#[cfg(feature = "borsh010")]
 impl_borsh_schema!(borsh0_10);
+#[cfg(feature = "borsh09")]
 impl_borsh_schema!(borsh0_9);
 
 macro_rules! impl_borsh_serialize {
@@ -737,7 +859,9 @@ macro_rules! impl_borsh_serialize {
         }
     };
 }
+#[cfg(feature = "borsh010")]
 impl_borsh_serialize!(borsh0_10);
+#[cfg(feature = "borsh09")]
 impl_borsh_serialize!(borsh0_9);
 
 #[cfg(test)]
@@ -749,6 +873,25 @@ mod tests {
         assert!(Pubkey::new_unique() != Pubkey::new_unique());
     }
 
+    #[test]
+    fn test_ct_eq() {
+        let pubkey = Pubkey::new_unique();
+        assert!(pubkey.ct_eq(&Pubkey::from(pubkey.to_bytes())));
+        assert!(!pubkey.ct_eq(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_short_string() {
+        let pubkey = Pubkey::from_str("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T").unwrap();
+        assert_eq!(pubkey.to_short_string(), "4Nd1…DB4T");
+        assert_eq!(format!("{pubkey:#}"), "4Nd1…DB4T");
+        assert_eq!(
+            format!("{pubkey}"),
+            "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T"
+        );
+        assert_eq!(Pubkey::default().to_short_string(), "1111…1111");
+    }
+
     #[test]
     fn pubkey_fromstr() {
         let pubkey = Pubkey::new_unique();
@@ -789,6 +932,16 @@ mod tests {
         assert_eq!(too_long.parse::<Pubkey>(), Err(ParsePubkeyError::WrongSize));
     }
 
+    #[test]
+    fn test_write_as_base58() {
+        let pubkey = Pubkey::new_unique();
+        let mut s = String::from("key: ");
+        pubkey.write_as_base58(&mut s).unwrap();
+        assert_eq!(s, format!("key: {}", bs58::encode(pubkey.0).into_string()));
+        assert_eq!(pubkey.to_string(), bs58::encode(pubkey.0).into_string());
+        assert_eq!(format!("{pubkey:?}"), pubkey.to_string());
+    }
+
     #[test]
     fn test_create_with_seed() {
         assert!(
@@ -957,6 +1110,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bytes_are_curve_point() {
+        let basepoint = curve25519_dalek::constants::ED25519_BASEPOINT_COMPRESSED.to_bytes();
+        assert!(bytes_are_curve_point(basepoint));
+        assert!(Pubkey::from(basepoint).is_on_curve());
+
+        let (address, _) = Pubkey::find_program_address(&[b"curve"], &Pubkey::new_unique());
+        assert!(!address.is_on_curve());
+
+        // wrong lengths are rejected rather than panicking
+        assert!(!bytes_are_curve_point([]));
+        assert!(!bytes_are_curve_point(&basepoint[..31]));
+        assert!(!bytes_are_curve_point(
+            [basepoint.as_slice(), &[0]].concat()
+        ));
+    }
+
     #[test]
     fn test_find_program_address() {
         for _ in 0..1_000 {
@@ -971,6 +1141,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_create_program_address_or_find() {
+        let program_id = Pubkey::new_unique();
+        let seeds: &[&[u8]] = &[b"Lil'", b"Bits"];
+        let canonical = Pubkey::find_program_address(seeds, &program_id);
+        assert_eq!(
+            Pubkey::create_program_address_or_find(seeds, canonical.1, &program_id),
+            canonical
+        );
+
+        let derive =
+            |bump: u8| Pubkey::create_program_address(&[seeds[0], seeds[1], &[bump]], &program_id);
+
+        // An on-curve bump seed falls back to the search
+        let invalid_bump = (0..=u8::MAX)
+            .find(|bump| derive(*bump) == Err(PubkeyError::InvalidSeeds))
+            .unwrap();
+        assert_eq!(
+            Pubkey::create_program_address_or_find(seeds, invalid_bump, &program_id),
+            canonical
+        );
+
+        // A valid but lower bump seed is used as given
+        let lower_bump = (0..canonical.1).rev().find(|bump| derive(*bump).is_ok());
+        if let Some(lower_bump) = lower_bump {
+            assert_eq!(
+                Pubkey::create_program_address_or_find(seeds, lower_bump, &program_id),
+                (derive(lower_bump).unwrap(), lower_bump)
+            );
+        }
+    }
+
     fn pubkey_from_seed_by_marker(marker: &[u8]) -> Result<Pubkey, PubkeyError> {
         let key = Pubkey::new_unique();
         let owner = Pubkey::default();
@@ -97,6 +97,25 @@ impl Rent {
         (lamports_per_year as f64 * years_elapsed) as u64
     }
 
+    /// Balance left on an account after rent is collected `collections` times,
+    /// `years_per_collection` years apart.
+    ///
+    /// Rent-exempt accounts are never charged. Accounts that are not exempt
+    /// only lose lamports, so they stay non-exempt and are charged the same
+    /// amount each time until their balance is exhausted.
+    pub fn balance_after_collections(
+        &self,
+        balance: u64,
+        data_len: usize,
+        years_per_collection: f64,
+        collections: u64,
+    ) -> u64 {
+        match self.due(balance, data_len, years_per_collection) {
+            RentDue::Exempt => balance,
+            RentDue::Paying(due) => balance.saturating_sub(due.saturating_mul(collections)),
+        }
+    }
+
     /// Creates a `Rent` that charges no lamports.
     ///
     /// This is used for testing.
@@ -199,6 +218,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_balance_after_collections() {
+        let rent = Rent::default();
+        let data_len = 100;
+        let exempt_balance = rent.minimum_balance(data_len);
+        assert_eq!(
+            rent.balance_after_collections(exempt_balance, data_len, 1.0, 1_000),
+            exempt_balance
+        );
+
+        let balance = exempt_balance - 1;
+        let due = rent.due_amount(data_len, 0.5);
+        assert_eq!(
+            rent.balance_after_collections(balance, data_len, 0.5, 0),
+            balance
+        );
+        assert_eq!(
+            rent.balance_after_collections(balance, data_len, 0.5, 3),
+            balance - 3 * due
+        );
+        assert_eq!(
+            rent.balance_after_collections(balance, data_len, 0.5, u64::MAX),
+            0
+        );
+    }
+
     #[test]
     fn test_rent_due_lamports() {
         assert_eq!(RentDue::Exempt.lamports(), 0);
@@ -63,6 +63,11 @@ impl From<Secp256k1RecoverError> for u64 {
 
 pub const SECP256K1_SIGNATURE_LENGTH: usize = 64;
 pub const SECP256K1_PUBLIC_KEY_LENGTH: usize = 64;
+/// Length of an Ethereum address, the last 20 bytes of the [`keccak`] hash of a
+/// public key.
+///
+/// [`keccak`]: crate::keccak
+pub const ETH_ADDRESS_LENGTH: usize = 20;
 
 #[repr(transparent)]
 #[derive(
@@ -92,6 +97,20 @@ impl Secp256k1Pubkey {
     pub fn to_bytes(self) -> [u8; 64] {
         self.0
     }
+
+    /// Derive the Ethereum address of this public key.
+    ///
+    /// This is the last [`ETH_ADDRESS_LENGTH`] bytes of the [`keccak`] hash of
+    /// the uncompressed public key, and can be compared against the address
+    /// an Ethereum signature is expected to recover to.
+    ///
+    /// [`keccak`]: crate::keccak
+    pub fn to_eth_address(&self) -> [u8; ETH_ADDRESS_LENGTH] {
+        let hash = crate::keccak::hash(&self.0).to_bytes();
+        let mut address = [0u8; ETH_ADDRESS_LENGTH];
+        address.copy_from_slice(&hash[hash.len().saturating_sub(ETH_ADDRESS_LENGTH)..]);
+        address
+    }
 }
 
 /// Recover the public key from a [secp256k1] ECDSA signature and
@@ -435,3 +454,33 @@ pub fn secp256k1_recover(
         Ok(Secp256k1Pubkey::new(&secp256k1_key.serialize()[1..65]))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_eth_address() {
+        // The well-known address of the secret key `1`
+        let mut secret_key_bytes = [0u8; 32];
+        secret_key_bytes[31] = 1;
+        let secret_key = libsecp256k1::SecretKey::parse(&secret_key_bytes).unwrap();
+        let public_key = libsecp256k1::PublicKey::from_secret_key(&secret_key);
+        let pubkey = Secp256k1Pubkey::new(&public_key.serialize()[1..]);
+        assert_eq!(
+            pubkey.to_eth_address(),
+            [
+                0x7e, 0x5f, 0x45, 0x52, 0x09, 0x1a, 0x69, 0x12, 0x5d, 0x5d, 0xfc, 0xb7, 0xb8, 0xc2,
+                0x65, 0x90, 0x29, 0x39, 0x5b, 0xdf,
+            ]
+        );
+
+        // Recovering a signature yields the signer's address
+        let hash = crate::keccak::hash(b"hello").to_bytes();
+        let message = libsecp256k1::Message::parse(&hash);
+        let (signature, recovery_id) = libsecp256k1::sign(&message, &secret_key);
+        let recovered =
+            secp256k1_recover(&hash, recovery_id.serialize(), &signature.serialize()).unwrap();
+        assert_eq!(recovered.to_eth_address(), pubkey.to_eth_address());
+    }
+}
@@ -0,0 +1,291 @@
+//! The [`Signature`] type, an ed25519 signature over a message.
+//!
+//! `solana_sdk::signature` re-exports this type, so crates that only depend on
+//! `solana-program`, such as light clients, can parse and check transaction
+//! signatures. Verification requires the `ed25519` feature, which builds
+//! `ed25519-dalek` without its `std` feature.
+
+use {
+    crate::sanitize::Sanitize,
+    serde::{
+        de::{self, SeqAccess, Visitor},
+        ser::SerializeTuple,
+        Deserialize, Deserializer, Serialize, Serializer,
+    },
+    std::{fmt, str::FromStr},
+    thiserror::Error,
+};
+
+/// Number of bytes in a signature
+pub const SIGNATURE_BYTES: usize = 64;
+/// Maximum string length of a base58 encoded signature
+const MAX_BASE58_SIGNATURE_LEN: usize = 88;
+
+#[repr(transparent)]
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, AbiExample)]
+pub struct Signature([u8; SIGNATURE_BYTES]);
+
+impl Sanitize for Signature {}
+
+impl Default for Signature {
+    fn default() -> Self {
+        Self([0; SIGNATURE_BYTES])
+    }
+}
+
+impl Signature {
+    #[deprecated(
+        since = "1.16.4",
+        note = "Please use 'Signature::from' or 'Signature::try_from' instead"
+    )]
+    pub fn new(signature_slice: &[u8]) -> Self {
+        Self::try_from(signature_slice).expect("signature slice is 64 bytes")
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    pub fn new_unique() -> Self {
+        Self(std::array::from_fn(|_| rand::random()))
+    }
+
+    #[cfg(feature = "ed25519")]
+    fn verify_verbose(
+        &self,
+        pubkey_bytes: &[u8],
+        message_bytes: &[u8],
+    ) -> Result<(), ed25519_dalek::SignatureError> {
+        let publickey = ed25519_dalek::PublicKey::from_bytes(pubkey_bytes)?;
+        let signature = self.0.as_slice().try_into()?;
+        publickey.verify_strict(message_bytes, &signature)
+    }
+
+    /// Verify this signature of `message_bytes` against the ed25519 public key
+    /// `pubkey_bytes`, rejecting non-canonical and small-order inputs.
+    #[cfg(feature = "ed25519")]
+    pub fn verify(&self, pubkey_bytes: &[u8], message_bytes: &[u8]) -> bool {
+        self.verify_verbose(pubkey_bytes, message_bytes).is_ok()
+    }
+
+    /// Compare with `other` in constant time, for signatures compared
+    /// against secret-derived values. See [`Hash::ct_eq`](crate::hash::Hash::ct_eq).
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        subtle::ConstantTimeEq::ct_eq(&self.0[..], &other.0[..]).into()
+    }
+}
+
+impl AsRef<[u8]> for Signature {
+    fn as_ref(&self) -> &[u8] {
+        &self.0[..]
+    }
+}
+
+impl fmt::Debug for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", bs58::encode(self.0).into_string())
+    }
+}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", bs58::encode(self.0).into_string())
+    }
+}
+
+impl From<Signature> for [u8; SIGNATURE_BYTES] {
+    fn from(signature: Signature) -> Self {
+        signature.0
+    }
+}
+
+impl From<[u8; SIGNATURE_BYTES]> for Signature {
+    #[inline]
+    fn from(signature: [u8; SIGNATURE_BYTES]) -> Self {
+        Self(signature)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for Signature {
+    type Error = <[u8; SIGNATURE_BYTES] as TryFrom<&'a [u8]>>::Error;
+
+    #[inline]
+    fn try_from(signature: &'a [u8]) -> Result<Self, Self::Error> {
+        <[u8; SIGNATURE_BYTES]>::try_from(signature).map(Self::from)
+    }
+}
+
+impl TryFrom<Vec<u8>> for Signature {
+    type Error = <[u8; SIGNATURE_BYTES] as TryFrom<Vec<u8>>>::Error;
+
+    #[inline]
+    fn try_from(signature: Vec<u8>) -> Result<Self, Self::Error> {
+        <[u8; SIGNATURE_BYTES]>::try_from(signature).map(Self::from)
+    }
+}
+
+// serde only derives for arrays of up to 32 elements, so serialize the bytes
+// as a fixed-size tuple inside a newtype struct, the layout `Signature` had
+// when it wrapped a `GenericArray` in the sdk.
+#[derive(AbiExample)]
+struct SignatureBytes([u8; SIGNATURE_BYTES]);
+
+impl Serialize for SignatureBytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(SIGNATURE_BYTES)?;
+        for byte in &self.0 {
+            tuple.serialize_element(byte)?;
+        }
+        tuple.end()
+    }
+}
+
+impl Serialize for Signature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct("Signature", &SignatureBytes(self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SignatureVisitor;
+
+        impl<'de> Visitor<'de> for SignatureVisitor {
+            type Value = Signature;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "an array of {SIGNATURE_BYTES} bytes")
+            }
+
+            fn visit_newtype_struct<D: Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Signature, D::Error> {
+                deserializer.deserialize_tuple(SIGNATURE_BYTES, self)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Signature, A::Error> {
+                let mut bytes = [0u8; SIGNATURE_BYTES];
+                for (i, byte) in bytes.iter_mut().enumerate() {
+                    *byte = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                }
+                Ok(Signature(bytes))
+            }
+        }
+
+        deserializer.deserialize_newtype_struct("Signature", SignatureVisitor)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseSignatureError {
+    #[error("string decoded to wrong size for signature")]
+    WrongSize,
+    #[error("failed to decode string to signature")]
+    Invalid,
+}
+
+impl FromStr for Signature {
+    type Err = ParseSignatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() > MAX_BASE58_SIGNATURE_LEN {
+            return Err(ParseSignatureError::WrongSize);
+        }
+        let bytes = bs58::decode(s)
+            .into_vec()
+            .map_err(|_| ParseSignatureError::Invalid)?;
+        Signature::try_from(bytes).map_err(|_| ParseSignatureError::WrongSize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_fromstr() {
+        let signature = Signature::new_unique();
+
+        let mut signature_base58_str = bs58::encode(signature).into_string();
+        assert_eq!(signature_base58_str.parse::<Signature>(), Ok(signature));
+
+        signature_base58_str.push_str(&bs58::encode(signature.0).into_string());
+        assert_eq!(
+            signature_base58_str.parse::<Signature>(),
+            Err(ParseSignatureError::WrongSize)
+        );
+
+        let mut signature_base58_str = signature.to_string();
+        signature_base58_str.replace_range(..1, "I");
+        assert_eq!(
+            signature_base58_str.parse::<Signature>(),
+            Err(ParseSignatureError::Invalid)
+        );
+
+        let mut too_long = bs58::encode(&[255u8; SIGNATURE_BYTES]).into_string();
+        too_long.push('1');
+        assert_eq!(
+            too_long.parse::<Signature>(),
+            Err(ParseSignatureError::WrongSize)
+        );
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        let signature = Signature::new_unique();
+        assert!(signature.ct_eq(&Signature::from(<[u8; SIGNATURE_BYTES]>::from(signature))));
+        assert!(!signature.ct_eq(&Signature::new_unique()));
+    }
+
+    #[test]
+    fn test_signature_serialization() {
+        let signature = Signature::new_unique();
+        let bytes = bincode::serialize(&signature).unwrap();
+        assert_eq!(bytes, signature.as_ref());
+        assert_eq!(
+            bincode::deserialize::<Signature>(&bytes).unwrap(),
+            signature
+        );
+        assert!(bincode::deserialize::<Signature>(&bytes[..SIGNATURE_BYTES - 1]).is_err());
+
+        let json = serde_json::to_string(&signature).unwrap();
+        assert_eq!(serde_json::from_str::<Signature>(&json).unwrap(), signature);
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_off_curve_pubkey_verify_fails() {
+        // Golden point off the ed25519 curve
+        let off_curve_bytes = bs58::decode("9z5nJyQar1FUxVJxpBXzon6kHehbomeYiDaLi9WAMhCq")
+            .into_vec()
+            .unwrap();
+        assert!(!crate::pubkey::bytes_are_curve_point(&off_curve_bytes));
+
+        let signature = Signature::default();
+        assert!(signature.verify_verbose(&off_curve_bytes, &[0u8]).is_err());
+        assert!(!signature.verify(&off_curve_bytes, &[0u8]));
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_signature_verify() {
+        use ed25519_dalek::Signer;
+
+        let keypair = ed25519_dalek::Keypair::from_bytes(&[
+            // secret key
+            0x9d, 0x61, 0xb1, 0x9d, 0xef, 0xfd, 0x5a, 0x60, 0xba, 0x84, 0x4a, 0xf4, 0x92, 0xec,
+            0x2c, 0xc4, 0x44, 0x49, 0xc5, 0x69, 0x7b, 0x32, 0x69, 0x19, 0x70, 0x3b, 0xac, 0x03,
+            0x1c, 0xae, 0x7f, 0x60, // public key
+            0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9, 0x64,
+            0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02, 0x1a, 0x68,
+            0xf7, 0x07, 0x51, 0x1a,
+        ])
+        .unwrap();
+        let message = b"light client";
+        let signature = Signature::from(keypair.sign(message).to_bytes());
+
+        assert!(signature.verify(keypair.public.as_bytes(), message));
+        assert!(!signature.verify(keypair.public.as_bytes(), b"other message"));
+        assert!(!Signature::default().verify(keypair.public.as_bytes(), message));
+    }
+}
@@ -7,4 +7,5 @@ pub mod stable_instruction;
 pub mod stable_rc;
 pub mod stable_ref_cell;
 pub mod stable_slice;
+pub mod stable_state;
 pub mod stable_vec;
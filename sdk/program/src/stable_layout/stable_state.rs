@@ -0,0 +1,345 @@
+//! Account state, with stable memory layouts
+//!
+//! The stake state types are laid out however rustc sees fit. Embedders that
+//! read stake accounts from memory across an FFI boundary can convert them to
+//! and from the mirrors here, whose layouts are fixed and checked at compile
+//! time. They are not the account data encodings, which remain bincode. The
+//! `Clock` and `Rent` sysvars are already `#[repr(C)]` and need no mirror.
+
+#![cfg(feature = "stable-layout")]
+
+use crate::{
+    clock::{Epoch, UnixTimestamp},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    stake::{
+        stake_flags::StakeFlags,
+        state::{Authorized, Delegation, Lockup, Meta, Stake, StakeStateV2},
+    },
+};
+
+/// `Authorized`, with a stable memory layout
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StableAuthorized {
+    pub staker: Pubkey,
+    pub withdrawer: Pubkey,
+}
+
+/// `Lockup`, with a stable memory layout
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StableLockup {
+    pub unix_timestamp: UnixTimestamp,
+    pub epoch: Epoch,
+    pub custodian: Pubkey,
+}
+
+/// `Meta`, with a stable memory layout
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StableMeta {
+    pub rent_exempt_reserve: u64,
+    pub authorized: StableAuthorized,
+    pub lockup: StableLockup,
+}
+
+/// `Delegation`, with a stable memory layout
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct StableDelegation {
+    pub voter_pubkey: Pubkey,
+    pub stake: u64,
+    pub activation_epoch: Epoch,
+    pub deactivation_epoch: Epoch,
+    pub warmup_cooldown_rate: f64,
+}
+
+/// `Stake`, with a stable memory layout
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct StableStake {
+    pub delegation: StableDelegation,
+    pub credits_observed: u64,
+}
+
+/// `StakeStateV2`, with a stable memory layout
+///
+/// A `u32` tag with the same values as the bincode encoding, followed at
+/// offset 8 by the fields of the variant. Fields the variant does not have,
+/// and all padding, are zero. Memory from outside may hold any tag, so it is
+/// converted back with `TryFrom`.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct StableStakeState {
+    pub tag: u32,
+    pub _padding: [u8; 4],
+    pub payload: StableStakeStatePayload,
+}
+
+impl StableStakeState {
+    pub const UNINITIALIZED: u32 = 0;
+    pub const INITIALIZED: u32 = 1;
+    pub const STAKE: u32 = 2;
+    pub const REWARDS_POOL: u32 = 3;
+}
+
+/// The fields of the variants of `StakeStateV2`, with a stable memory layout
+///
+/// `stake_flags` holds the `StakeFlags` bits.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct StableStakeStatePayload {
+    pub meta: StableMeta,
+    pub stake: StableStake,
+    pub stake_flags: u8,
+    pub _padding: [u8; 7],
+}
+
+macro_rules! assert_layout {
+    ($type:ty, size = $size:expr, align = $align:expr) => {
+        const _: () = assert!(
+            std::mem::size_of::<$type>() == $size && std::mem::align_of::<$type>() == $align
+        );
+    };
+}
+
+assert_layout!(StableAuthorized, size = 64, align = 1);
+assert_layout!(StableLockup, size = 48, align = 8);
+assert_layout!(StableMeta, size = 120, align = 8);
+assert_layout!(StableDelegation, size = 64, align = 8);
+assert_layout!(StableStake, size = 72, align = 8);
+assert_layout!(StableStakeStatePayload, size = 200, align = 8);
+assert_layout!(StableStakeState, size = 208, align = 8);
+
+impl From<Authorized> for StableAuthorized {
+    fn from(other: Authorized) -> Self {
+        Self {
+            staker: other.staker,
+            withdrawer: other.withdrawer,
+        }
+    }
+}
+
+impl From<StableAuthorized> for Authorized {
+    fn from(other: StableAuthorized) -> Self {
+        Self {
+            staker: other.staker,
+            withdrawer: other.withdrawer,
+        }
+    }
+}
+
+impl From<Lockup> for StableLockup {
+    fn from(other: Lockup) -> Self {
+        Self {
+            unix_timestamp: other.unix_timestamp,
+            epoch: other.epoch,
+            custodian: other.custodian,
+        }
+    }
+}
+
+impl From<StableLockup> for Lockup {
+    fn from(other: StableLockup) -> Self {
+        Self {
+            unix_timestamp: other.unix_timestamp,
+            epoch: other.epoch,
+            custodian: other.custodian,
+        }
+    }
+}
+
+impl From<Meta> for StableMeta {
+    fn from(other: Meta) -> Self {
+        Self {
+            rent_exempt_reserve: other.rent_exempt_reserve,
+            authorized: other.authorized.into(),
+            lockup: other.lockup.into(),
+        }
+    }
+}
+
+impl From<StableMeta> for Meta {
+    fn from(other: StableMeta) -> Self {
+        Self {
+            rent_exempt_reserve: other.rent_exempt_reserve,
+            authorized: other.authorized.into(),
+            lockup: other.lockup.into(),
+        }
+    }
+}
+
+impl From<Delegation> for StableDelegation {
+    fn from(other: Delegation) -> Self {
+        #[allow(deprecated)]
+        Self {
+            voter_pubkey: other.voter_pubkey,
+            stake: other.stake,
+            activation_epoch: other.activation_epoch,
+            deactivation_epoch: other.deactivation_epoch,
+            warmup_cooldown_rate: other.warmup_cooldown_rate,
+        }
+    }
+}
+
+impl From<StableDelegation> for Delegation {
+    fn from(other: StableDelegation) -> Self {
+        #[allow(deprecated)]
+        Self {
+            voter_pubkey: other.voter_pubkey,
+            stake: other.stake,
+            activation_epoch: other.activation_epoch,
+            deactivation_epoch: other.deactivation_epoch,
+            warmup_cooldown_rate: other.warmup_cooldown_rate,
+        }
+    }
+}
+
+impl From<Stake> for StableStake {
+    fn from(other: Stake) -> Self {
+        Self {
+            delegation: other.delegation.into(),
+            credits_observed: other.credits_observed,
+        }
+    }
+}
+
+impl From<StableStake> for Stake {
+    fn from(other: StableStake) -> Self {
+        Self {
+            delegation: other.delegation.into(),
+            credits_observed: other.credits_observed,
+        }
+    }
+}
+
+impl From<StakeStateV2> for StableStakeState {
+    fn from(other: StakeStateV2) -> Self {
+        let (tag, payload) = match other {
+            StakeStateV2::Uninitialized => {
+                (Self::UNINITIALIZED, StableStakeStatePayload::default())
+            }
+            StakeStateV2::Initialized(meta) => (
+                Self::INITIALIZED,
+                StableStakeStatePayload {
+                    meta: meta.into(),
+                    ..StableStakeStatePayload::default()
+                },
+            ),
+            StakeStateV2::Stake(meta, stake, stake_flags) => (
+                Self::STAKE,
+                StableStakeStatePayload {
+                    meta: meta.into(),
+                    stake: stake.into(),
+                    stake_flags: stake_flags.bits(),
+                    _padding: [0; 7],
+                },
+            ),
+            StakeStateV2::RewardsPool => (Self::REWARDS_POOL, StableStakeStatePayload::default()),
+        };
+        Self {
+            tag,
+            _padding: [0; 4],
+            payload,
+        }
+    }
+}
+
+impl TryFrom<StableStakeState> for StakeStateV2 {
+    type Error = ProgramError;
+
+    fn try_from(other: StableStakeState) -> Result<Self, Self::Error> {
+        let payload = other.payload;
+        match other.tag {
+            StableStakeState::UNINITIALIZED => Ok(Self::Uninitialized),
+            StableStakeState::INITIALIZED => Ok(Self::Initialized(payload.meta.into())),
+            StableStakeState::STAKE => Ok(Self::Stake(
+                payload.meta.into(),
+                payload.stake.into(),
+                StakeFlags::from_bits_retain(payload.stake_flags),
+            )),
+            StableStakeState::REWARDS_POOL => Ok(Self::RewardsPool),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, memoffset::offset_of};
+
+    #[test]
+    fn test_memory_layout() {
+        assert_eq!(offset_of!(StableLockup, custodian), 16);
+        assert_eq!(offset_of!(StableMeta, authorized), 8);
+        assert_eq!(offset_of!(StableMeta, lockup), 72);
+        assert_eq!(offset_of!(StableDelegation, warmup_cooldown_rate), 56);
+        assert_eq!(offset_of!(StableStake, credits_observed), 64);
+        assert_eq!(offset_of!(StableStakeState, payload), 8);
+        assert_eq!(offset_of!(StableStakeStatePayload, stake_flags), 192);
+
+        let meta = StableMeta {
+            rent_exempt_reserve: 1,
+            ..StableMeta::default()
+        };
+        let state = StableStakeState {
+            tag: StableStakeState::STAKE,
+            _padding: [0; 4],
+            payload: StableStakeStatePayload {
+                meta,
+                stake: StableStake::default(),
+                stake_flags: 0xa5,
+                _padding: [0; 7],
+            },
+        };
+        let bytes: &[u8; 208] = unsafe { &*(&state as *const _ as *const [u8; 208]) };
+        assert_eq!(bytes[..4], 2u32.to_ne_bytes());
+        assert_eq!(bytes[8..16], 1u64.to_ne_bytes());
+        assert_eq!(bytes[8 + 120 + 72], 0xa5);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let meta = Meta {
+            rent_exempt_reserve: 1,
+            authorized: Authorized {
+                staker: Pubkey::new_unique(),
+                withdrawer: Pubkey::new_unique(),
+            },
+            lockup: Lockup {
+                unix_timestamp: -2,
+                epoch: 3,
+                custodian: Pubkey::new_unique(),
+            },
+        };
+        let stake = Stake {
+            delegation: Delegation::new(&Pubkey::new_unique(), 4, 5),
+            credits_observed: 6,
+        };
+        for state in [
+            StakeStateV2::Uninitialized,
+            StakeStateV2::Initialized(meta),
+            StakeStateV2::Stake(
+                meta,
+                stake,
+                StakeFlags::MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED,
+            ),
+            StakeStateV2::RewardsPool,
+        ] {
+            assert_eq!(
+                StakeStateV2::try_from(StableStakeState::from(state)),
+                Ok(state)
+            );
+        }
+
+        let state = StableStakeState {
+            tag: 4,
+            ..StableStakeState::default()
+        };
+        assert_eq!(
+            StakeStateV2::try_from(state),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+}
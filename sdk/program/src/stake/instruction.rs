@@ -1,16 +1,22 @@
 #[allow(deprecated)]
 use crate::stake::config;
+#[cfg(feature = "stake-borsh")]
+use borsh::{io, BorshDeserialize, BorshSchema, BorshSerialize};
 use {
     crate::{
-        clock::{Epoch, UnixTimestamp},
+        account_spec::AccountSpec,
+        clock::{Clock, Epoch, UnixTimestamp},
+        compute_units::BuiltinComputeUnits,
         decode_error::DecodeError,
-        instruction::{AccountMeta, Instruction},
+        instruction::{AccountMeta, Instruction, InstructionError},
         pubkey::Pubkey,
+        sanitize::{Sanitize, SanitizeError},
         stake::{
             program::id,
             state::{Authorized, Lockup, StakeAuthorize, StakeStateV2},
         },
-        system_instruction, sysvar,
+        stake_history::StakeHistory,
+        system_instruction, system_program, sysvar,
     },
     log::*,
     num_derive::{FromPrimitive, ToPrimitive},
@@ -79,6 +85,7 @@ impl<E> DecodeError<E> for StakeError {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(any(test, feature = "fuzz"), derive(arbitrary::Arbitrary))]
 pub enum StakeInstruction {
     /// Initialize a stake with lockup and authorization information
     ///
@@ -293,9 +300,443 @@ pub enum StakeInstruction {
     ///   4. `[SIGNER]` Stake authority
     ///
     Redelegate,
+
+    /// Initialize a stake with lockup and authorization information, unless
+    /// it is already initialized with the same information
+    ///
+    /// Unlike `Initialize`, this succeeds without changing anything when the
+    /// stake account has already been initialized, or since delegated, with
+    /// exactly the given `Authorized` and `Lockup`, so that transactions that
+    /// initialize a stake can be retried. Any other initialized stake fails as
+    /// with `Initialize`.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Stake account, uninitialized or already initialized
+    ///   1. `[]` Rent sysvar
+    InitializeIfNeeded(Authorized, Lockup),
+
+    /// Replace the lockup custodian, leaving the lockup schedule as it is
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Initialized stake account
+    ///   1. `[SIGNER]` Current lockup custodian
+    ///
+    /// The Pubkey parameter is the new custodian
+    UpdateCustodian(Pubkey),
+
+    /// Move lamports from a funding account into a rewards pool
+    ///
+    /// Fails unless the destination is a genuine rewards pool: at one of the
+    /// rewards pool ids and in the `RewardsPool` state. The lamports are moved
+    /// with a system program transfer, so the funding account must be owned by
+    /// the system program and sign.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Rewards pool account
+    ///   1. `[WRITE, SIGNER]` Funding account
+    ///   2. `[]` System program
+    ///
+    /// The u64 parameter is the amount of lamports to move
+    ReplenishRewardsPool(u64),
+}
+
+impl BuiltinComputeUnits for StakeInstruction {
+    const DEFAULT_COMPUTE_UNITS: u64 = 750;
+}
+
+impl StakeInstruction {
+    /// The positional account requirements the stake processor enforces for
+    /// this instruction: how many accounts it needs, which of them must be
+    /// particular sysvars, and which must sign.
+    ///
+    /// This is narrower than the `# Account references` of each instruction.
+    /// Authorities are matched against all of the instruction's signers
+    /// wherever they appear, and writability is only checked when an account
+    /// is written, so neither is part of the spec.
+    pub fn account_spec(&self) -> &'static [AccountSpec] {
+        const CLOCK: AccountSpec = AccountSpec::address(sysvar::clock::ID);
+        const RENT: AccountSpec = AccountSpec::address(sysvar::rent::ID);
+        const STAKE_HISTORY: AccountSpec = AccountSpec::address(sysvar::stake_history::ID);
+        const SYSTEM_PROGRAM: AccountSpec = AccountSpec::address(system_program::ID);
+        const ACCOUNT: AccountSpec = AccountSpec::readonly();
+        const SIGNER: AccountSpec = AccountSpec::signer();
+        const OPTIONAL_ACCOUNT: AccountSpec = AccountSpec::readonly().optional();
+        const OPTIONAL_SIGNER: AccountSpec = AccountSpec::signer().optional();
+
+        match self {
+            Self::Initialize(..) | Self::InitializeIfNeeded(..) => &[ACCOUNT, RENT],
+            Self::Authorize(..) => &[ACCOUNT, CLOCK, ACCOUNT],
+            Self::DelegateStake => &[ACCOUNT, ACCOUNT, CLOCK, STAKE_HISTORY, ACCOUNT],
+            Self::Split(_) => &[ACCOUNT, ACCOUNT],
+            Self::Withdraw(_) => &[ACCOUNT, ACCOUNT, CLOCK, STAKE_HISTORY, SIGNER],
+            Self::Deactivate => &[ACCOUNT, CLOCK],
+            Self::SetLockup(_) | Self::UpdateCustodian(_) => &[ACCOUNT],
+            Self::Merge => &[ACCOUNT, ACCOUNT, CLOCK, STAKE_HISTORY],
+            Self::AuthorizeWithSeed(_) => &[ACCOUNT, ACCOUNT, CLOCK],
+            Self::InitializeChecked => &[ACCOUNT, RENT, ACCOUNT, SIGNER],
+            Self::AuthorizeChecked(_) => &[ACCOUNT, CLOCK, ACCOUNT, SIGNER],
+            Self::AuthorizeCheckedWithSeed(_) => &[ACCOUNT, ACCOUNT, CLOCK, SIGNER],
+            // the custodian is optional, but must sign when present
+            Self::SetLockupChecked(_) => &[ACCOUNT, OPTIONAL_ACCOUNT, OPTIONAL_SIGNER],
+            Self::GetMinimumDelegation => &[],
+            Self::DeactivateDelinquent => &[ACCOUNT, ACCOUNT, ACCOUNT],
+            Self::Redelegate => &[ACCOUNT, ACCOUNT, ACCOUNT],
+            Self::ReplenishRewardsPool(_) => &[ACCOUNT, SIGNER, SYSTEM_PROGRAM],
+        }
+    }
+
+    /// Decode instruction data in the bincode layout produced by the
+    /// constructors in this module.
+    ///
+    /// This is equivalent to `bincode::deserialize::<StakeInstruction>`, but
+    /// reads the discriminant and fields directly instead of going through
+    /// serde. Like bincode, trailing bytes after the instruction are ignored.
+    pub fn decode(data: &[u8]) -> Result<Self, InstructionError> {
+        Self::read(&mut BincodeReader(data))
+    }
+
+    /// Like [`decode`](Self::decode), but rejecting data with trailing bytes
+    /// after the instruction, so that each instruction has exactly one valid
+    /// encoding.
+    ///
+    /// This is equivalent to deserializing with bincode's
+    /// `reject_trailing_bytes` option, as done by
+    /// [`deserialize_checked`](crate::program_utils::deserialize_checked).
+    pub fn decode_checked(data: &[u8]) -> Result<Self, InstructionError> {
+        let mut reader = BincodeReader(data);
+        let instruction = Self::read(&mut reader)?;
+        if !reader.0.is_empty() {
+            return Err(InstructionError::InvalidInstructionData);
+        }
+        Ok(instruction)
+    }
+
+    fn read(reader: &mut BincodeReader) -> Result<Self, InstructionError> {
+        let instruction = match reader.read_u32()? {
+            0 => StakeInstruction::Initialize(
+                Authorized {
+                    staker: reader.read_pubkey()?,
+                    withdrawer: reader.read_pubkey()?,
+                },
+                Lockup {
+                    unix_timestamp: reader.read_i64()?,
+                    epoch: reader.read_u64()?,
+                    custodian: reader.read_pubkey()?,
+                },
+            ),
+            1 => StakeInstruction::Authorize(reader.read_pubkey()?, reader.read_stake_authorize()?),
+            2 => StakeInstruction::DelegateStake,
+            3 => StakeInstruction::Split(reader.read_u64()?),
+            4 => StakeInstruction::Withdraw(reader.read_u64()?),
+            5 => StakeInstruction::Deactivate,
+            6 => StakeInstruction::SetLockup(LockupArgs {
+                unix_timestamp: reader.read_option(BincodeReader::read_i64)?,
+                epoch: reader.read_option(BincodeReader::read_u64)?,
+                custodian: reader.read_option(BincodeReader::read_pubkey)?,
+            }),
+            7 => StakeInstruction::Merge,
+            8 => StakeInstruction::AuthorizeWithSeed(AuthorizeWithSeedArgs {
+                new_authorized_pubkey: reader.read_pubkey()?,
+                stake_authorize: reader.read_stake_authorize()?,
+                authority_seed: reader.read_string()?,
+                authority_owner: reader.read_pubkey()?,
+            }),
+            9 => StakeInstruction::InitializeChecked,
+            10 => StakeInstruction::AuthorizeChecked(reader.read_stake_authorize()?),
+            11 => StakeInstruction::AuthorizeCheckedWithSeed(AuthorizeCheckedWithSeedArgs {
+                stake_authorize: reader.read_stake_authorize()?,
+                authority_seed: reader.read_string()?,
+                authority_owner: reader.read_pubkey()?,
+            }),
+            12 => StakeInstruction::SetLockupChecked(LockupCheckedArgs {
+                unix_timestamp: reader.read_option(BincodeReader::read_i64)?,
+                epoch: reader.read_option(BincodeReader::read_u64)?,
+            }),
+            13 => StakeInstruction::GetMinimumDelegation,
+            14 => StakeInstruction::DeactivateDelinquent,
+            15 => StakeInstruction::Redelegate,
+            16 => StakeInstruction::InitializeIfNeeded(
+                Authorized {
+                    staker: reader.read_pubkey()?,
+                    withdrawer: reader.read_pubkey()?,
+                },
+                Lockup {
+                    unix_timestamp: reader.read_i64()?,
+                    epoch: reader.read_u64()?,
+                    custodian: reader.read_pubkey()?,
+                },
+            ),
+            17 => StakeInstruction::UpdateCustodian(reader.read_pubkey()?),
+            18 => StakeInstruction::ReplenishRewardsPool(reader.read_u64()?),
+            _ => return Err(InstructionError::InvalidInstructionData),
+        };
+        Ok(instruction)
+    }
+}
+
+/// Bounds on instruction arguments that hold whatever the stake account's
+/// state, so the processor can reject them before reading any account.
+///
+/// Withdrawals and pool replenishments must move some lamports, new authorities can't be the default
+/// pubkey, which nobody can sign for, and lockup timestamps can't be before
+/// the Unix epoch. The default custodian means "no custodian" and is allowed.
+impl Sanitize for StakeInstruction {
+    fn sanitize(&self) -> Result<(), SanitizeError> {
+        match self {
+            StakeInstruction::Initialize(authorized, lockup)
+            | StakeInstruction::InitializeIfNeeded(authorized, lockup) => {
+                sanitize_authority(&authorized.staker)?;
+                sanitize_authority(&authorized.withdrawer)?;
+                sanitize_lockup_timestamp(Some(lockup.unix_timestamp))
+            }
+            StakeInstruction::Authorize(new_authority, _) => sanitize_authority(new_authority),
+            StakeInstruction::AuthorizeWithSeed(args) => {
+                sanitize_authority(&args.new_authorized_pubkey)
+            }
+            StakeInstruction::Withdraw(0) | StakeInstruction::ReplenishRewardsPool(0) => {
+                Err(SanitizeError::ValueOutOfBounds)
+            }
+            StakeInstruction::SetLockup(args) => sanitize_lockup_timestamp(args.unix_timestamp),
+            StakeInstruction::SetLockupChecked(args) => {
+                sanitize_lockup_timestamp(args.unix_timestamp)
+            }
+            StakeInstruction::DelegateStake
+            | StakeInstruction::Split(_)
+            | StakeInstruction::Withdraw(_)
+            | StakeInstruction::Deactivate
+            | StakeInstruction::Merge
+            | StakeInstruction::InitializeChecked
+            | StakeInstruction::AuthorizeChecked(_)
+            | StakeInstruction::AuthorizeCheckedWithSeed(_)
+            | StakeInstruction::GetMinimumDelegation
+            | StakeInstruction::DeactivateDelinquent
+            | StakeInstruction::Redelegate
+            | StakeInstruction::UpdateCustodian(_)
+            | StakeInstruction::ReplenishRewardsPool(_) => Ok(()),
+        }
+    }
+}
+
+fn sanitize_authority(authority: &Pubkey) -> Result<(), SanitizeError> {
+    if *authority == Pubkey::default() {
+        Err(SanitizeError::InvalidValue)
+    } else {
+        Ok(())
+    }
+}
+
+fn sanitize_lockup_timestamp(unix_timestamp: Option<UnixTimestamp>) -> Result<(), SanitizeError> {
+    match unix_timestamp {
+        Some(unix_timestamp) if unix_timestamp < 0 => Err(SanitizeError::ValueOutOfBounds),
+        _ => Ok(()),
+    }
+}
+
+/// Reads the fixed-width little-endian bincode encoding of primitive fields
+struct BincodeReader<'a>(&'a [u8]);
+
+impl<'a> BincodeReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], InstructionError> {
+        if len > self.0.len() {
+            return Err(InstructionError::InvalidInstructionData);
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], InstructionError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+        Ok(array)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, InstructionError> {
+        self.read_array().map(u8::from_le_bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, InstructionError> {
+        self.read_array().map(u32::from_le_bytes)
+    }
+
+    fn read_u64(&mut self) -> Result<u64, InstructionError> {
+        self.read_array().map(u64::from_le_bytes)
+    }
+
+    fn read_i64(&mut self) -> Result<i64, InstructionError> {
+        self.read_array().map(i64::from_le_bytes)
+    }
+
+    fn read_pubkey(&mut self) -> Result<Pubkey, InstructionError> {
+        self.read_array().map(Pubkey::from)
+    }
+
+    fn read_stake_authorize(&mut self) -> Result<StakeAuthorize, InstructionError> {
+        match self.read_u32()? {
+            0 => Ok(StakeAuthorize::Staker),
+            1 => Ok(StakeAuthorize::Withdrawer),
+            _ => Err(InstructionError::InvalidInstructionData),
+        }
+    }
+
+    fn read_option<T>(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> Result<T, InstructionError>,
+    ) -> Result<Option<T>, InstructionError> {
+        match self.read_u8()? {
+            0 => Ok(None),
+            1 => read(self).map(Some),
+            _ => Err(InstructionError::InvalidInstructionData),
+        }
+    }
+
+    fn read_string(&mut self) -> Result<String, InstructionError> {
+        let len = usize::try_from(self.read_u64()?)
+            .map_err(|_| InstructionError::InvalidInstructionData)?;
+        let bytes = self.read_bytes(len)?;
+        std::str::from_utf8(bytes)
+            .map(str::to_string)
+            .map_err(|_| InstructionError::InvalidInstructionData)
+    }
+}
+
+// Borsh encoding of `StakeInstruction` starts with the same little-endian `u32`
+// discriminant as the bincode instruction data, like `StakeStateV2`. The fields
+// that follow use borsh's own layout, so the encodings still differ: a
+// `StakeAuthorize` takes one byte rather than four, and a string length four
+// bytes rather than eight.
+#[cfg(feature = "stake-borsh")]
+impl BorshDeserialize for StakeInstruction {
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let enum_value: u32 = BorshDeserialize::deserialize_reader(reader)?;
+        match enum_value {
+            0 => Ok(StakeInstruction::Initialize(
+                BorshDeserialize::deserialize_reader(reader)?,
+                BorshDeserialize::deserialize_reader(reader)?,
+            )),
+            1 => Ok(StakeInstruction::Authorize(
+                BorshDeserialize::deserialize_reader(reader)?,
+                BorshDeserialize::deserialize_reader(reader)?,
+            )),
+            2 => Ok(StakeInstruction::DelegateStake),
+            3 => Ok(StakeInstruction::Split(
+                BorshDeserialize::deserialize_reader(reader)?,
+            )),
+            4 => Ok(StakeInstruction::Withdraw(
+                BorshDeserialize::deserialize_reader(reader)?,
+            )),
+            5 => Ok(StakeInstruction::Deactivate),
+            6 => Ok(StakeInstruction::SetLockup(
+                BorshDeserialize::deserialize_reader(reader)?,
+            )),
+            7 => Ok(StakeInstruction::Merge),
+            8 => Ok(StakeInstruction::AuthorizeWithSeed(
+                BorshDeserialize::deserialize_reader(reader)?,
+            )),
+            9 => Ok(StakeInstruction::InitializeChecked),
+            10 => Ok(StakeInstruction::AuthorizeChecked(
+                BorshDeserialize::deserialize_reader(reader)?,
+            )),
+            11 => Ok(StakeInstruction::AuthorizeCheckedWithSeed(
+                BorshDeserialize::deserialize_reader(reader)?,
+            )),
+            12 => Ok(StakeInstruction::SetLockupChecked(
+                BorshDeserialize::deserialize_reader(reader)?,
+            )),
+            13 => Ok(StakeInstruction::GetMinimumDelegation),
+            14 => Ok(StakeInstruction::DeactivateDelinquent),
+            15 => Ok(StakeInstruction::Redelegate),
+            16 => Ok(StakeInstruction::InitializeIfNeeded(
+                BorshDeserialize::deserialize_reader(reader)?,
+                BorshDeserialize::deserialize_reader(reader)?,
+            )),
+            17 => Ok(StakeInstruction::UpdateCustodian(
+                BorshDeserialize::deserialize_reader(reader)?,
+            )),
+            18 => Ok(StakeInstruction::ReplenishRewardsPool(
+                BorshDeserialize::deserialize_reader(reader)?,
+            )),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Invalid enum value",
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "stake-borsh")]
+impl BorshSerialize for StakeInstruction {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        match self {
+            StakeInstruction::Initialize(authorized, lockup) => {
+                writer.write_all(&0u32.to_le_bytes())?;
+                BorshSerialize::serialize(authorized, writer)?;
+                BorshSerialize::serialize(lockup, writer)
+            }
+            StakeInstruction::Authorize(pubkey, stake_authorize) => {
+                writer.write_all(&1u32.to_le_bytes())?;
+                BorshSerialize::serialize(pubkey, writer)?;
+                BorshSerialize::serialize(stake_authorize, writer)
+            }
+            StakeInstruction::DelegateStake => writer.write_all(&2u32.to_le_bytes()),
+            StakeInstruction::Split(lamports) => {
+                writer.write_all(&3u32.to_le_bytes())?;
+                BorshSerialize::serialize(lamports, writer)
+            }
+            StakeInstruction::Withdraw(lamports) => {
+                writer.write_all(&4u32.to_le_bytes())?;
+                BorshSerialize::serialize(lamports, writer)
+            }
+            StakeInstruction::Deactivate => writer.write_all(&5u32.to_le_bytes()),
+            StakeInstruction::SetLockup(args) => {
+                writer.write_all(&6u32.to_le_bytes())?;
+                BorshSerialize::serialize(args, writer)
+            }
+            StakeInstruction::Merge => writer.write_all(&7u32.to_le_bytes()),
+            StakeInstruction::AuthorizeWithSeed(args) => {
+                writer.write_all(&8u32.to_le_bytes())?;
+                BorshSerialize::serialize(args, writer)
+            }
+            StakeInstruction::InitializeChecked => writer.write_all(&9u32.to_le_bytes()),
+            StakeInstruction::AuthorizeChecked(stake_authorize) => {
+                writer.write_all(&10u32.to_le_bytes())?;
+                BorshSerialize::serialize(stake_authorize, writer)
+            }
+            StakeInstruction::AuthorizeCheckedWithSeed(args) => {
+                writer.write_all(&11u32.to_le_bytes())?;
+                BorshSerialize::serialize(args, writer)
+            }
+            StakeInstruction::SetLockupChecked(args) => {
+                writer.write_all(&12u32.to_le_bytes())?;
+                BorshSerialize::serialize(args, writer)
+            }
+            StakeInstruction::GetMinimumDelegation => writer.write_all(&13u32.to_le_bytes()),
+            StakeInstruction::DeactivateDelinquent => writer.write_all(&14u32.to_le_bytes()),
+            StakeInstruction::Redelegate => writer.write_all(&15u32.to_le_bytes()),
+            StakeInstruction::InitializeIfNeeded(authorized, lockup) => {
+                writer.write_all(&16u32.to_le_bytes())?;
+                BorshSerialize::serialize(authorized, writer)?;
+                BorshSerialize::serialize(lockup, writer)
+            }
+            StakeInstruction::UpdateCustodian(custodian) => {
+                writer.write_all(&17u32.to_le_bytes())?;
+                BorshSerialize::serialize(custodian, writer)
+            }
+            StakeInstruction::ReplenishRewardsPool(lamports) => {
+                writer.write_all(&18u32.to_le_bytes())?;
+                BorshSerialize::serialize(lamports, writer)
+            }
+        }
+    }
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "stake-borsh",
+    derive(BorshDeserialize, BorshSchema, BorshSerialize),
+    borsh(crate = "borsh")
+)]
+#[cfg_attr(any(test, feature = "fuzz"), derive(arbitrary::Arbitrary))]
 pub struct LockupArgs {
     pub unix_timestamp: Option<UnixTimestamp>,
     pub epoch: Option<Epoch>,
@@ -303,12 +744,24 @@ pub struct LockupArgs {
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "stake-borsh",
+    derive(BorshDeserialize, BorshSchema, BorshSerialize),
+    borsh(crate = "borsh")
+)]
+#[cfg_attr(any(test, feature = "fuzz"), derive(arbitrary::Arbitrary))]
 pub struct LockupCheckedArgs {
     pub unix_timestamp: Option<UnixTimestamp>,
     pub epoch: Option<Epoch>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(
+    feature = "stake-borsh",
+    derive(BorshDeserialize, BorshSchema, BorshSerialize),
+    borsh(crate = "borsh")
+)]
+#[cfg_attr(any(test, feature = "fuzz"), derive(arbitrary::Arbitrary))]
 pub struct AuthorizeWithSeedArgs {
     pub new_authorized_pubkey: Pubkey,
     pub stake_authorize: StakeAuthorize,
@@ -317,6 +770,12 @@ pub struct AuthorizeWithSeedArgs {
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(
+    feature = "stake-borsh",
+    derive(BorshDeserialize, BorshSchema, BorshSerialize),
+    borsh(crate = "borsh")
+)]
+#[cfg_attr(any(test, feature = "fuzz"), derive(arbitrary::Arbitrary))]
 pub struct AuthorizeCheckedWithSeedArgs {
     pub stake_authorize: StakeAuthorize,
     pub authority_seed: String,
@@ -334,6 +793,21 @@ pub fn initialize(stake_pubkey: &Pubkey, authorized: &Authorized, lockup: &Locku
     )
 }
 
+pub fn initialize_if_needed(
+    stake_pubkey: &Pubkey,
+    authorized: &Authorized,
+    lockup: &Lockup,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        id(),
+        &StakeInstruction::InitializeIfNeeded(*authorized, *lockup),
+        vec![
+            AccountMeta::new(*stake_pubkey, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+    )
+}
+
 pub fn initialize_checked(stake_pubkey: &Pubkey, authorized: &Authorized) -> Instruction {
     Instruction::new_with_bincode(
         id(),
@@ -705,6 +1179,78 @@ pub fn withdraw(
     Instruction::new_with_bincode(id(), &StakeInstruction::Withdraw(lamports), account_metas)
 }
 
+/// The most lamports that `Withdraw` will allow to be taken out of a stake
+/// account holding `stake_lamports` in `stake_state`, leaving aside any lockup.
+///
+/// An account with stake that is active, activating or not yet deactivated
+/// keeps that stake plus its rent exempt reserve. Any other account can be
+/// emptied, which closes it.
+pub fn max_withdraw_lamports(
+    stake_lamports: u64,
+    stake_state: &StakeStateV2,
+    clock: &Clock,
+    stake_history: &StakeHistory,
+    new_rate_activation_epoch: Option<Epoch>,
+) -> u64 {
+    match stake_state {
+        StakeStateV2::Stake(meta, stake, _stake_flags) => {
+            // mirrors the processor: stake that hasn't been deactivated
+            //  counts in full, since it may still be warming up
+            let staked = if clock.epoch >= stake.delegation.deactivation_epoch {
+                stake
+                    .delegation
+                    .stake(clock.epoch, stake_history, new_rate_activation_epoch)
+            } else {
+                stake.delegation.stake
+            };
+            if staked == 0 {
+                stake_lamports
+            } else {
+                stake_lamports.saturating_sub(staked.saturating_add(meta.rent_exempt_reserve))
+            }
+        }
+        StakeStateV2::Initialized(_) | StakeStateV2::Uninitialized => stake_lamports,
+        StakeStateV2::RewardsPool => 0,
+    }
+}
+
+/// A `Withdraw` of [`max_withdraw_lamports`] from the stake account, given
+/// its current balance and state, along with the amount withdrawn.
+///
+/// Returns `None` when nothing can be withdrawn, since a `Withdraw` of zero
+/// lamports fails sanitization.
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw_all(
+    stake_pubkey: &Pubkey,
+    withdrawer_pubkey: &Pubkey,
+    to_pubkey: &Pubkey,
+    stake_lamports: u64,
+    stake_state: &StakeStateV2,
+    clock: &Clock,
+    stake_history: &StakeHistory,
+    new_rate_activation_epoch: Option<Epoch>,
+    custodian_pubkey: Option<&Pubkey>,
+) -> Option<(Instruction, u64)> {
+    let lamports = max_withdraw_lamports(
+        stake_lamports,
+        stake_state,
+        clock,
+        stake_history,
+        new_rate_activation_epoch,
+    );
+    if lamports == 0 {
+        return None;
+    }
+    let instruction = withdraw(
+        stake_pubkey,
+        withdrawer_pubkey,
+        to_pubkey,
+        lamports,
+        custodian_pubkey,
+    );
+    Some((instruction, lamports))
+}
+
 pub fn deactivate_stake(stake_pubkey: &Pubkey, authorized_pubkey: &Pubkey) -> Instruction {
     let account_metas = vec![
         AccountMeta::new(*stake_pubkey, false),
@@ -750,6 +1296,39 @@ pub fn set_lockup_checked(
     )
 }
 
+pub fn update_custodian(
+    stake_pubkey: &Pubkey,
+    custodian_pubkey: &Pubkey,
+    new_custodian_pubkey: &Pubkey,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*custodian_pubkey, true),
+    ];
+    Instruction::new_with_bincode(
+        id(),
+        &StakeInstruction::UpdateCustodian(*new_custodian_pubkey),
+        account_metas,
+    )
+}
+
+pub fn replenish_rewards_pool(
+    pool_pubkey: &Pubkey,
+    funder_pubkey: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*pool_pubkey, false),
+        AccountMeta::new(*funder_pubkey, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Instruction::new_with_bincode(
+        id(),
+        &StakeInstruction::ReplenishRewardsPool(lamports),
+        account_metas,
+    )
+}
+
 pub fn get_minimum_delegation() -> Instruction {
     Instruction::new_with_bincode(
         id(),
@@ -833,7 +1412,17 @@ pub fn redelegate_with_seed(
 
 #[cfg(test)]
 mod tests {
-    use {super::*, crate::instruction::InstructionError};
+    use {
+        super::*,
+        crate::{
+            account_spec::check_account_metas,
+            stake::{
+                stake_flags::StakeFlags,
+                state::{Delegation, Meta, Stake},
+            },
+        },
+        bincode::Options,
+    };
 
     #[test]
     fn test_custom_error_decode() {
@@ -860,4 +1449,441 @@ mod tests {
             pretty_err::<StakeError>(StakeError::NoCreditsToRedeem.into())
         )
     }
+
+    #[cfg(feature = "stake-borsh")]
+    #[test]
+    fn test_stake_instruction_borsh_matches_bincode() {
+        // The bincode discriminant of each variant, matched exhaustively so
+        // that a new variant has to be added to this test
+        fn discriminant(instruction: &StakeInstruction) -> u32 {
+            match instruction {
+                StakeInstruction::Initialize(..) => 0,
+                StakeInstruction::Authorize(..) => 1,
+                StakeInstruction::DelegateStake => 2,
+                StakeInstruction::Split(_) => 3,
+                StakeInstruction::Withdraw(_) => 4,
+                StakeInstruction::Deactivate => 5,
+                StakeInstruction::SetLockup(_) => 6,
+                StakeInstruction::Merge => 7,
+                StakeInstruction::AuthorizeWithSeed(_) => 8,
+                StakeInstruction::InitializeChecked => 9,
+                StakeInstruction::AuthorizeChecked(_) => 10,
+                StakeInstruction::AuthorizeCheckedWithSeed(_) => 11,
+                StakeInstruction::SetLockupChecked(_) => 12,
+                StakeInstruction::GetMinimumDelegation => 13,
+                StakeInstruction::DeactivateDelinquent => 14,
+                StakeInstruction::Redelegate => 15,
+                StakeInstruction::InitializeIfNeeded(..) => 16,
+                StakeInstruction::UpdateCustodian(_) => 17,
+                StakeInstruction::ReplenishRewardsPool(_) => 18,
+            }
+        }
+
+        let authorized = Authorized::auto(&Pubkey::new_unique());
+        let lockup = Lockup {
+            unix_timestamp: 1,
+            epoch: 2,
+            custodian: Pubkey::new_unique(),
+        };
+        let instructions = [
+            StakeInstruction::Initialize(authorized, lockup),
+            StakeInstruction::Authorize(Pubkey::new_unique(), StakeAuthorize::Withdrawer),
+            StakeInstruction::DelegateStake,
+            StakeInstruction::Split(42),
+            StakeInstruction::Withdraw(u64::MAX),
+            StakeInstruction::Deactivate,
+            StakeInstruction::SetLockup(LockupArgs {
+                unix_timestamp: Some(-1),
+                epoch: None,
+                custodian: Some(Pubkey::new_unique()),
+            }),
+            StakeInstruction::Merge,
+            StakeInstruction::AuthorizeWithSeed(AuthorizeWithSeedArgs {
+                new_authorized_pubkey: Pubkey::new_unique(),
+                stake_authorize: StakeAuthorize::Staker,
+                authority_seed: "seed".to_string(),
+                authority_owner: Pubkey::new_unique(),
+            }),
+            StakeInstruction::InitializeChecked,
+            StakeInstruction::AuthorizeChecked(StakeAuthorize::Staker),
+            StakeInstruction::AuthorizeCheckedWithSeed(AuthorizeCheckedWithSeedArgs {
+                stake_authorize: StakeAuthorize::Withdrawer,
+                authority_seed: "seed".to_string(),
+                authority_owner: Pubkey::new_unique(),
+            }),
+            StakeInstruction::SetLockupChecked(LockupCheckedArgs {
+                unix_timestamp: None,
+                epoch: Some(3),
+            }),
+            StakeInstruction::GetMinimumDelegation,
+            StakeInstruction::DeactivateDelinquent,
+            StakeInstruction::Redelegate,
+            StakeInstruction::InitializeIfNeeded(Authorized::default(), Lockup::default()),
+            StakeInstruction::UpdateCustodian(Pubkey::new_unique()),
+            StakeInstruction::ReplenishRewardsPool(42),
+        ];
+        assert_eq!(
+            instructions.iter().map(discriminant).collect::<Vec<_>>(),
+            (0..=18).collect::<Vec<_>>()
+        );
+
+        for instruction in instructions {
+            let bincode_serialized = bincode::serialize(&instruction).unwrap();
+            let borsh_serialized = borsh::to_vec(&instruction).unwrap();
+            let discriminant = discriminant(&instruction).to_le_bytes();
+            assert_eq!(bincode_serialized[..4], discriminant);
+            assert_eq!(borsh_serialized[..4], discriminant);
+
+            // Each codec decodes its own bytes to the same value
+            let from_bincode: StakeInstruction = bincode::deserialize(&bincode_serialized).unwrap();
+            let from_borsh: StakeInstruction = borsh::from_slice(&borsh_serialized).unwrap();
+            assert_eq!(from_bincode, from_borsh);
+            assert_eq!(from_borsh, instruction);
+        }
+
+        assert!(borsh::from_slice::<StakeInstruction>(&19u32.to_le_bytes()).is_err());
+    }
+
+    fn check_decode_matches_bincode(data: &[u8]) {
+        assert_eq!(
+            StakeInstruction::decode(data).ok(),
+            bincode::deserialize::<StakeInstruction>(data).ok(),
+            "{data:?}"
+        );
+        assert_eq!(
+            StakeInstruction::decode_checked(data).ok(),
+            bincode::options()
+                .with_fixint_encoding()
+                .reject_trailing_bytes()
+                .deserialize::<StakeInstruction>(data)
+                .ok(),
+            "{data:?}"
+        );
+    }
+
+    #[test]
+    fn test_stake_instruction_decode_matches_bincode() {
+        let lockup_args = LockupArgs {
+            unix_timestamp: Some(i64::MIN),
+            epoch: Some(u64::MAX),
+            custodian: Some(Pubkey::new_unique()),
+        };
+        let mut instructions = vec![
+            StakeInstruction::Initialize(
+                Authorized {
+                    staker: Pubkey::new_unique(),
+                    withdrawer: Pubkey::new_unique(),
+                },
+                Lockup {
+                    unix_timestamp: -1,
+                    epoch: 2,
+                    custodian: Pubkey::new_unique(),
+                },
+            ),
+            StakeInstruction::DelegateStake,
+            StakeInstruction::Split(42),
+            StakeInstruction::Withdraw(u64::MAX),
+            StakeInstruction::Deactivate,
+            StakeInstruction::Merge,
+            StakeInstruction::InitializeChecked,
+            StakeInstruction::GetMinimumDelegation,
+            StakeInstruction::DeactivateDelinquent,
+            StakeInstruction::Redelegate,
+            StakeInstruction::InitializeIfNeeded(Authorized::default(), Lockup::default()),
+            StakeInstruction::UpdateCustodian(Pubkey::new_unique()),
+            StakeInstruction::ReplenishRewardsPool(u64::MAX),
+        ];
+        for stake_authorize in [StakeAuthorize::Staker, StakeAuthorize::Withdrawer] {
+            for authority_seed in ["", "seed", "\u{1f980}"] {
+                instructions.push(StakeInstruction::AuthorizeWithSeed(AuthorizeWithSeedArgs {
+                    new_authorized_pubkey: Pubkey::new_unique(),
+                    stake_authorize,
+                    authority_seed: authority_seed.to_string(),
+                    authority_owner: Pubkey::new_unique(),
+                }));
+                instructions.push(StakeInstruction::AuthorizeCheckedWithSeed(
+                    AuthorizeCheckedWithSeedArgs {
+                        stake_authorize,
+                        authority_seed: authority_seed.to_string(),
+                        authority_owner: Pubkey::new_unique(),
+                    },
+                ));
+            }
+            instructions.push(StakeInstruction::Authorize(
+                Pubkey::new_unique(),
+                stake_authorize,
+            ));
+            instructions.push(StakeInstruction::AuthorizeChecked(stake_authorize));
+        }
+        for unix_timestamp in [None, lockup_args.unix_timestamp] {
+            for epoch in [None, lockup_args.epoch] {
+                for custodian in [None, lockup_args.custodian] {
+                    instructions.push(StakeInstruction::SetLockup(LockupArgs {
+                        unix_timestamp,
+                        epoch,
+                        custodian,
+                    }));
+                }
+                instructions.push(StakeInstruction::SetLockupChecked(LockupCheckedArgs {
+                    unix_timestamp,
+                    epoch,
+                }));
+            }
+        }
+
+        for instruction in instructions {
+            let mut data = bincode::serialize(&instruction).unwrap();
+            assert_eq!(StakeInstruction::decode(&data).as_ref(), Ok(&instruction));
+            assert_eq!(StakeInstruction::decode_checked(&data), Ok(instruction));
+            // Every truncation fails in both
+            for len in 0..data.len() {
+                check_decode_matches_bincode(&data[..len]);
+            }
+            // Trailing bytes are ignored by both, or rejected by both when
+            // checked
+            data.push(0);
+            check_decode_matches_bincode(&data);
+            assert!(StakeInstruction::decode_checked(&data).is_err());
+            // Corrupting any single byte is accepted or rejected by both
+            for i in 0..data.len() {
+                for byte in [2, 0x80, 0xff] {
+                    let mut corrupted = data.clone();
+                    corrupted[i] = byte;
+                    check_decode_matches_bincode(&corrupted);
+                }
+            }
+        }
+
+        // Random data behind each discriminant, 0..=18, and the first invalid
+        // one
+        for _ in 0..1000 {
+            let len = rand::random::<usize>() % 128;
+            let mut data: Vec<u8> = (0..len).map(|_| rand::random()).collect();
+            if data.len() >= 4 {
+                data[..4].copy_from_slice(&(rand::random::<u32>() % 20).to_le_bytes());
+            }
+            check_decode_matches_bincode(&data);
+        }
+    }
+
+    #[test]
+    fn test_builders_match_account_spec() {
+        let [stake, authority, new_authority, custodian, other, vote] =
+            [(); 6].map(|_| Pubkey::new_unique());
+        let authorized = Authorized::auto(&authority);
+        let lockup = Lockup::default();
+        let lockup_args = LockupArgs {
+            custodian: Some(new_authority),
+            ..LockupArgs::default()
+        };
+        let mut instructions = vec![
+            initialize(&stake, &authorized, &lockup),
+            initialize_if_needed(&stake, &authorized, &lockup),
+            initialize_checked(&stake, &authorized),
+            delegate_stake(&stake, &authority, &vote),
+            deactivate_stake(&stake, &authority),
+            set_lockup(&stake, &lockup_args, &custodian),
+            set_lockup_checked(&stake, &lockup_args, &custodian),
+            update_custodian(&stake, &custodian, &new_authority),
+            replenish_rewards_pool(&other, &authority, 42),
+            get_minimum_delegation(),
+            deactivate_delinquent_stake(&stake, &vote, &other),
+        ];
+        for custodian in [None, Some(&custodian)] {
+            for stake_authorize in [StakeAuthorize::Staker, StakeAuthorize::Withdrawer] {
+                instructions.push(authorize(
+                    &stake,
+                    &authority,
+                    &new_authority,
+                    stake_authorize,
+                    custodian,
+                ));
+                instructions.push(authorize_checked(
+                    &stake,
+                    &authority,
+                    &new_authority,
+                    stake_authorize,
+                    custodian,
+                ));
+                instructions.push(authorize_with_seed(
+                    &stake,
+                    &authority,
+                    "seed".to_string(),
+                    &other,
+                    &new_authority,
+                    stake_authorize,
+                    custodian,
+                ));
+                instructions.push(authorize_checked_with_seed(
+                    &stake,
+                    &authority,
+                    "seed".to_string(),
+                    &other,
+                    &new_authority,
+                    stake_authorize,
+                    custodian,
+                ));
+            }
+            instructions.push(withdraw(&stake, &authority, &other, 1, custodian));
+        }
+        instructions.extend(split(&stake, &authority, 1, &other));
+        instructions.extend(merge(&stake, &other, &authority));
+        instructions.extend(redelegate(&stake, &authority, &vote, &other));
+
+        for instruction in instructions.iter().filter(|ix| ix.program_id == id()) {
+            let stake_instruction: StakeInstruction =
+                bincode::deserialize(&instruction.data).unwrap();
+            assert_eq!(
+                check_account_metas(stake_instruction.account_spec(), &instruction.accounts),
+                Ok(()),
+                "{stake_instruction:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_withdraw_all() {
+        let meta = Meta {
+            rent_exempt_reserve: 10,
+            ..Meta::default()
+        };
+        let mut stake = Stake {
+            delegation: Delegation::new(&Pubkey::new_unique(), 100, 0),
+            credits_observed: 0,
+        };
+        let clock = Clock {
+            epoch: 5,
+            ..Clock::default()
+        };
+        let stake_history = StakeHistory::default();
+        let max = |lamports, state: &StakeStateV2| {
+            max_withdraw_lamports(lamports, state, &clock, &stake_history, None)
+        };
+
+        assert_eq!(max(1000, &StakeStateV2::Uninitialized), 1000);
+        assert_eq!(max(1000, &StakeStateV2::Initialized(meta)), 1000);
+        assert_eq!(max(1000, &StakeStateV2::RewardsPool), 0);
+
+        // delegated, keeps its stake and reserve
+        let state = StakeStateV2::Stake(meta, stake, StakeFlags::empty());
+        assert_eq!(max(1000, &state), 890);
+        assert_eq!(max(105, &state), 0);
+
+        // deactivated and cooled down, can be closed
+        stake.delegation.deactivation_epoch = 1;
+        let state = StakeStateV2::Stake(meta, stake, StakeFlags::empty());
+        assert_eq!(max(1000, &state), 1000);
+
+        let stake_pubkey = Pubkey::new_unique();
+        let withdrawer_pubkey = Pubkey::new_unique();
+        let to_pubkey = Pubkey::new_unique();
+        assert_eq!(
+            withdraw_all(
+                &stake_pubkey,
+                &withdrawer_pubkey,
+                &to_pubkey,
+                1000,
+                &state,
+                &clock,
+                &stake_history,
+                None,
+                None,
+            ),
+            Some((
+                withdraw(&stake_pubkey, &withdrawer_pubkey, &to_pubkey, 1000, None),
+                1000
+            ))
+        );
+
+        // nothing to withdraw
+        let state = StakeStateV2::RewardsPool;
+        assert_eq!(
+            withdraw_all(
+                &stake_pubkey,
+                &withdrawer_pubkey,
+                &to_pubkey,
+                1000,
+                &state,
+                &clock,
+                &stake_history,
+                None,
+                None,
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sanitize() {
+        let authorized = Authorized::auto(&Pubkey::new_unique());
+        let lockup = Lockup::default();
+        for instruction in [
+            StakeInstruction::Initialize(authorized, lockup),
+            StakeInstruction::InitializeIfNeeded(authorized, lockup),
+            StakeInstruction::Authorize(Pubkey::new_unique(), StakeAuthorize::Staker),
+            StakeInstruction::Withdraw(1),
+            StakeInstruction::SetLockup(LockupArgs {
+                unix_timestamp: Some(0),
+                epoch: Some(Epoch::MAX),
+                custodian: Some(Pubkey::default()),
+            }),
+            StakeInstruction::UpdateCustodian(Pubkey::default()),
+            StakeInstruction::ReplenishRewardsPool(1),
+        ] {
+            assert_eq!(instruction.sanitize(), Ok(()), "{instruction:?}");
+        }
+
+        for (instruction, expected) in [
+            (
+                StakeInstruction::Withdraw(0),
+                SanitizeError::ValueOutOfBounds,
+            ),
+            (
+                StakeInstruction::ReplenishRewardsPool(0),
+                SanitizeError::ValueOutOfBounds,
+            ),
+            (
+                StakeInstruction::Initialize(
+                    Authorized {
+                        withdrawer: Pubkey::default(),
+                        ..authorized
+                    },
+                    lockup,
+                ),
+                SanitizeError::InvalidValue,
+            ),
+            (
+                StakeInstruction::InitializeIfNeeded(
+                    authorized,
+                    Lockup {
+                        unix_timestamp: -1,
+                        ..lockup
+                    },
+                ),
+                SanitizeError::ValueOutOfBounds,
+            ),
+            (
+                StakeInstruction::Authorize(Pubkey::default(), StakeAuthorize::Withdrawer),
+                SanitizeError::InvalidValue,
+            ),
+            (
+                StakeInstruction::AuthorizeWithSeed(AuthorizeWithSeedArgs {
+                    new_authorized_pubkey: Pubkey::default(),
+                    stake_authorize: StakeAuthorize::Staker,
+                    authority_seed: "seed".to_string(),
+                    authority_owner: Pubkey::new_unique(),
+                }),
+                SanitizeError::InvalidValue,
+            ),
+            (
+                StakeInstruction::SetLockupChecked(LockupCheckedArgs {
+                    unix_timestamp: Some(-1),
+                    epoch: None,
+                }),
+                SanitizeError::ValueOutOfBounds,
+            ),
+        ] {
+            assert_eq!(instruction.sanitize(), Err(expected), "{instruction:?}");
+        }
+    }
 }
@@ -18,9 +18,11 @@ use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
     Debug,
 )]
 #[borsh(crate = "borsh")]
+#[cfg_attr(any(test, feature = "fuzz"), derive(arbitrary::Arbitrary))]
 pub struct StakeFlags {
     bits: u8,
 }
+#[cfg(feature = "borsh010")]
 impl borsh0_10::de::BorshDeserialize for StakeFlags {
     fn deserialize_reader<R: borsh0_10::maybestd::io::Read>(
         reader: &mut R,
@@ -30,6 +32,7 @@ impl borsh0_10::de::BorshDeserialize for StakeFlags {
         })
     }
 }
+#[cfg(feature = "borsh010")]
 impl borsh0_10::BorshSchema for StakeFlags {
     fn declaration() -> borsh0_10::schema::Declaration {
         "StakeFlags".to_string()
@@ -55,6 +58,7 @@ impl borsh0_10::BorshSchema for StakeFlags {
         <u8 as borsh0_10::BorshSchema>::add_definitions_recursively(definitions);
     }
 }
+#[cfg(feature = "borsh010")]
 impl borsh0_10::ser::BorshSerialize for StakeFlags {
     fn serialize<W: borsh0_10::maybestd::io::Write>(
         &self,
@@ -75,6 +79,16 @@ impl StakeFlags {
         Self { bits: 0 }
     }
 
+    /// The raw bits, including any reserved for future usage
+    pub const fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Flags from raw bits, keeping any reserved for future usage
+    pub const fn from_bits_retain(bits: u8) -> Self {
+        Self { bits }
+    }
+
     pub const fn contains(&self, other: Self) -> bool {
         (self.bits & other.bits) == other.bits
     }
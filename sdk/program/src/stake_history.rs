@@ -7,7 +7,7 @@
 //! [`sysvar::stake_history`]: crate::sysvar::stake_history
 
 pub use crate::clock::Epoch;
-use std::ops::Deref;
+use std::ops::{Bound, Deref, RangeBounds};
 
 pub const MAX_ENTRIES: usize = 512; // it should never take as many as 512 epochs to warm up or cool down
 
@@ -73,6 +73,28 @@ impl StakeHistory {
         }
         (self.0).truncate(MAX_ENTRIES);
     }
+
+    /// Iterate over the entries whose epoch lies in `epochs`, newest first.
+    ///
+    /// Entries are kept sorted by epoch, so the bounds of the range are found
+    /// by binary search rather than by scanning the whole history.
+    pub fn iter_range<R: RangeBounds<Epoch>>(
+        &self,
+        epochs: R,
+    ) -> impl DoubleEndedIterator<Item = &(Epoch, StakeHistoryEntry)> {
+        // entries are sorted from the newest to the oldest epoch
+        let first = match epochs.end_bound() {
+            Bound::Included(end) => self.partition_point(|(epoch, _)| epoch > end),
+            Bound::Excluded(end) => self.partition_point(|(epoch, _)| epoch >= end),
+            Bound::Unbounded => 0,
+        };
+        let last = match epochs.start_bound() {
+            Bound::Included(start) => self.partition_point(|(epoch, _)| epoch >= start),
+            Bound::Excluded(start) => self.partition_point(|(epoch, _)| epoch > start),
+            Bound::Unbounded => self.len(),
+        };
+        self[first..last.max(first)].iter()
+    }
 }
 
 impl Deref for StakeHistory {
@@ -110,4 +132,46 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_iter_range() {
+        let mut stake_history = StakeHistory::default();
+        for epoch in (0..10).step_by(2) {
+            stake_history.add(epoch, StakeHistoryEntry::with_effective(epoch));
+        }
+        let epochs = |range: &mut dyn Iterator<Item = &(Epoch, StakeHistoryEntry)>| {
+            range.map(|(epoch, _)| *epoch).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            epochs(&mut stake_history.iter_range(..)),
+            vec![8, 6, 4, 2, 0]
+        );
+        assert_eq!(epochs(&mut stake_history.iter_range(2..6)), vec![4, 2]);
+        assert_eq!(epochs(&mut stake_history.iter_range(2..=6)), vec![6, 4, 2]);
+        assert_eq!(epochs(&mut stake_history.iter_range(3..)), vec![8, 6, 4]);
+        assert_eq!(epochs(&mut stake_history.iter_range(..=3)), vec![2, 0]);
+        assert_eq!(
+            epochs(&mut stake_history.iter_range((Bound::Excluded(4), Bound::Unbounded))),
+            vec![8, 6]
+        );
+        assert_eq!(
+            epochs(&mut stake_history.iter_range(5..6)),
+            Vec::<Epoch>::new()
+        );
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 6..2;
+        assert_eq!(
+            epochs(&mut stake_history.iter_range(reversed)),
+            Vec::<Epoch>::new()
+        );
+        assert_eq!(
+            epochs(&mut stake_history.iter_range(20..)),
+            Vec::<Epoch>::new()
+        );
+        assert_eq!(
+            epochs(&mut stake_history.iter_range(2..=6).rev()),
+            vec![2, 4, 6]
+        );
+    }
 }
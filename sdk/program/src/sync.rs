@@ -0,0 +1,180 @@
+//! Synchronization primitives for process-wide state.
+//!
+//! By default these wrap the standard library's primitives. With the `spin`
+//! feature they wrap the [spin] crate's spinning primitives instead, which
+//! don't depend on the operating system and so also work in `no_std`
+//! environments. Either way the API is the same, so code initializing statics
+//! can use it without caring which backend is selected.
+//!
+//! Unlike the standard library's, these primitives don't propagate panics as
+//! poisoning: a lock whose holder panicked can still be taken.
+//!
+//! [spin]: https://docs.rs/spin
+
+use std::ops::Deref;
+
+#[cfg(feature = "spin")]
+mod backend {
+    pub(super) type OnceCell<T> = spin::Once<T>;
+    pub(super) type Mutex<T> = spin::Mutex<T>;
+    pub type MutexGuard<'a, T> = spin::MutexGuard<'a, T>;
+
+    pub(super) fn get_or_init<T>(cell: &OnceCell<T>, f: impl FnOnce() -> T) -> &T {
+        cell.call_once(f)
+    }
+
+    pub(super) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+        mutex.lock()
+    }
+}
+
+#[cfg(not(feature = "spin"))]
+mod backend {
+    pub(super) type OnceCell<T> = std::sync::OnceLock<T>;
+    pub(super) type Mutex<T> = std::sync::Mutex<T>;
+    pub type MutexGuard<'a, T> = std::sync::MutexGuard<'a, T>;
+
+    pub(super) fn get_or_init<T>(cell: &OnceCell<T>, f: impl FnOnce() -> T) -> &T {
+        cell.get_or_init(f)
+    }
+
+    pub(super) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+        mutex
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+pub use backend::MutexGuard;
+
+/// A cell written at most once, like [`std::sync::OnceLock`].
+///
+/// `Once<()>` runs an initialization routine once, like [`std::sync::Once`].
+pub struct Once<T = ()>(backend::OnceCell<T>);
+
+impl<T> Once<T> {
+    pub const fn new() -> Self {
+        Self(backend::OnceCell::new())
+    }
+
+    /// Initializes the value with `f` if no other call has, and returns it.
+    ///
+    /// Concurrent callers block until the value is initialized.
+    pub fn call_once(&self, f: impl FnOnce() -> T) -> &T {
+        backend::get_or_init(&self.0, f)
+    }
+
+    /// Returns the value, if initialized.
+    pub fn get(&self) -> Option<&T> {
+        self.0.get()
+    }
+}
+
+impl<T> Default for Once<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A mutual exclusion lock, like [`std::sync::Mutex`].
+pub struct Mutex<T>(backend::Mutex<T>);
+
+impl<T> Mutex<T> {
+    pub const fn new(value: T) -> Self {
+        Self(backend::Mutex::new(value))
+    }
+
+    /// Blocks until the lock is acquired.
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        backend::lock(&self.0)
+    }
+}
+
+impl<T: Default> Default for Mutex<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// A value initialized on first access, like `std::sync::LazyLock`.
+pub struct LazyLock<T> {
+    cell: Once<T>,
+    init: fn() -> T,
+}
+
+impl<T> LazyLock<T> {
+    pub const fn new(init: fn() -> T) -> Self {
+        Self {
+            cell: Once::new(),
+            init,
+        }
+    }
+
+    /// Initializes the value if necessary and returns it.
+    pub fn force(this: &Self) -> &T {
+        this.cell.call_once(this.init)
+    }
+}
+
+impl<T> Deref for LazyLock<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        Self::force(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    #[test]
+    fn test_once() {
+        static INIT: Once = Once::new();
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        assert!(INIT.get().is_none());
+        for _ in 0..3 {
+            INIT.call_once(|| {
+                CALLS.fetch_add(1, Ordering::Relaxed);
+            });
+        }
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+        assert!(INIT.get().is_some());
+
+        let once = Once::default();
+        assert_eq!(*once.call_once(|| 1), 1);
+        assert_eq!(*once.call_once(|| 2), 1);
+    }
+
+    #[test]
+    fn test_mutex_survives_panic() {
+        let mutex = Mutex::new(0);
+        std::thread::scope(|scope| {
+            let result = scope
+                .spawn(|| {
+                    let _guard = mutex.lock();
+                    panic!("while holding the lock");
+                })
+                .join();
+            assert!(result.is_err());
+        });
+        *mutex.lock() += 1;
+        assert_eq!(*mutex.lock(), 1);
+    }
+
+    #[test]
+    fn test_lazy_lock() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        static VALUE: LazyLock<Vec<u8>> = LazyLock::new(|| {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+            vec![1, 2, 3]
+        });
+        assert_eq!(CALLS.load(Ordering::Relaxed), 0);
+        assert_eq!(VALUE.len(), 3);
+        assert_eq!(*LazyLock::force(&VALUE), [1, 2, 3]);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    }
+}
@@ -42,8 +42,9 @@
 #[allow(deprecated)]
 use {
     crate::{
+        compute_units::BuiltinComputeUnits,
         decode_error::DecodeError,
-        instruction::{AccountMeta, Instruction},
+        instruction::{AccountMeta, Instruction, InstructionError},
         nonce,
         pubkey::Pubkey,
         system_program,
@@ -275,6 +276,10 @@ pub enum SystemInstruction {
     UpgradeNonceAccount,
 }
 
+impl BuiltinComputeUnits for SystemInstruction {
+    const DEFAULT_COMPUTE_UNITS: u64 = 150;
+}
+
 /// Create an account.
 ///
 /// This function produces an [`Instruction`] which must be submitted in a
@@ -1278,6 +1283,25 @@ pub fn transfer_many(from_pubkey: &Pubkey, to_lamports: &[(Pubkey, u64)]) -> Vec
         .collect()
 }
 
+/// Like [`transfer_many`], but checking that the transfers add up to no more
+/// than `u64::MAX` lamports, which no account could hold.
+///
+/// # Errors
+///
+/// Returns [`InstructionError::ArithmeticOverflow`] if the total overflows.
+pub fn try_transfer_many(
+    from_pubkey: &Pubkey,
+    to_lamports: &[(Pubkey, u64)],
+) -> Result<Vec<Instruction>, InstructionError> {
+    to_lamports
+        .iter()
+        .try_fold(0u64, |total, (_to_pubkey, lamports)| {
+            total.checked_add(*lamports)
+        })
+        .ok_or(InstructionError::ArithmeticOverflow)?;
+    Ok(transfer_many(from_pubkey, to_lamports))
+}
+
 pub fn create_nonce_account_with_seed(
     from_pubkey: &Pubkey,
     nonce_pubkey: &Pubkey,
@@ -1786,6 +1810,25 @@ mod tests {
         assert_eq!(get_keys(&instructions[1]), vec![alice_pubkey, carol_pubkey]);
     }
 
+    #[test]
+    fn test_try_transfer_many() {
+        let alice_pubkey = Pubkey::new_unique();
+        let bob_pubkey = Pubkey::new_unique();
+        let carol_pubkey = Pubkey::new_unique();
+
+        let to_lamports = vec![(bob_pubkey, u64::MAX - 2), (carol_pubkey, 2)];
+        assert_eq!(
+            try_transfer_many(&alice_pubkey, &to_lamports),
+            Ok(transfer_many(&alice_pubkey, &to_lamports))
+        );
+        let to_lamports = vec![(bob_pubkey, u64::MAX - 2), (carol_pubkey, 3)];
+        assert_eq!(
+            try_transfer_many(&alice_pubkey, &to_lamports),
+            Err(InstructionError::ArithmeticOverflow)
+        );
+        assert_eq!(try_transfer_many(&alice_pubkey, &[]), Ok(vec![]));
+    }
+
     #[test]
     fn test_create_nonce_account() {
         let from_pubkey = Pubkey::new_unique();
@@ -81,10 +81,7 @@
 //!
 //! [sysvardoc]: https://docs.solanalabs.com/runtime/sysvars
 
-use {
-    crate::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey},
-    lazy_static::lazy_static,
-};
+use crate::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
 
 pub mod clock;
 pub mod epoch_rewards;
@@ -99,27 +96,53 @@ pub mod slot_hashes;
 pub mod slot_history;
 pub mod stake_history;
 
-lazy_static! {
-    // This will be deprecated and so this list shouldn't be modified
-    pub static ref ALL_IDS: Vec<Pubkey> = vec![
-        clock::id(),
-        epoch_schedule::id(),
-        #[allow(deprecated)]
-        fees::id(),
-        #[allow(deprecated)]
-        recent_blockhashes::id(),
-        rent::id(),
-        rewards::id(),
-        slot_hashes::id(),
-        slot_history::id(),
-        stake_history::id(),
-        instructions::id(),
-    ];
+// This will be deprecated and so this list shouldn't be modified
+pub const ALL_IDS: [Pubkey; 10] = [
+    clock::ID,
+    epoch_schedule::ID,
+    fees::ID,
+    recent_blockhashes::ID,
+    rent::ID,
+    rewards::ID,
+    slot_hashes::ID,
+    slot_history::ID,
+    stake_history::ID,
+    instructions::ID,
+];
+
+/// [`ALL_IDS`] in ascending order, for binary search
+const SORTED_IDS: [Pubkey; ALL_IDS.len()] = sort_ids(ALL_IDS);
+
+#[allow(clippy::arithmetic_side_effects)]
+const fn sort_ids<const N: usize>(mut ids: [Pubkey; N]) -> [Pubkey; N] {
+    // Insertion sort, ordering by bytes the same way as `Pubkey`'s `Ord`
+    const fn less_than(a: &Pubkey, b: &Pubkey) -> bool {
+        let mut i = 0;
+        while i < a.0.len() {
+            if a.0[i] != b.0[i] {
+                return a.0[i] < b.0[i];
+            }
+            i += 1;
+        }
+        false
+    }
+    let mut i = 1;
+    while i < N {
+        let mut j = i;
+        while j > 0 && less_than(&ids[j], &ids[j - 1]) {
+            let id = ids[j];
+            ids[j] = ids[j - 1];
+            ids[j - 1] = id;
+            j -= 1;
+        }
+        i += 1;
+    }
+    ids
 }
 
 /// Returns `true` of the given `Pubkey` is a sysvar account.
 pub fn is_sysvar_id(id: &Pubkey) -> bool {
-    ALL_IDS.iter().any(|key| key == id)
+    SORTED_IDS.binary_search(id).is_ok()
 }
 
 /// Declares an ID that implements [`SysvarId`].
@@ -263,6 +286,23 @@ mod tests {
     }
     impl Sysvar for TestSysvar {}
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_is_sysvar_id() {
+        assert!(SORTED_IDS.windows(2).all(|ids| ids[0] < ids[1]));
+        let mut ids = ALL_IDS;
+        ids.sort();
+        assert_eq!(ids, SORTED_IDS);
+
+        for id in ALL_IDS {
+            assert!(is_sysvar_id(&id));
+        }
+        assert!(!is_sysvar_id(&Pubkey::default()));
+        assert!(!is_sysvar_id(&Pubkey::new_unique()));
+        // Newer sysvars were never added to the list
+        assert!(!is_sysvar_id(&epoch_rewards::ID));
+    }
+
     #[test]
     fn test_sysvar_account_info_to_from() {
         let test_sysvar = TestSysvar::default();
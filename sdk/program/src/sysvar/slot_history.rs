@@ -12,6 +12,10 @@
 //! [`SysvarId::id`]: crate::sysvar::SysvarId::id
 //! [`SysvarId::check_id`]: crate::sysvar::SysvarId::check_id
 //!
+//! Programs that only need to know whether a slot is present can pass the
+//! sysvar account to [`check_slot`], which reads the requested bit directly
+//! from the serialized account data without deserializing the bitvector.
+//!
 //! # Examples
 //!
 //! Calling via the RPC client:
@@ -47,10 +51,62 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
-use crate::sysvar::Sysvar;
 pub use crate::{
     account_info::AccountInfo, program_error::ProgramError, slot_history::SlotHistory,
 };
+use crate::{
+    clock::Slot,
+    slot_history::{Check, MAX_ENTRIES},
+    sysvar::Sysvar,
+};
+
+// Offsets into the bincode layout of `SlotHistory`: the bitvector's
+// `Option<Box<[u64]>>` tag and block count, the blocks themselves, then the
+// bitvector's bit length and `next_slot`.
+const BLOCKS_LEN_OFFSET: usize = 1;
+const BLOCKS_OFFSET: usize = BLOCKS_LEN_OFFSET + 8;
+const NUM_BLOCKS: u64 = MAX_ENTRIES / 64;
+const NEXT_SLOT_OFFSET: usize = BLOCKS_OFFSET + (NUM_BLOCKS as usize) * 8 + 8;
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    data.get(offset..offset.checked_add(8)?)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+}
+
+/// Checks `slot` against serialized [`SlotHistory`] account data.
+///
+/// Returns the same result as [`SlotHistory::check`] without allocating the
+/// bitvector, so it can be used on-chain. Returns
+/// [`ProgramError::InvalidAccountData`] if `data` is not a serialized
+/// `SlotHistory`.
+pub fn check_slot(data: &[u8], slot: Slot) -> Result<Check, ProgramError> {
+    if data.len() != SlotHistory::size_of()
+        || data[0] != 1
+        || read_u64(data, BLOCKS_LEN_OFFSET) != Some(NUM_BLOCKS)
+    {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let next_slot = read_u64(data, NEXT_SLOT_OFFSET).ok_or(ProgramError::InvalidAccountData)?;
+    let newest = next_slot
+        .checked_sub(1)
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    if slot > newest {
+        return Ok(Check::Future);
+    }
+    if slot < next_slot.saturating_sub(MAX_ENTRIES) {
+        return Ok(Check::TooOld);
+    }
+    let index = slot % MAX_ENTRIES;
+    let block_offset = BLOCKS_OFFSET.saturating_add(((index / 64) as usize).saturating_mul(8));
+    let block = read_u64(data, block_offset).ok_or(ProgramError::InvalidAccountData)?;
+    if block & (1 << (index % 64)) != 0 {
+        Ok(Check::Found)
+    } else {
+        Ok(Check::NotFound)
+    }
+}
 
 crate::declare_sysvar_id!("SysvarS1otHistory11111111111111111111111111", SlotHistory);
 
@@ -76,4 +132,51 @@ mod tests {
             bincode::serialized_size(&SlotHistory::default()).unwrap() as usize
         );
     }
+
+    #[test]
+    fn test_check_slot() {
+        let mut slot_history = SlotHistory::default();
+        for slot in [2, 20, 63, 64, 65, MAX_ENTRIES, MAX_ENTRIES + 100] {
+            slot_history.add(slot);
+        }
+        let data = bincode::serialize(&slot_history).unwrap();
+
+        for slot in [
+            0,
+            1,
+            2,
+            3,
+            20,
+            63,
+            64,
+            65,
+            101,
+            MAX_ENTRIES,
+            MAX_ENTRIES + 100,
+            u64::MAX,
+        ] {
+            assert_eq!(
+                check_slot(&data, slot).unwrap(),
+                slot_history.check(slot),
+                "slot: {slot}"
+            );
+        }
+
+        assert_eq!(
+            check_slot(&data[..data.len() - 1], 2),
+            Err(ProgramError::InvalidAccountData)
+        );
+        let mut bad_data = data.clone();
+        bad_data[0] = 0;
+        assert_eq!(
+            check_slot(&bad_data, 2),
+            Err(ProgramError::InvalidAccountData)
+        );
+        let mut bad_data = data;
+        bad_data[NEXT_SLOT_OFFSET..].fill(0);
+        assert_eq!(
+            check_slot(&bad_data, 2),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
 }
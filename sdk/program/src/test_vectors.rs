@@ -0,0 +1,211 @@
+//! Canonical serializations of core types, for checking wire compatibility.
+//!
+//! Each vector pairs a deterministic value with the exact bytes this crate
+//! serializes it to. Downstream crates, forks and non-Rust clients can check
+//! their own encoders and decoders against these bytes, and
+//! [`assert_bincode_vector`] checks both directions for Rust types.
+
+#![cfg(not(target_os = "solana"))]
+
+use {
+    crate::{
+        clock::Clock,
+        epoch_schedule::EpochSchedule,
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+        pubkey::Pubkey,
+        rent::Rent,
+        stake::state::{Authorized, Lockup, Meta, StakeStateV2},
+    },
+    serde::{de::DeserializeOwned, Serialize},
+    std::fmt::Debug,
+};
+
+/// A value together with its expected bincode encoding
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestVector<T> {
+    pub name: &'static str,
+    pub value: T,
+    pub bincode: &'static [u8],
+}
+
+/// Assert that `vector.value` serializes to exactly `vector.bincode`, and that
+/// those bytes deserialize back to `vector.value`.
+pub fn assert_bincode_vector<T>(vector: &TestVector<T>)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    assert_eq!(
+        bincode::serialize(&vector.value).unwrap(),
+        vector.bincode,
+        "{} serialization mismatch",
+        vector.name
+    );
+    assert_eq!(
+        bincode::deserialize::<T>(vector.bincode).unwrap(),
+        vector.value,
+        "{} deserialization mismatch",
+        vector.name
+    );
+}
+
+pub fn pubkey() -> TestVector<Pubkey> {
+    TestVector {
+        name: "pubkey",
+        value: Pubkey::new_from_array(std::array::from_fn(|i| i as u8)),
+        bincode: PUBKEY_BINCODE,
+    }
+}
+
+pub fn hash() -> TestVector<Hash> {
+    TestVector {
+        name: "hash",
+        value: Hash::new_from_array(std::array::from_fn(|i| u8::MAX.wrapping_sub(i as u8))),
+        bincode: HASH_BINCODE,
+    }
+}
+
+pub fn instruction() -> TestVector<Instruction> {
+    TestVector {
+        name: "instruction",
+        value: Instruction::new_with_bytes(
+            Pubkey::new_from_array([3; 32]),
+            &[1, 2, 3],
+            vec![
+                AccountMeta::new(Pubkey::new_from_array([4; 32]), true),
+                AccountMeta::new_readonly(Pubkey::new_from_array([5; 32]), false),
+            ],
+        ),
+        bincode: INSTRUCTION_BINCODE,
+    }
+}
+
+/// A legacy message containing [`instruction()`], paid for by its signer
+pub fn message() -> TestVector<Message> {
+    TestVector {
+        name: "message",
+        value: Message::new_with_blockhash(
+            &[instruction().value],
+            Some(&Pubkey::new_from_array([4; 32])),
+            &hash().value,
+        ),
+        bincode: MESSAGE_BINCODE,
+    }
+}
+
+pub fn stake_state() -> TestVector<StakeStateV2> {
+    TestVector {
+        name: "stake_state",
+        value: StakeStateV2::Initialized(Meta {
+            rent_exempt_reserve: 2_282_880,
+            authorized: Authorized {
+                staker: Pubkey::new_from_array([6; 32]),
+                withdrawer: Pubkey::new_from_array([7; 32]),
+            },
+            lockup: Lockup {
+                unix_timestamp: 1_700_000_000,
+                epoch: 500,
+                custodian: Pubkey::new_from_array([8; 32]),
+            },
+        }),
+        bincode: STAKE_STATE_BINCODE,
+    }
+}
+
+pub fn clock() -> TestVector<Clock> {
+    TestVector {
+        name: "clock",
+        value: Clock {
+            slot: 250_000_000,
+            epoch_start_timestamp: 1_699_990_000,
+            epoch: 578,
+            leader_schedule_epoch: 579,
+            unix_timestamp: 1_700_000_000,
+        },
+        bincode: CLOCK_BINCODE,
+    }
+}
+
+pub fn rent() -> TestVector<Rent> {
+    TestVector {
+        name: "rent",
+        value: Rent::default(),
+        bincode: RENT_BINCODE,
+    }
+}
+
+pub fn epoch_schedule() -> TestVector<EpochSchedule> {
+    TestVector {
+        name: "epoch_schedule",
+        value: EpochSchedule::default(),
+        bincode: EPOCH_SCHEDULE_BINCODE,
+    }
+}
+
+const PUBKEY_BINCODE: &[u8] = &[
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+    26, 27, 28, 29, 30, 31,
+];
+const HASH_BINCODE: &[u8] = &[
+    255, 254, 253, 252, 251, 250, 249, 248, 247, 246, 245, 244, 243, 242, 241, 240, 239, 238, 237,
+    236, 235, 234, 233, 232, 231, 230, 229, 228, 227, 226, 225, 224,
+];
+const INSTRUCTION_BINCODE: &[u8] = &[
+    3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3,
+    2, 0, 0, 0, 0, 0, 0, 0, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4,
+    4, 4, 4, 4, 4, 4, 4, 4, 1, 1, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5,
+    5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3,
+];
+const MESSAGE_BINCODE: &[u8] = &[
+    1, 0, 2, 3, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4,
+    4, 4, 4, 4, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3,
+    3, 3, 3, 3, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5,
+    5, 5, 5, 5, 255, 254, 253, 252, 251, 250, 249, 248, 247, 246, 245, 244, 243, 242, 241, 240,
+    239, 238, 237, 236, 235, 234, 233, 232, 231, 230, 229, 228, 227, 226, 225, 224, 1, 1, 2, 0, 2,
+    3, 1, 2, 3,
+];
+const STAKE_STATE_BINCODE: &[u8] = &[
+    1, 0, 0, 0, 128, 213, 34, 0, 0, 0, 0, 0, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6,
+    6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7,
+    7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 0, 241, 83, 101, 0, 0, 0, 0, 244, 1, 0, 0, 0, 0, 0,
+    0, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8,
+    8,
+];
+const CLOCK_BINCODE: &[u8] = &[
+    128, 178, 230, 14, 0, 0, 0, 0, 240, 201, 83, 101, 0, 0, 0, 0, 66, 2, 0, 0, 0, 0, 0, 0, 67, 2,
+    0, 0, 0, 0, 0, 0, 0, 241, 83, 101, 0, 0, 0, 0,
+];
+const RENT_BINCODE: &[u8] = &[152, 13, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 64, 50];
+const EPOCH_SCHEDULE_BINCODE: &[u8] = &[
+    128, 151, 6, 0, 0, 0, 0, 0, 128, 151, 6, 0, 0, 0, 0, 0, 1, 14, 0, 0, 0, 0, 0, 0, 0, 224, 255,
+    7, 0, 0, 0, 0, 0,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vectors() {
+        assert_bincode_vector(&pubkey());
+        assert_bincode_vector(&hash());
+        assert_bincode_vector(&instruction());
+        assert_bincode_vector(&message());
+        assert_bincode_vector(&stake_state());
+        assert_bincode_vector(&clock());
+        assert_bincode_vector(&rent());
+        assert_bincode_vector(&epoch_schedule());
+    }
+
+    #[test]
+    fn test_vectors_match_borsh() {
+        let stake_state = stake_state();
+        assert_eq!(
+            borsh::to_vec(&stake_state.value).unwrap(),
+            stake_state.bincode
+        );
+        let pubkey = pubkey();
+        assert_eq!(borsh::to_vec(&pubkey.value).unwrap(), pubkey.bincode);
+    }
+}
@@ -0,0 +1,125 @@
+//! The source of wall-clock time for hosts and tests.
+//!
+//! Genesis creation and test harnesses read the current time with [`now`]
+//! rather than from the system directly. Swapping in a [`MockTimeSource`] with
+//! [`set_time_source`] then makes simulations reproducible, however many
+//! epochs they span. Timestamps taken by the validator itself, such as
+//! `timing::timestamp`, always come from the system clock.
+//!
+//! There is no default source on-chain, where programs must use the `Clock`
+//! sysvar, so only the [`TimeSource`] trait and [`MockTimeSource`] are
+//! available there.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+#[cfg(not(target_os = "solana"))]
+use {
+    crate::sync::LazyLock,
+    std::sync::{Arc, RwLock},
+};
+
+/// Something that tells the time.
+pub trait TimeSource: Send + Sync {
+    /// Time elapsed since the Unix epoch.
+    fn now(&self) -> Duration;
+}
+
+/// The system clock.
+///
+/// On `wasm32` outside of WASI, which has no system clock, this is
+/// JavaScript's `Date.now()`.
+#[cfg(not(target_os = "solana"))]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemTimeSource;
+
+#[cfg(not(target_os = "solana"))]
+impl TimeSource for SystemTimeSource {
+    #[cfg(not(all(target_arch = "wasm32", not(target_os = "wasi"))))]
+    fn now(&self) -> Duration {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system clock is before the Unix epoch")
+    }
+
+    #[cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
+    fn now(&self) -> Duration {
+        Duration::from_millis(js_sys::Date::now() as u64)
+    }
+}
+
+/// A clock that only moves when told to, with millisecond resolution.
+#[derive(Debug, Default)]
+pub struct MockTimeSource {
+    millis: AtomicU64,
+}
+
+impl MockTimeSource {
+    /// A clock reading `now`.
+    pub fn new(now: Duration) -> Self {
+        Self {
+            millis: AtomicU64::new(now.as_millis() as u64),
+        }
+    }
+
+    pub fn set(&self, now: Duration) {
+        self.millis.store(now.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.millis
+            .fetch_add(duration.as_millis() as u64, Ordering::Relaxed);
+    }
+}
+
+impl TimeSource for MockTimeSource {
+    fn now(&self) -> Duration {
+        Duration::from_millis(self.millis.load(Ordering::Relaxed))
+    }
+}
+
+#[cfg(not(target_os = "solana"))]
+static TIME_SOURCE: LazyLock<RwLock<Arc<dyn TimeSource>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(SystemTimeSource)));
+
+/// Replaces the process-wide time source, returning the previous one.
+///
+/// The [`SystemTimeSource`] is used by default.
+#[cfg(not(target_os = "solana"))]
+pub fn set_time_source(time_source: Arc<dyn TimeSource>) -> Arc<dyn TimeSource> {
+    std::mem::replace(&mut TIME_SOURCE.write().unwrap(), time_source)
+}
+
+/// Time elapsed since the Unix epoch, according to the process-wide time
+/// source.
+#[cfg(not(target_os = "solana"))]
+pub fn now() -> Duration {
+    TIME_SOURCE.read().unwrap().now()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_time_source() {
+        let mock = MockTimeSource::new(Duration::from_secs(10));
+        assert_eq!(mock.now(), Duration::from_secs(10));
+        mock.advance(Duration::from_millis(1500));
+        assert_eq!(mock.now(), Duration::from_millis(11_500));
+        mock.set(Duration::from_secs(1));
+        assert_eq!(mock.now(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_set_time_source() {
+        let mock = Arc::new(MockTimeSource::new(Duration::from_secs(42)));
+        let previous = set_time_source(mock.clone());
+        assert_eq!(now(), Duration::from_secs(42));
+        mock.advance(Duration::from_secs(1));
+        assert_eq!(now(), Duration::from_secs(43));
+        set_time_source(previous);
+        assert!(now() > Duration::from_secs(43));
+    }
+}
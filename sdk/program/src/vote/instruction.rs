@@ -3,6 +3,7 @@
 use {
     crate::{
         clock::{Slot, UnixTimestamp},
+        compute_units::BuiltinComputeUnits,
         hash::Hash,
         instruction::{AccountMeta, Instruction},
         pubkey::Pubkey,
@@ -148,6 +149,10 @@ pub enum VoteInstruction {
     ),
 }
 
+impl BuiltinComputeUnits for VoteInstruction {
+    const DEFAULT_COMPUTE_UNITS: u64 = 2_100;
+}
+
 impl VoteInstruction {
     pub fn is_simple_vote(&self) -> bool {
         matches!(
@@ -0,0 +1,86 @@
+//! Backends for embedding solana-program in a WASI host.
+//!
+//! `wasm32-unknown-unknown` builds reach the outside world through
+//! JavaScript, see the `wasm` module. WASI builds have no JavaScript, so this
+//! module sends `log` output to stderr instead. Test harnesses can install
+//! [`WallClockSyscallStubs`] to fill in the `Clock` sysvar's timestamps from
+//! the process-wide [`time_source`](crate::time_source), which is the WASI
+//! clock by default; the default stubs still report the sysvar as
+//! unsupported. The `#[wasm_bindgen]` bindings are stubbed out on WASI,
+//! leaving the module without imports a WASI runtime can't satisfy.
+
+#![cfg(all(target_arch = "wasm32", target_os = "wasi"))]
+
+use {
+    crate::{
+        clock::{Clock, UnixTimestamp},
+        entrypoint::SUCCESS,
+        program_stubs::SyscallStubs,
+        sync::Once,
+        time_source,
+    },
+    std::io::Write,
+};
+
+struct StderrLogger;
+
+impl log::Log for StderrLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {
+        let _ = std::io::stderr().flush();
+    }
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// Initialize logging to stderr, the WASI counterpart of
+/// `wasm::solana_program_init`.
+pub fn solana_program_init() {
+    static INIT: Once = Once::new();
+
+    INIT.call_once(|| {
+        // Fails only if the host already installed its own logger
+        if log::set_logger(&LOGGER).is_ok() {
+            log::set_max_level(log::LevelFilter::Info);
+        }
+    });
+}
+
+/// Seconds since the Unix epoch, according to the process-wide time source.
+pub fn wall_clock_unix_timestamp() -> UnixTimestamp {
+    time_source::now().as_secs() as UnixTimestamp
+}
+
+/// A default `Clock` whose timestamps are the current time.
+pub fn wall_clock() -> Clock {
+    let now = wall_clock_unix_timestamp();
+    Clock {
+        epoch_start_timestamp: now,
+        unix_timestamp: now,
+        ..Clock::default()
+    }
+}
+
+/// Syscall stubs for test harnesses, answering `Clock::get` with
+/// [`wall_clock`]. Install them with
+/// [`set_syscall_stubs`](crate::program_stubs::set_syscall_stubs).
+pub struct WallClockSyscallStubs;
+
+impl SyscallStubs for WallClockSyscallStubs {
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe {
+            *(var_addr as *mut Clock) = wall_clock();
+        }
+        SUCCESS
+    }
+}
@@ -0,0 +1,13 @@
+//! Memo program Javascript interface
+#![cfg(target_arch = "wasm32")]
+#![allow(non_snake_case)]
+use {
+    crate::{instruction::Instruction, memo::build_memo},
+    wasm_bindgen::prelude::*,
+};
+
+/// Build an unsigned memo instruction
+#[wasm_bindgen]
+pub fn buildMemo(memo: &str) -> Instruction {
+    build_memo(memo.as_bytes(), &[])
+}
@@ -1,16 +1,18 @@
 //! solana-program Javascript interface
-#![cfg(target_arch = "wasm32")]
+#![cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
 use wasm_bindgen::prelude::*;
 
 pub mod hash;
 pub mod instructions;
+pub mod memo;
+pub mod native_token;
 pub mod pubkey;
 pub mod system_instruction;
 
 /// Initialize Javascript logging and panic handler
 #[wasm_bindgen]
 pub fn solana_program_init() {
-    use std::sync::Once;
+    use crate::sync::Once;
     static INIT: Once = Once::new();
 
     INIT.call_once(|| {
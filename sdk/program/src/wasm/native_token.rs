@@ -0,0 +1,18 @@
+//! Native token Javascript interface
+#![cfg(target_arch = "wasm32")]
+#![allow(non_snake_case)]
+use {crate::native_token::*, wasm_bindgen::prelude::*};
+
+/// Exactly convert a decimal SOL string into lamports
+///
+/// Returns `undefined` if the string is not a valid SOL amount.
+#[wasm_bindgen]
+pub fn solStrToLamports(sol_str: &str) -> Option<u64> {
+    sol_str_to_lamports(sol_str)
+}
+
+/// Format lamports as a `◎` prefixed SOL amount with all nine decimal places
+#[wasm_bindgen]
+pub fn lamportsToSolString(lamports: u64) -> String {
+    Sol(lamports).to_string()
+}
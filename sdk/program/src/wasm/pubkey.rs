@@ -64,6 +64,11 @@ impl Pubkey {
         self.to_string()
     }
 
+    /// Return the abbreviated base58 representation, e.g. `4Nd1…Wxyz`
+    pub fn toShortString(&self) -> String {
+        self.to_short_string()
+    }
+
     /// Check if a `Pubkey` is on the ed25519 curve.
     pub fn isOnCurve(&self) -> bool {
         self.is_on_curve()
@@ -47,6 +47,26 @@ impl SystemInstruction {
         transfer(from_pubkey, to_pubkey, lamports)
     }
 
+    /// Transfers from `from_pubkey` to each of `to_pubkeys`, given as base58
+    /// strings or byte arrays, of the matching `lamports`
+    pub fn transferMany(
+        from_pubkey: &Pubkey,
+        to_pubkeys: Box<[JsValue]>,
+        lamports: Box<[u64]>,
+    ) -> Result<js_sys::Array, JsValue> {
+        if to_pubkeys.len() != lamports.len() {
+            return Err("Mismatched pubkey and lamports lengths".into());
+        }
+        let to_lamports = to_pubkeys
+            .iter()
+            .zip(lamports.iter())
+            .map(|(to_pubkey, lamports)| Ok((Pubkey::constructor(to_pubkey.clone())?, *lamports)))
+            .collect::<Result<Vec<_>, JsValue>>()?;
+        let instructions = try_transfer_many(from_pubkey, &to_lamports)
+            .map_err(|err| JsValue::from(err.to_string()))?;
+        Ok(instructions.into_iter().map(JsValue::from).collect())
+    }
+
     pub fn transferWithSeed(
         from_pubkey: &Pubkey,
         from_base: &Pubkey,
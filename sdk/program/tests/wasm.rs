@@ -0,0 +1,123 @@
+//! Tests for the Javascript interface.
+//!
+//! Run with `npm run test:wasm` under node, or `npm run test:wasm:browser` in
+//! headless browsers.
+#![cfg(target_arch = "wasm32")]
+#![allow(non_snake_case)]
+
+use {
+    js_sys::{Array, Uint8Array},
+    solana_program::{
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_instruction,
+        wasm::instructions::Instructions,
+    },
+    wasm_bindgen::JsValue,
+    wasm_bindgen_test::*,
+};
+
+fn bytes_to_array(bytes: &[u8]) -> Array {
+    bytes.iter().map(|byte| JsValue::from(*byte)).collect()
+}
+
+#[wasm_bindgen_test]
+fn hash_round_trip() {
+    let hash = Hash::new_from_array([7; 32]);
+
+    let from_string = Hash::constructor(hash.toString().into()).unwrap();
+    assert!(from_string.equals(&hash));
+    let from_uint8_array = Hash::constructor(Uint8Array::from(&hash.toBytes()[..]).into()).unwrap();
+    assert_eq!(from_uint8_array, hash);
+    let from_array = Hash::constructor(bytes_to_array(&hash.toBytes()).into()).unwrap();
+    assert_eq!(from_array, hash);
+    assert_eq!(
+        Hash::constructor(JsValue::UNDEFINED).unwrap(),
+        Hash::default()
+    );
+}
+
+#[wasm_bindgen_test]
+fn hash_invalid() {
+    assert!(Hash::constructor("0x300000000000000000000000000000000000".into()).is_err());
+    assert!(Hash::constructor(
+        bytes_to_array(&[0; 32])
+            .concat(&Array::of1(&256.into()))
+            .into()
+    )
+    .is_err());
+    assert!(Hash::constructor(JsValue::from(3)).is_err());
+}
+
+#[wasm_bindgen_test]
+fn pubkey_round_trip() {
+    let pubkey = Pubkey::new_from_array([3; 32]);
+
+    let from_string = Pubkey::constructor(pubkey.toString().into()).unwrap();
+    assert!(from_string.equals(&pubkey));
+    let from_uint8_array =
+        Pubkey::constructor(Uint8Array::from(&pubkey.toBytes()[..]).into()).unwrap();
+    assert_eq!(from_uint8_array, pubkey);
+    let from_array = Pubkey::constructor(bytes_to_array(&pubkey.toBytes()).into()).unwrap();
+    assert_eq!(from_array, pubkey);
+    assert_eq!(
+        Pubkey::constructor(JsValue::UNDEFINED).unwrap(),
+        Pubkey::default()
+    );
+}
+
+#[wasm_bindgen_test]
+fn pubkey_invalid() {
+    assert!(Pubkey::constructor("0x300000000000000000000000000000000000".into()).is_err());
+    assert!(Pubkey::constructor(Uint8Array::from(&[0u8; 33][..]).into()).is_err());
+    assert!(Pubkey::constructor(bytes_to_array(&[0; 31]).into()).is_err());
+    assert!(Pubkey::constructor(JsValue::from(3)).is_err());
+}
+
+#[wasm_bindgen_test]
+fn pubkey_derivation() {
+    let base = Pubkey::new_from_array([1; 32]);
+    let owner = Pubkey::new_from_array([2; 32]);
+    assert_eq!(
+        Pubkey::createWithSeed(&base, "seed", &owner).unwrap(),
+        Pubkey::create_with_seed(&base, "seed", &owner).unwrap()
+    );
+
+    let seeds = [b"seed".as_slice(), base.as_ref()];
+    let (address, bump_seed) = Pubkey::find_program_address(&seeds, &owner);
+    let js_seeds = || -> Box<[JsValue]> {
+        seeds
+            .iter()
+            .map(|seed| Uint8Array::from(*seed).into())
+            .collect()
+    };
+    let result: Array = Pubkey::findProgramAddress(js_seeds(), &owner)
+        .unwrap()
+        .into();
+    assert_eq!(result.get(1).as_f64(), Some(f64::from(bump_seed)));
+
+    let mut seeds_with_bump = js_seeds().into_vec();
+    seeds_with_bump.push(Uint8Array::from(&[bump_seed][..]).into());
+    assert_eq!(
+        Pubkey::createProgramAddress(seeds_with_bump.into_boxed_slice(), &owner).unwrap(),
+        address
+    );
+    assert!(Pubkey::createProgramAddress(Box::new([JsValue::from(1)]), &owner).is_err());
+}
+
+#[wasm_bindgen_test]
+fn instructions_round_trip() {
+    let from = Pubkey::new_from_array([4; 32]);
+    let to = Pubkey::new_from_array([5; 32]);
+    let expected = vec![
+        system_instruction::transfer(&from, &to, 42),
+        Instruction::new_with_bytes(to, &[1, 2, 3], vec![AccountMeta::new(from, true)]),
+    ];
+
+    let mut instructions = Instructions::constructor();
+    for instruction in &expected {
+        instructions.push(instruction.clone());
+    }
+    assert_eq!(Vec::<Instruction>::from(instructions), expected);
+}
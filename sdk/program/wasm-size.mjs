@@ -0,0 +1,13 @@
+// Reports the size of a wasm bundle built by `npm run size`, and fails if it
+// exceeds `WASM_SIZE_LIMIT` bytes when that is set.
+import { statSync } from "fs";
+
+const path = process.argv[2];
+const size = statSync(path).size;
+console.log(`${path}: ${size} bytes`);
+
+const limit = process.env.WASM_SIZE_LIMIT;
+if (limit !== undefined && size > Number(limit)) {
+  console.error(`exceeds WASM_SIZE_LIMIT of ${limit} bytes`);
+  process.exit(1);
+}
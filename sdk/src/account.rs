@@ -657,6 +657,88 @@ impl AccountSharedData {
     }
 }
 
+/// Builds accounts for tests and fixtures.
+///
+/// Starts from an empty, non-executable account with no lamports, owned by
+/// the system program.
+///
+/// ```
+/// # use solana_sdk::{account::{AccountBuilder, ReadableAccount}, pubkey::Pubkey};
+/// let owner = Pubkey::new_unique();
+/// let account = AccountBuilder::new()
+///     .lamports(42)
+///     .owner(&owner)
+///     .space(8)
+///     .state(&7u32)
+///     .build();
+/// assert_eq!(account.lamports(), 42);
+/// assert_eq!(account.owner(), &owner);
+/// assert_eq!(account.data(), &[7, 0, 0, 0, 0, 0, 0, 0]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AccountBuilder {
+    account: Account,
+}
+
+impl AccountBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn lamports(mut self, lamports: u64) -> Self {
+        self.account.lamports = lamports;
+        self
+    }
+
+    pub fn owner(mut self, owner: &Pubkey) -> Self {
+        self.account.owner = *owner;
+        self
+    }
+
+    pub fn data(mut self, data: Vec<u8>) -> Self {
+        self.account.data = data;
+        self
+    }
+
+    /// Resize the data to `space` bytes, zero filling any new bytes
+    pub fn space(mut self, space: usize) -> Self {
+        self.account.data.resize(space, 0);
+        self
+    }
+
+    /// Serialize `state` with bincode into the start of the data, growing
+    /// the data if it is too short. Set [`space`](Self::space) first to
+    /// reserve a fixed size.
+    ///
+    /// Panics if `state` cannot be serialized.
+    pub fn state<T: serde::Serialize>(mut self, state: &T) -> Self {
+        let serialized = bincode::serialize(state).unwrap();
+        if self.account.data.len() < serialized.len() {
+            self.account.data.resize(serialized.len(), 0);
+        }
+        self.account.data[..serialized.len()].copy_from_slice(&serialized);
+        self
+    }
+
+    pub fn executable(mut self, executable: bool) -> Self {
+        self.account.executable = executable;
+        self
+    }
+
+    pub fn rent_epoch(mut self, rent_epoch: Epoch) -> Self {
+        self.account.rent_epoch = rent_epoch;
+        self
+    }
+
+    pub fn build(self) -> AccountSharedData {
+        self.account.into()
+    }
+
+    pub fn build_account(self) -> Account {
+        self.account
+    }
+}
+
 pub type InheritableAccountFields = (u64, Epoch);
 pub const DUMMY_INHERITABLE_ACCOUNT_FIELDS: InheritableAccountFields = (1, INITIAL_RENT_EPOCH);
 
@@ -789,6 +871,29 @@ pub mod tests {
         assert_eq!(account1.owner(), &key2);
     }
 
+    #[test]
+    fn test_account_builder() {
+        let owner = Pubkey::new_unique();
+        let account = AccountBuilder::new()
+            .lamports(1)
+            .owner(&owner)
+            .space(2)
+            .executable(true)
+            .rent_epoch(4)
+            .build();
+        let (_, expected) = make_two_accounts(&owner);
+        assert_eq!(account, expected);
+
+        // state grows the data if it doesn't fit, and leaves any tail intact
+        let account = AccountBuilder::new().state(&1u16).build_account();
+        assert_eq!(account.data, vec![1, 0]);
+        let account = AccountBuilder::new()
+            .data(vec![9; 4])
+            .state(&1u8)
+            .build_account();
+        assert_eq!(account.data, vec![1, 9, 9, 9]);
+    }
+
     #[test]
     fn test_account_set_data_from_slice() {
         let key = Pubkey::new_unique();
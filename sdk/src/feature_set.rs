@@ -780,6 +780,30 @@ pub mod deprecate_unused_legacy_vote_plumbing {
     solana_sdk::declare_id!("6Uf8S75PVh91MYgPQSHnjRAPQq6an5BDv9vomrCwDqLe");
 }
 
+pub mod reject_noncanonical_stake_instruction_data {
+    solana_sdk::declare_id!("2ergpDPN8MX411i7gse8ALRpPucvV96jZoLLcyuwbvu6");
+}
+
+pub mod stake_initialize_if_needed_instruction {
+    solana_sdk::declare_id!("ASMHFRv8rLDW92PjWcBo5g14guPF3BGRoREPzPfQUmtV");
+}
+
+pub mod stake_update_custodian_instruction {
+    solana_sdk::declare_id!("GsGdw7Yoeeg6pLCF7X5WnMLoyMyK79RWMyqgzpnSHkkh");
+}
+
+pub mod sanitize_stake_instructions {
+    solana_sdk::declare_id!("D1ziyqKDHWs1qsZDWL5Baqpe8dNR6sCoEapL6b6wHkgi");
+}
+
+pub mod precompile_builtin_processors {
+    solana_sdk::declare_id!("FVwH6XfDpUyCgQjRyx175ShuEpGeDVJuZgVWa7GVgW9w");
+}
+
+pub mod stake_replenish_rewards_pool_instruction {
+    solana_sdk::declare_id!("EZ62ZSZB6Tc2NfS9js7Ap29cymtYceuyz857xDpmbfEs");
+}
+
 lazy_static! {
     /// Map of feature identifiers to user-visible description
     pub static ref FEATURE_NAMES: HashMap<Pubkey, &'static str> = [
@@ -970,6 +994,12 @@ lazy_static! {
         (enable_chained_merkle_shreds::id(), "Enable chained Merkle shreds #34916"),
         (remove_rounding_in_fee_calculation::id(), "Removing unwanted rounding in fee calculation #34982"),
         (deprecate_unused_legacy_vote_plumbing::id(), "Deprecate unused legacy vote tx plumbing"),
+        (reject_noncanonical_stake_instruction_data::id(), "Reject stake instruction data with trailing bytes"),
+        (stake_initialize_if_needed_instruction::id(), "Enable the idempotent stake InitializeIfNeeded instruction"),
+        (stake_update_custodian_instruction::id(), "Enable the stake UpdateCustodian instruction"),
+        (sanitize_stake_instructions::id(), "Reject out of bounds stake instruction arguments before processing"),
+        (precompile_builtin_processors::id(), "Register native processors for the precompiled programs"),
+        (stake_replenish_rewards_pool_instruction::id(), "Enable the stake ReplenishRewardsPool instruction"),
         /*************** ADD NEW FEATURES HERE ***************/
     ]
     .iter()
@@ -16,7 +16,7 @@ use {
         rent::Rent,
         shred_version::compute_shred_version,
         signature::{Keypair, Signer},
-        system_program,
+        system_program, time_source,
         timing::years_as_slots,
     },
     bincode::{deserialize, serialize},
@@ -29,7 +29,6 @@ use {
         io::Write,
         path::{Path, PathBuf},
         str::FromStr,
-        time::{SystemTime, UNIX_EPOCH},
     },
 };
 
@@ -131,10 +130,7 @@ pub fn create_genesis_config(lamports: u64) -> (GenesisConfig, Keypair) {
 impl Default for GenesisConfig {
     fn default() -> Self {
         Self {
-            creation_time: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs() as UnixTimestamp,
+            creation_time: time_source::now().as_secs() as UnixTimestamp,
             accounts: BTreeMap::default(),
             native_instruction_processors: Vec::default(),
             rewards_pools: BTreeMap::default(),
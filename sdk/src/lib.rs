@@ -41,21 +41,26 @@ pub use signer::signers;
 // confusing duplication in the docs due to a rustdoc bug. #26211
 #[allow(deprecated)]
 pub use solana_program::address_lookup_table_account;
+#[cfg(feature = "pay")]
+pub use solana_program::pay;
 #[cfg(not(target_os = "solana"))]
 pub use solana_program::program_stubs;
 pub use solana_program::{
-    account_info, address_lookup_table, alt_bn128, big_mod_exp, blake3, borsh, borsh0_10, borsh0_9,
-    borsh1, bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable, clock, config,
-    custom_heap_default, custom_panic_default, debug_account_data, declare_deprecated_sysvar_id,
-    declare_sysvar_id, decode_error, ed25519_program, epoch_rewards, epoch_schedule,
-    fee_calculator, impl_sysvar_get, incinerator, instruction, keccak, lamports,
-    loader_instruction, loader_upgradeable_instruction, loader_v4, loader_v4_instruction, message,
-    msg, native_token, nonce, poseidon, program, program_error, program_memory, program_option,
+    account_info, account_spec, address_lookup_table, alt_bn128, big_mod_exp, blake3, borsh,
+    borsh0_10, borsh0_9, borsh1, borsh_compat, bpf_loader, bpf_loader_deprecated,
+    bpf_loader_upgradeable, clock, compute_units, config, custom_heap, custom_heap_default,
+    custom_panic_default, debug_account_data, declare_deprecated_sysvar_id, declare_sysvar_id,
+    decode_error, discriminator, ed25519_program, epoch_rewards, epoch_schedule, fee_calculator,
+    impl_sysvar_get, incinerator, instruction, keccak, lamports, loader_instruction,
+    loader_upgradeable_instruction, loader_v4, loader_v4_instruction, memo, message, msg,
+    native_token, nonce, poseidon, program, program_error, program_memory, program_option,
     program_pack, rent, sanitize, sdk_ids, secp256k1_program, secp256k1_recover, serde_varint,
     serialize_utils, short_vec, slot_hashes, slot_history, stable_layout, stake, stake_history,
-    syscalls, system_instruction, system_program, sysvar, unchecked_div_by_const, vote,
-    wasm_bindgen,
+    sync, syscalls, system_instruction, system_program, sysvar, time_source,
+    unchecked_div_by_const, vote, wasm_bindgen,
 };
+#[cfg(any(not(target_os = "solana"), feature = "custom-entropy"))]
+pub use solana_program::{entropy, register_entropy_backend};
 
 pub mod account;
 pub mod account_utils;
@@ -98,6 +103,7 @@ pub mod reserved_account_keys;
 pub mod reward_info;
 pub mod reward_type;
 pub mod rpc_port;
+pub mod rpc_types;
 pub mod secp256k1_instruction;
 pub mod shred_version;
 pub mod signature;
@@ -184,6 +190,26 @@ extern crate solana_frozen_abi_macro;
 
 #[cfg(test)]
 mod tests {
+    use crate::{
+        borsh1::ConstPackedLen,
+        discriminator::{account_discriminator, AccountDiscriminator},
+        pubkey::Pubkey,
+    };
+
+    #[test]
+    fn test_derive_crate_path() {
+        #[derive(AccountDiscriminator, ConstPackedLen)]
+        #[account_discriminator(crate = "solana_sdk")]
+        #[const_packed_len(crate = "solana_sdk")]
+        enum Counter {
+            _Unset,
+            _Set { _owner: Pubkey, _count: u64 },
+        }
+
+        assert_eq!(Counter::PACKED_LEN, 1 + 32 + 8);
+        assert_eq!(Counter::DISCRIMINATOR, account_discriminator("Counter"));
+    }
+
     #[test]
     fn test_saturating_add_assign() {
         let mut i = 0u64;
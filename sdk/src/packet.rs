@@ -14,6 +14,11 @@ use {
 
 #[cfg(test)]
 static_assertions::const_assert_eq!(PACKET_DATA_SIZE, 1232);
+#[cfg(test)]
+static_assertions::const_assert_eq!(
+    PACKET_DATA_SIZE,
+    solana_program::message::MAX_COMPILED_INSTRUCTION_DATA_LEN
+);
 /// Maximum over-the-wire size of a Transaction
 ///   1280 is IPv6 minimum MTU
 ///   40 bytes is the size of the IPv6 header
@@ -8,11 +8,12 @@ use {
         pubkey::Pubkey,
     },
     lazy_static::lazy_static,
+    num_derive::{FromPrimitive, ToPrimitive},
     thiserror::Error,
 };
 
 /// Precompile errors
-#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[derive(Error, Debug, Clone, PartialEq, Eq, FromPrimitive, ToPrimitive)]
 pub enum PrecompileError {
     #[error("public key is not valid")]
     InvalidPublicKey,
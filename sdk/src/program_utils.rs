@@ -1,4 +1,4 @@
-//! Contains a single utility function for deserializing from [bincode].
+//! Utility functions for deserializing instruction data from [bincode].
 //!
 //! [bincode]: https://docs.rs/bincode
 
@@ -16,6 +16,19 @@ where
     )
 }
 
+/// Like [`limited_deserialize`], but rejecting data with trailing bytes after
+/// the deserialized value. See
+/// [`solana_program::program_utils::deserialize_checked`].
+pub fn deserialize_checked<T>(instruction_data: &[u8]) -> Result<T, InstructionError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    solana_program::program_utils::deserialize_checked(
+        instruction_data,
+        crate::packet::PACKET_DATA_SIZE as u64,
+    )
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -0,0 +1,355 @@
+//! Minimal JSON-RPC request and response types.
+//!
+//! These cover the handful of methods a light client needs to build, sign and
+//! submit a transaction: `getAccountInfo`, `getLatestBlockhash`,
+//! `sendTransaction` and `getStakeActivation`. The wire format matches the
+//! types in `solana-rpc-client-api`, so responses from a validator deserialize
+//! the same way, but without pulling in `reqwest` or the transaction-status
+//! crates. This makes them usable from any HTTP transport, including `fetch`
+//! in the browser.
+
+#![cfg(feature = "full")]
+
+use {
+    crate::{
+        account::Account,
+        clock::{Epoch, Slot},
+        commitment_config::{CommitmentConfig, CommitmentLevel},
+        hash::Hash,
+        pubkey::Pubkey,
+    },
+    base64::{prelude::BASE64_STANDARD, Engine},
+    serde_json::{json, Value},
+    std::{fmt, str::FromStr},
+    thiserror::Error,
+};
+
+pub const JSON_RPC_VERSION: &str = "2.0";
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum RpcRequest {
+    GetAccountInfo,
+    GetLatestBlockhash,
+    GetStakeActivation,
+    SendTransaction,
+}
+
+impl fmt::Display for RpcRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let method = match self {
+            RpcRequest::GetAccountInfo => "getAccountInfo",
+            RpcRequest::GetLatestBlockhash => "getLatestBlockhash",
+            RpcRequest::GetStakeActivation => "getStakeActivation",
+            RpcRequest::SendTransaction => "sendTransaction",
+        };
+        write!(f, "{method}")
+    }
+}
+
+impl RpcRequest {
+    pub fn build_request_json(self, id: u64, params: Value) -> Value {
+        json!({
+           "jsonrpc": JSON_RPC_VERSION,
+           "id": id,
+           "method": format!("{self}"),
+           "params": params,
+        })
+    }
+
+    /// Build a `getAccountInfo` request asking for base64 encoded data.
+    pub fn get_account_info(id: u64, pubkey: &Pubkey, commitment: CommitmentConfig) -> Value {
+        RpcRequest::GetAccountInfo.build_request_json(
+            id,
+            json!([
+                pubkey.to_string(),
+                {
+                    "encoding": "base64",
+                    "commitment": commitment.commitment,
+                },
+            ]),
+        )
+    }
+
+    /// Build a `getLatestBlockhash` request.
+    pub fn get_latest_blockhash(id: u64, commitment: CommitmentConfig) -> Value {
+        RpcRequest::GetLatestBlockhash.build_request_json(id, json!([commitment]))
+    }
+
+    /// Build a `getStakeActivation` request for the current epoch.
+    pub fn get_stake_activation(id: u64, pubkey: &Pubkey, commitment: CommitmentConfig) -> Value {
+        RpcRequest::GetStakeActivation
+            .build_request_json(id, json!([pubkey.to_string(), commitment]))
+    }
+
+    /// Build a `sendTransaction` request from a bincode serialized
+    /// transaction, legacy or versioned.
+    pub fn send_transaction(
+        id: u64,
+        serialized_transaction: &[u8],
+        config: RpcSendTransactionConfig,
+    ) -> Value {
+        let mut config = json!(config);
+        config["encoding"] = json!("base64");
+        RpcRequest::SendTransaction.build_request_json(
+            id,
+            json!([BASE64_STANDARD.encode(serialized_transaction), config]),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSendTransactionConfig {
+    #[serde(default)]
+    pub skip_preflight: bool,
+    pub preflight_commitment: Option<CommitmentLevel>,
+    pub max_retries: Option<usize>,
+    pub min_context_slot: Option<Slot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcErrorObject {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl fmt::Display for RpcErrorObject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RPC error {}: {}", self.code, self.message)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RpcTypesError {
+    #[error("{0}")]
+    Rpc(RpcErrorObject),
+    #[error("response contains neither a result nor an error")]
+    MissingResult,
+    #[error("failed to decode response: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("failed to decode {0}")]
+    InvalidValue(&'static str),
+}
+
+/// The JSON-RPC envelope around every response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcResponse<T> {
+    pub jsonrpc: String,
+    pub id: u64,
+    #[serde(default = "Option::default", skip_serializing_if = "Option::is_none")]
+    pub result: Option<T>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcErrorObject>,
+}
+
+impl<T> RpcResponse<T> {
+    pub fn into_result(self) -> Result<T, RpcTypesError> {
+        match (self.result, self.error) {
+            (_, Some(error)) => Err(RpcTypesError::Rpc(error)),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(RpcTypesError::MissingResult),
+        }
+    }
+}
+
+impl<T: serde::de::DeserializeOwned> RpcResponse<T> {
+    /// Parse a response body and unwrap its result.
+    pub fn parse(body: &[u8]) -> Result<T, RpcTypesError> {
+        serde_json::from_slice::<Self>(body)?.into_result()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcResponseContext {
+    pub slot: Slot,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<String>,
+}
+
+/// The `{ context, value }` wrapper used by most methods.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcContextResult<T> {
+    pub context: RpcResponseContext,
+    pub value: T,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBlockhash {
+    pub blockhash: String,
+    pub last_valid_block_height: u64,
+}
+
+impl RpcBlockhash {
+    pub fn hash(&self) -> Result<Hash, RpcTypesError> {
+        Hash::from_str(&self.blockhash).map_err(|_| RpcTypesError::InvalidValue("blockhash"))
+    }
+}
+
+/// An account as returned by `getAccountInfo` with base64 encoding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccount {
+    pub lamports: u64,
+    /// `[data, encoding]`
+    pub data: (String, String),
+    pub owner: String,
+    pub executable: bool,
+    pub rent_epoch: Epoch,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub space: Option<u64>,
+}
+
+impl RpcAccount {
+    pub fn decode(&self) -> Result<Account, RpcTypesError> {
+        let (data, encoding) = &self.data;
+        if encoding != "base64" {
+            return Err(RpcTypesError::InvalidValue("account data encoding"));
+        }
+        Ok(Account {
+            lamports: self.lamports,
+            data: BASE64_STANDARD
+                .decode(data)
+                .map_err(|_| RpcTypesError::InvalidValue("account data"))?,
+            owner: Pubkey::from_str(&self.owner)
+                .map_err(|_| RpcTypesError::InvalidValue("account owner"))?,
+            executable: self.executable,
+            rent_epoch: self.rent_epoch,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum StakeActivationState {
+    Activating,
+    Active,
+    Deactivating,
+    Inactive,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcStakeActivation {
+    pub state: StakeActivationState,
+    pub active: u64,
+    pub inactive: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_requests() {
+        let pubkey = Pubkey::new_unique();
+        assert_eq!(
+            RpcRequest::get_account_info(1, &pubkey, CommitmentConfig::confirmed()),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "getAccountInfo",
+                "params": [
+                    pubkey.to_string(),
+                    {"encoding": "base64", "commitment": "confirmed"},
+                ],
+            })
+        );
+        assert_eq!(
+            RpcRequest::get_latest_blockhash(2, CommitmentConfig::finalized())["params"],
+            json!([{"commitment": "finalized"}])
+        );
+        assert_eq!(
+            RpcRequest::get_stake_activation(3, &pubkey, CommitmentConfig::processed())["params"],
+            json!([pubkey.to_string(), {"commitment": "processed"}])
+        );
+        assert_eq!(
+            RpcRequest::send_transaction(
+                4,
+                &[1, 2, 3],
+                RpcSendTransactionConfig {
+                    skip_preflight: true,
+                    ..RpcSendTransactionConfig::default()
+                }
+            )["params"],
+            json!([
+                "AQID",
+                {
+                    "skipPreflight": true,
+                    "preflightCommitment": null,
+                    "encoding": "base64",
+                    "maxRetries": null,
+                    "minContextSlot": null,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_responses() {
+        let blockhash = Hash::new_unique();
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "context": {"slot": 42, "apiVersion": "2.0.0"},
+                "value": {"blockhash": blockhash.to_string(), "lastValidBlockHeight": 300},
+            },
+        })
+        .to_string();
+        let result = RpcResponse::<RpcContextResult<RpcBlockhash>>::parse(body.as_bytes()).unwrap();
+        assert_eq!(result.context.slot, 42);
+        assert_eq!(result.value.last_valid_block_height, 300);
+        assert_eq!(result.value.hash().unwrap(), blockhash);
+
+        let owner = Pubkey::new_unique();
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "result": {
+                "context": {"slot": 43},
+                "value": {
+                    "lamports": 10,
+                    "data": ["AQID", "base64"],
+                    "owner": owner.to_string(),
+                    "executable": false,
+                    "rentEpoch": 18446744073709551615u64,
+                    "space": 3,
+                },
+            },
+        })
+        .to_string();
+        let result =
+            RpcResponse::<RpcContextResult<Option<RpcAccount>>>::parse(body.as_bytes()).unwrap();
+        assert_eq!(
+            result.value.unwrap().decode().unwrap(),
+            Account {
+                lamports: 10,
+                data: vec![1, 2, 3],
+                owner,
+                executable: false,
+                rent_epoch: u64::MAX,
+            }
+        );
+
+        let body =
+            r#"{"jsonrpc":"2.0","id":3,"result":{"state":"activating","active":0,"inactive":5}}"#;
+        assert_eq!(
+            RpcResponse::<RpcStakeActivation>::parse(body.as_bytes()).unwrap(),
+            RpcStakeActivation {
+                state: StakeActivationState::Activating,
+                active: 0,
+                inactive: 5,
+            }
+        );
+
+        let body = r#"{"jsonrpc":"2.0","id":4,"error":{"code":-32002,"message":"Transaction simulation failed"}}"#;
+        match RpcResponse::<String>::parse(body.as_bytes()) {
+            Err(RpcTypesError::Rpc(error)) => assert_eq!(error.code, -32002),
+            result => panic!("unexpected result {result:?}"),
+        }
+    }
+}
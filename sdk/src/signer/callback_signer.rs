@@ -0,0 +1,140 @@
+#![cfg(feature = "full")]
+
+use {
+    crate::{
+        pubkey::Pubkey,
+        signature::Signature,
+        signer::{Signer, SignerError},
+    },
+    std::fmt,
+};
+
+type SignFn = dyn Fn(&[u8]) -> Result<Signature, SignerError> + Send + Sync;
+
+/// A `Signer` implementation that delegates signing to a callback.
+///
+/// Used for signers whose secret key lives elsewhere, such as a remote
+/// signing service or a browser wallet; the callback receives the serialized
+/// message and returns the signature produced for it. The signature is not
+/// checked against `pubkey`, so a misbehaving callback surfaces as a
+/// verification failure on the signed transaction.
+pub struct CallbackSigner {
+    pubkey: Pubkey,
+    sign_fn: Box<SignFn>,
+    is_interactive: bool,
+}
+
+impl CallbackSigner {
+    pub fn new<F>(pubkey: &Pubkey, sign_fn: F) -> Self
+    where
+        F: Fn(&[u8]) -> Result<Signature, SignerError> + Send + Sync + 'static,
+    {
+        Self {
+            pubkey: *pubkey,
+            sign_fn: Box::new(sign_fn),
+            is_interactive: false,
+        }
+    }
+
+    /// Marks the signer as requiring user interaction, e.g. a wallet prompt
+    pub fn interactive(mut self) -> Self {
+        self.is_interactive = true;
+        self
+    }
+}
+
+impl fmt::Debug for CallbackSigner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CallbackSigner")
+            .field("pubkey", &self.pubkey)
+            .field("is_interactive", &self.is_interactive)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Signer for CallbackSigner {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        Ok(self.pubkey)
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        (self.sign_fn)(message)
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.is_interactive
+    }
+}
+
+impl<T> PartialEq<T> for CallbackSigner
+where
+    T: Signer,
+{
+    fn eq(&self, other: &T) -> bool {
+        self.pubkey == other.pubkey()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            signer::keypair::Keypair,
+            system_instruction,
+            transaction::{Transaction, TransactionError},
+        },
+        std::sync::Arc,
+    };
+
+    #[test]
+    fn test_callback_signer() {
+        let keypair = Arc::new(Keypair::new());
+        let signer = CallbackSigner::new(&keypair.pubkey(), {
+            let keypair = keypair.clone();
+            move |message| keypair.try_sign_message(message)
+        });
+        assert_eq!(signer.pubkey(), keypair.pubkey());
+        assert!(!signer.is_interactive());
+        assert_eq!(
+            signer.sign_message(b"hello"),
+            keypair.sign_message(b"hello")
+        );
+        assert!(signer == *keypair);
+
+        let instruction = system_instruction::transfer(&keypair.pubkey(), &Pubkey::new_unique(), 1);
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&keypair.pubkey()),
+            &[&signer],
+            Default::default(),
+        );
+        assert!(tx.verify().is_ok());
+
+        let signer = CallbackSigner::new(&Pubkey::new_unique(), |_message| {
+            Err(SignerError::UserCancel("rejected".to_string()))
+        })
+        .interactive();
+        assert!(signer.is_interactive());
+        assert_eq!(
+            signer.try_sign_message(b"hello"),
+            Err(SignerError::UserCancel("rejected".to_string()))
+        );
+        assert_eq!(signer.sign_message(b"hello"), Signature::default());
+
+        // a callback returning someone else's signature is caught on verify
+        let signer = CallbackSigner::new(&Pubkey::new_unique(), move |message| {
+            keypair.try_sign_message(message)
+        });
+        let mut tx = Transaction::new_with_payer(
+            &[system_instruction::transfer(
+                &signer.pubkey(),
+                &Pubkey::new_unique(),
+                1,
+            )],
+            Some(&signer.pubkey()),
+        );
+        tx.sign(&[&signer], Default::default());
+        assert_eq!(tx.verify(), Err(TransactionError::SignatureFailure));
+    }
+}
@@ -6,6 +6,7 @@ use {
         pubkey::Pubkey,
         signature::Signature,
         signer::{EncodableKey, EncodableKeypair, SeedDerivable, Signer, SignerError},
+        wasm_bindgen,
     },
     ed25519_dalek::Signer as DalekSigner,
     ed25519_dalek_bip32::Error as Bip32Error,
@@ -16,7 +17,6 @@ use {
         io::{Read, Write},
         path::Path,
     },
-    wasm_bindgen::prelude::*,
 };
 
 /// A vanilla Ed25519 key pair
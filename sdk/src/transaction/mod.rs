@@ -117,12 +117,14 @@ use {
         instruction::{CompiledInstruction, Instruction},
         message::Message,
         nonce::NONCED_TX_MARKER_IX_INDEX,
+        packet::PACKET_DATA_SIZE,
         precompiles::verify_if_precompile,
         program_utils::limited_deserialize,
         pubkey::Pubkey,
         sanitize::{Sanitize, SanitizeError},
         short_vec,
         signature::{Signature, SignerError},
+        signer::presigner::Presigner,
         signers::Signers,
         wasm_bindgen,
     },
@@ -130,6 +132,7 @@ use {
     solana_program::{system_instruction::SystemInstruction, system_program},
     solana_sdk::feature_set,
     std::result,
+    thiserror::Error,
 };
 
 mod error;
@@ -147,6 +150,16 @@ pub enum TransactionVerificationMode {
 
 pub type Result<T> = result::Result<T, TransactionError>;
 
+/// A transaction too large to be sent in a single packet
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("transaction is {size} bytes, more than the {limit} that fit in a packet")]
+pub struct TransactionTooLarge {
+    /// Serialized size of the transaction
+    pub size: usize,
+    /// [`PACKET_DATA_SIZE`]
+    pub limit: usize,
+}
+
 /// An atomically-committed sequence of instructions.
 ///
 /// While [`Instruction`]s are the basic unit of computation in Solana,
@@ -168,7 +181,7 @@ pub type Result<T> = result::Result<T, TransactionError>;
 /// transaction's `Message` is both a signer and the expected fee-payer, then
 /// redundantly specifying the fee-payer is not strictly required.
 #[wasm_bindgen]
-#[frozen_abi(digest = "FZtncnS1Xk8ghHfKiXE5oGiUbw2wJhmfXQuNgQR3K6Mc")]
+#[frozen_abi(digest = "9R25FgSNNvnvkpGku1JenE61NS8iztfRYNVNCon3jgGR")]
 #[derive(Debug, PartialEq, Default, Eq, Clone, Serialize, Deserialize, AbiExample)]
 pub struct Transaction {
     /// A set of signatures of a serialized [`Message`], signed by the first
@@ -1072,6 +1085,93 @@ impl Transaction {
             .iter()
             .all(|signature| *signature != Signature::default())
     }
+
+    /// Add a signature made elsewhere by the required signer `pubkey`, as
+    /// when co-signers sign the same message separately.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `pubkey` is not a required signer of the message, or if
+    /// `signature` is not its signature of the message.
+    pub fn add_signature(
+        &mut self,
+        pubkey: &Pubkey,
+        signature: Signature,
+    ) -> result::Result<(), SignerError> {
+        let recent_blockhash = self.message.recent_blockhash;
+        self.try_partial_sign(&[&Presigner::new(pubkey, &signature)], recent_blockhash)
+    }
+
+    /// Returns the required signers whose signatures are absent or don't
+    /// verify, in signature order.
+    ///
+    /// A multiparty transaction is ready to send once this is empty.
+    pub fn missing_signers(&self) -> Vec<Pubkey> {
+        let results = self.verify_with_results();
+        self.message
+            .account_keys
+            .iter()
+            .take(self.message.header.num_required_signatures as usize)
+            .enumerate()
+            .filter(|(index, _)| !results.get(*index).copied().unwrap_or(false))
+            .map(|(_, pubkey)| *pubkey)
+            .collect()
+    }
+
+    /// Returns the serialized size of this transaction, or an error if it
+    /// doesn't fit in a single packet.
+    ///
+    /// The size is the same signed or unsigned, so this can be checked
+    /// before collecting signatures.
+    pub fn check_size(&self) -> result::Result<usize, TransactionTooLarge> {
+        let size = self
+            .message
+            .serialized_size_with_signatures(self.signatures.len());
+        if size > PACKET_DATA_SIZE {
+            Err(TransactionTooLarge {
+                size,
+                limit: PACKET_DATA_SIZE,
+            })
+        } else {
+            Ok(size)
+        }
+    }
+}
+
+/// Splits `instructions` into as few unsigned transactions as possible, in
+/// order, each fitting in a single packet.
+///
+/// Instructions are packed greedily: each transaction takes as many of the
+/// remaining instructions as fit alongside the signatures they require. Fails
+/// if a single instruction is too large for a transaction of its own.
+pub fn pack_instructions(
+    instructions: &[Instruction],
+    payer: Option<&Pubkey>,
+) -> result::Result<Vec<Transaction>, TransactionTooLarge> {
+    let packed_size = |instructions: &[Instruction]| {
+        let message = Message::new(instructions, payer);
+        message.serialized_size_with_signatures(message.header.num_required_signatures as usize)
+    };
+
+    let mut transactions = vec![];
+    let mut remaining = instructions;
+    while !remaining.is_empty() {
+        let size = packed_size(&remaining[..1]);
+        if size > PACKET_DATA_SIZE {
+            return Err(TransactionTooLarge {
+                size,
+                limit: PACKET_DATA_SIZE,
+            });
+        }
+        let mut len = 1;
+        while len < remaining.len() && packed_size(&remaining[..=len]) <= PACKET_DATA_SIZE {
+            len = len.saturating_add(1);
+        }
+        let (packed, rest) = remaining.split_at(len);
+        transactions.push(Transaction::new_with_payer(packed, payer));
+        remaining = rest;
+    }
+    Ok(transactions)
 }
 
 /// Returns true if transaction begins with an advance nonce instruction.
@@ -1114,7 +1214,7 @@ mod tests {
         crate::{
             hash::hash,
             instruction::AccountMeta,
-            signature::{Keypair, Presigner, Signer},
+            signature::{Keypair, Presigner, PresignerError, Signer},
             system_instruction,
         },
         bincode::{deserialize, serialize, serialized_size},
@@ -1641,6 +1741,114 @@ mod tests {
         assert_eq!(err, SignerError::KeypairPubkeyMismatch);
     }
 
+    #[test]
+    fn test_add_signature() {
+        let payer = Keypair::new();
+        let cosigner = Keypair::new();
+        let program_id = Pubkey::new_unique();
+        let instruction = Instruction::new_with_bincode(
+            program_id,
+            &(),
+            vec![AccountMeta::new_readonly(cosigner.pubkey(), true)],
+        );
+        let blockhash = hash(b"blockhash");
+        let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+        tx.message.recent_blockhash = blockhash;
+        assert_eq!(
+            tx.missing_signers(),
+            vec![payer.pubkey(), cosigner.pubkey()]
+        );
+
+        tx.partial_sign(&[&payer], blockhash);
+        assert_eq!(tx.missing_signers(), vec![cosigner.pubkey()]);
+
+        // signed elsewhere, over the same message
+        let message_data = tx.message_data();
+        assert_eq!(
+            tx.add_signature(&cosigner.pubkey(), payer.sign_message(&message_data)),
+            Err(PresignerError::VerificationFailure.into())
+        );
+        assert_eq!(
+            tx.add_signature(&Pubkey::new_unique(), cosigner.sign_message(&message_data)),
+            Err(SignerError::KeypairPubkeyMismatch)
+        );
+        assert!(tx.missing_signers().contains(&cosigner.pubkey()));
+        tx.add_signature(&cosigner.pubkey(), cosigner.sign_message(&message_data))
+            .unwrap();
+        assert!(tx.missing_signers().is_empty());
+        assert_eq!(tx.verify(), Ok(()));
+    }
+
+    #[test]
+    fn test_check_size() {
+        let payer = Keypair::new();
+        let to = Pubkey::new_unique();
+        let mut tx = Transaction::new_with_payer(
+            &[system_instruction::transfer(&payer.pubkey(), &to, 1)],
+            Some(&payer.pubkey()),
+        );
+        let size = tx.check_size().unwrap();
+        tx.sign(&[&payer], Hash::default());
+        assert_eq!(tx.check_size(), Ok(size));
+        assert_eq!(size, serialize(&tx).unwrap().len());
+
+        let program_id = Pubkey::new_unique();
+        let tx = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &[0; PACKET_DATA_SIZE],
+                vec![],
+            )],
+            Some(&payer.pubkey()),
+        );
+        let size = serialize(&tx).unwrap().len();
+        assert_eq!(
+            tx.check_size(),
+            Err(TransactionTooLarge {
+                size,
+                limit: PACKET_DATA_SIZE
+            })
+        );
+    }
+
+    #[test]
+    fn test_pack_instructions() {
+        let payer = Pubkey::new_unique();
+        let instructions: Vec<_> = (0..50)
+            .map(|_| system_instruction::transfer(&payer, &Pubkey::new_unique(), 1))
+            .collect();
+        let transactions = pack_instructions(&instructions, Some(&payer)).unwrap();
+        assert!(transactions.len() > 1);
+
+        let mut packed = 0;
+        for tx in &transactions {
+            assert!(tx.check_size().is_ok());
+            let len = tx.message.instructions.len();
+            // the next instruction wouldn't have fit
+            if packed + len < instructions.len() {
+                let tx =
+                    Transaction::new_with_payer(&instructions[packed..=packed + len], Some(&payer));
+                assert!(tx.check_size().is_err());
+            }
+            packed += len;
+        }
+        assert_eq!(packed, instructions.len());
+        assert_eq!(pack_instructions(&[], Some(&payer)), Ok(vec![]));
+
+        let program_id = Pubkey::new_unique();
+        assert!(matches!(
+            pack_instructions(
+                &[Instruction::new_with_bytes(
+                    program_id,
+                    &[0; PACKET_DATA_SIZE],
+                    vec![]
+                )],
+                Some(&payer),
+            ),
+            Err(TransactionTooLarge { .. })
+        ));
+    }
+
     #[test]
     fn test_unsized_signers() {
         fn instructions_to_tx(
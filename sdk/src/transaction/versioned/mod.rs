@@ -5,7 +5,12 @@
 use {
     crate::{
         hash::Hash,
-        message::VersionedMessage,
+        instruction::{AccountMeta, Instruction},
+        message::{
+            v0::LoadedAddresses, SanitizedMessage, SanitizedVersionedMessage, SimpleAddressLoader,
+            VersionedMessage,
+        },
+        packet::PACKET_DATA_SIZE,
         sanitize::SanitizeError,
         short_vec,
         signature::Signature,
@@ -13,6 +18,7 @@ use {
         signers::Signers,
         transaction::{Result, Transaction, TransactionError},
     },
+    bincode::Options,
     serde::Serialize,
     std::cmp::Ordering,
 };
@@ -138,6 +144,66 @@ impl VersionedTransaction {
         Ok(())
     }
 
+    /// Deserialize a transaction from its wire format, such as the bytes of
+    /// a packet or of a base58/base64 encoded transaction returned by RPC.
+    ///
+    /// Both legacy and versioned messages are accepted. Inputs larger than
+    /// [`PACKET_DATA_SIZE`] or with trailing bytes are rejected.
+    pub fn deserialize(bytes: &[u8]) -> bincode::Result<Self> {
+        bincode::options()
+            .with_limit(PACKET_DATA_SIZE as u64)
+            .with_fixint_encoding()
+            .reject_trailing_bytes()
+            .deserialize(bytes)
+    }
+
+    /// Returns the message instructions with their account indexes resolved
+    /// to account keys, signer and writable flags.
+    ///
+    /// `loaded_addresses` are the addresses loaded from the message's address
+    /// table lookups, in lookup order. They are ignored for legacy messages.
+    pub fn resolve_instructions(
+        &self,
+        loaded_addresses: LoadedAddresses,
+    ) -> Result<Vec<Instruction>> {
+        if let Some(lookups) = self.message.address_table_lookups() {
+            let (num_writable, num_readonly) =
+                lookups.iter().fold((0usize, 0usize), |(w, r), lookup| {
+                    (
+                        w.saturating_add(lookup.writable_indexes.len()),
+                        r.saturating_add(lookup.readonly_indexes.len()),
+                    )
+                });
+            if loaded_addresses.writable.len() != num_writable
+                || loaded_addresses.readonly.len() != num_readonly
+            {
+                return Err(TransactionError::InvalidAddressLookupTableIndex);
+            }
+        }
+
+        let message = SanitizedMessage::try_new(
+            SanitizedVersionedMessage::try_new(self.message.clone())?,
+            SimpleAddressLoader::Enabled(loaded_addresses),
+        )?;
+        Ok(message
+            .decompile_instructions()
+            .into_iter()
+            .map(|instruction| Instruction {
+                program_id: *instruction.program_id,
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|meta| AccountMeta {
+                        pubkey: *meta.pubkey,
+                        is_signer: meta.is_signer,
+                        is_writable: meta.is_writable,
+                    })
+                    .collect(),
+                data: instruction.data.to_vec(),
+            })
+            .collect())
+    }
+
     /// Returns the version of the transaction
     pub fn version(&self) -> TransactionVersion {
         match self.message {
@@ -207,21 +273,73 @@ impl VersionedTransaction {
     }
 }
 
+/// Verify the signatures of many transactions, hashing the message of each
+/// transaction whose signatures are all valid.
+///
+/// The results are in the same order as `transactions`, and each is the same
+/// as [`VersionedTransaction::verify_and_hash_message`] would return. With the
+/// `rayon` feature the transactions are verified on rayon's global thread
+/// pool, otherwise, and always on wasm32, they are verified one at a time.
+pub fn verify_transactions_parallel(transactions: &[VersionedTransaction]) -> Vec<Result<Hash>> {
+    #[cfg(all(feature = "rayon", not(target_arch = "wasm32")))]
+    {
+        use rayon::prelude::*;
+        transactions
+            .par_iter()
+            .map(VersionedTransaction::verify_and_hash_message)
+            .collect()
+    }
+    #[cfg(not(all(feature = "rayon", not(target_arch = "wasm32"))))]
+    {
+        transactions
+            .iter()
+            .map(VersionedTransaction::verify_and_hash_message)
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {
         super::*,
         crate::{
-            message::Message as LegacyMessage,
+            message::{Message as LegacyMessage, MESSAGE_VERSION_PREFIX},
             signer::{keypair::Keypair, Signer},
             system_instruction,
         },
-        solana_program::{
-            instruction::{AccountMeta, Instruction},
-            pubkey::Pubkey,
-        },
+        solana_program::pubkey::Pubkey,
     };
 
+    #[test]
+    fn test_verify_transactions_parallel() {
+        let transactions: Vec<_> = (0..64u64)
+            .map(|i| {
+                let keypair = Keypair::new();
+                let message = VersionedMessage::Legacy(LegacyMessage::new(
+                    &[system_instruction::transfer(
+                        &keypair.pubkey(),
+                        &Pubkey::new_unique(),
+                        i,
+                    )],
+                    Some(&keypair.pubkey()),
+                ));
+                let mut tx = VersionedTransaction::try_new(message, &[&keypair]).unwrap();
+                if i % 3 == 0 {
+                    tx.signatures[0] = Signature::default();
+                }
+                tx
+            })
+            .collect();
+
+        let results = verify_transactions_parallel(&transactions);
+        assert_eq!(results.len(), transactions.len());
+        for (i, (tx, result)) in transactions.iter().zip(results).enumerate() {
+            assert_eq!(result, tx.verify_and_hash_message());
+            assert_eq!(result.is_ok(), i % 3 != 0);
+        }
+        assert!(verify_transactions_parallel(&[]).is_empty());
+    }
+
     #[test]
     fn test_try_new() {
         let keypair0 = Keypair::new();
@@ -339,4 +457,78 @@ mod tests {
         let tx = VersionedTransaction::from(tx);
         assert!(!tx.uses_durable_nonce());
     }
+
+    #[test]
+    fn test_deserialize_v0_wire_bytes() {
+        let mut bytes = vec![1u8]; // signature count
+        bytes.extend_from_slice(&[1; 64]);
+        bytes.push(MESSAGE_VERSION_PREFIX);
+        bytes.extend_from_slice(&[1, 0, 1]); // header
+        bytes.push(2); // static account keys
+        bytes.extend_from_slice(&[2; 32]);
+        bytes.extend_from_slice(&[3; 32]);
+        bytes.extend_from_slice(&[4; 32]); // recent blockhash
+        bytes.push(1); // instructions
+        bytes.extend_from_slice(&[1, 2, 0, 2, 3, 9, 8, 7]);
+        bytes.push(1); // address table lookups
+        bytes.extend_from_slice(&[5; 32]);
+        bytes.extend_from_slice(&[1, 7, 0]);
+
+        let tx = VersionedTransaction::deserialize(&bytes).unwrap();
+        assert_eq!(tx.signatures, vec![Signature::from([1; 64])]);
+        assert_eq!(tx.version(), TransactionVersion::Number(0));
+        assert_eq!(
+            tx.message.recent_blockhash(),
+            &Hash::new_from_array([4; 32])
+        );
+        assert_eq!(bincode::serialize(&tx).unwrap(), bytes);
+
+        let loaded_addresses = LoadedAddresses {
+            writable: vec![Pubkey::from([6; 32])],
+            readonly: vec![],
+        };
+        assert_eq!(
+            tx.resolve_instructions(loaded_addresses).unwrap(),
+            vec![Instruction::new_with_bytes(
+                Pubkey::from([3; 32]),
+                &[9, 8, 7],
+                vec![
+                    AccountMeta::new(Pubkey::from([2; 32]), true),
+                    AccountMeta::new(Pubkey::from([6; 32]), false),
+                ],
+            )]
+        );
+        assert_eq!(
+            tx.resolve_instructions(LoadedAddresses::default()),
+            Err(TransactionError::InvalidAddressLookupTableIndex)
+        );
+
+        bytes.push(0);
+        assert!(VersionedTransaction::deserialize(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_legacy_wire_bytes() {
+        let keypair = Keypair::new();
+        let to = Pubkey::new_unique();
+        let instruction = system_instruction::transfer(&keypair.pubkey(), &to, 42);
+        let tx = Transaction::new(
+            &[&keypair],
+            LegacyMessage::new(&[instruction.clone()], Some(&keypair.pubkey())),
+            Hash::new_unique(),
+        );
+        let bytes = bincode::serialize(&tx).unwrap();
+
+        let versioned_tx = VersionedTransaction::deserialize(&bytes).unwrap();
+        assert_eq!(versioned_tx.version(), TransactionVersion::LEGACY);
+        assert_eq!(versioned_tx, VersionedTransaction::from(tx));
+        assert_eq!(
+            versioned_tx
+                .resolve_instructions(LoadedAddresses::default())
+                .unwrap(),
+            vec![instruction]
+        );
+
+        assert!(VersionedTransaction::deserialize(&[0; PACKET_DATA_SIZE + 1]).is_err());
+    }
 }
@@ -1,8 +1,18 @@
 //! `Keypair` Javascript interface
-#![cfg(target_arch = "wasm32")]
+#![cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
 #![allow(non_snake_case)]
 use {
-    crate::signer::{keypair::Keypair, Signer},
+    crate::{
+        derivation_path::DerivationPath,
+        signer::{
+            keypair::{
+                generate_seed_from_seed_phrase_and_passphrase,
+                keypair_from_seed_and_derivation_path, keypair_from_seed_phrase_and_passphrase,
+                Keypair,
+            },
+            Signer,
+        },
+    },
     solana_program::{pubkey::Pubkey, wasm::display_to_jsvalue},
     wasm_bindgen::prelude::*,
 };
@@ -25,6 +35,25 @@ impl Keypair {
         Keypair::from_bytes(bytes).map_err(display_to_jsvalue)
     }
 
+    /// Recover a `Keypair` from a BIP39 seed phrase and passphrase
+    pub fn fromSeedPhrase(seed_phrase: &str, passphrase: &str) -> Result<Keypair, JsValue> {
+        keypair_from_seed_phrase_and_passphrase(seed_phrase, passphrase).map_err(display_to_jsvalue)
+    }
+
+    /// Derive a `Keypair` from a BIP39 seed phrase and passphrase along a
+    /// BIP44 derivation path, such as `m/44'/501'/0'/0'`
+    pub fn fromSeedPhraseAndDerivationPath(
+        seed_phrase: &str,
+        passphrase: &str,
+        derivation_path: &str,
+    ) -> Result<Keypair, JsValue> {
+        let derivation_path =
+            DerivationPath::from_absolute_path_str(derivation_path).map_err(display_to_jsvalue)?;
+        let seed = generate_seed_from_seed_phrase_and_passphrase(seed_phrase, passphrase);
+        keypair_from_seed_and_derivation_path(&seed, Some(derivation_path))
+            .map_err(display_to_jsvalue)
+    }
+
     /// Return the `Pubkey` for this `Keypair`
     #[wasm_bindgen(js_name = pubkey)]
     pub fn js_pubkey(&self) -> Pubkey {
@@ -1,5 +1,7 @@
 //! solana-sdk Javascript interface
-#![cfg(target_arch = "wasm32")]
+#![cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
 
 pub mod keypair;
+pub mod offchain_message;
+pub mod rpc;
 pub mod transaction;
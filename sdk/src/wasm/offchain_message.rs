@@ -0,0 +1,52 @@
+//! Message signing Javascript interface
+//!
+//! Builds the byte payloads passed to a wallet's `signMessage` and verifies
+//! the signatures it returns.
+#![cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
+#![allow(non_snake_case)]
+use {
+    crate::{offchain_message::OffchainMessage, signature::Signature},
+    solana_program::{pubkey::Pubkey, wasm::display_to_jsvalue},
+    wasm_bindgen::prelude::*,
+};
+
+/// Encode a plain text message as the UTF-8 bytes a wallet signs
+#[wasm_bindgen]
+pub fn signMessagePayload(message: &str) -> Box<[u8]> {
+    message.as_bytes().into()
+}
+
+/// Wrap `message` in the off-chain message envelope of the given version,
+/// returning the serialized bytes a wallet signs
+#[wasm_bindgen]
+pub fn offchainMessagePayload(version: u8, message: &[u8]) -> Result<Box<[u8]>, JsValue> {
+    OffchainMessage::new(version, message)
+        .and_then(|message| message.serialize())
+        .map(Vec::into_boxed_slice)
+        .map_err(display_to_jsvalue)
+}
+
+/// Check that `signature` is `pubkey`'s signature over the raw `message` bytes
+#[wasm_bindgen]
+pub fn verifyMessageSignature(
+    pubkey: &Pubkey,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool, JsValue> {
+    let signature = Signature::try_from(signature).map_err(display_to_jsvalue)?;
+    Ok(signature.verify(pubkey.as_ref(), message))
+}
+
+/// Check that `signature` is `pubkey`'s signature over a serialized off-chain
+/// message, failing if `payload` is not a well-formed envelope
+#[wasm_bindgen]
+pub fn verifyOffchainMessageSignature(
+    pubkey: &Pubkey,
+    payload: &[u8],
+    signature: &[u8],
+) -> Result<bool, JsValue> {
+    let signature = Signature::try_from(signature).map_err(display_to_jsvalue)?;
+    OffchainMessage::deserialize(payload)
+        .and_then(|message| message.verify(pubkey, &signature))
+        .map_err(display_to_jsvalue)
+}
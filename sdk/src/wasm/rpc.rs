@@ -0,0 +1,170 @@
+//! `RpcClient` Javascript interface
+//!
+//! A minimal JSON-RPC client built on the browser `fetch` API and the types in
+//! [`crate::rpc_types`], enough to fetch a blockhash and accounts and submit a
+//! signed transaction.
+#![cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
+#![allow(non_snake_case)]
+use {
+    crate::{
+        account::Account,
+        commitment_config::CommitmentConfig,
+        hash::Hash,
+        rpc_types::{
+            RpcAccount, RpcBlockhash, RpcContextResult, RpcRequest, RpcResponse,
+            RpcSendTransactionConfig,
+        },
+        transaction::Transaction,
+    },
+    js_sys::{BigInt, Object, Promise, Reflect, Uint8Array},
+    serde::de::DeserializeOwned,
+    serde_json::Value,
+    solana_program::{pubkey::Pubkey, wasm::display_to_jsvalue},
+    std::{cell::Cell, rc::Rc, str::FromStr},
+    wasm_bindgen::{prelude::*, JsCast},
+    wasm_bindgen_futures::{future_to_promise, JsFuture},
+    web_sys::{Request, RequestInit, Response, Window, WorkerGlobalScope},
+};
+
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct RpcClient {
+    url: Rc<str>,
+    commitment: CommitmentConfig,
+    next_id: Rc<Cell<u64>>,
+}
+
+impl RpcClient {
+    pub fn new(url: &str, commitment: CommitmentConfig) -> Self {
+        Self {
+            url: url.into(),
+            commitment,
+            next_id: Rc::new(Cell::new(1)),
+        }
+    }
+
+    fn next_request_id(&self) -> u64 {
+        let id = self.next_id.get();
+        self.next_id.set(id.wrapping_add(1));
+        id
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: Value) -> Result<T, JsValue> {
+        let mut init = RequestInit::new();
+        init.method("POST");
+        init.body(Some(&JsValue::from_str(&request.to_string())));
+        let request = Request::new_with_str_and_init(&self.url, &init)?;
+        request.headers().set("Content-Type", "application/json")?;
+
+        let global = js_sys::global();
+        let response = if let Some(window) = global.dyn_ref::<Window>() {
+            window.fetch_with_request(&request)
+        } else if let Some(worker) = global.dyn_ref::<WorkerGlobalScope>() {
+            worker.fetch_with_request(&request)
+        } else {
+            return Err(JsValue::from_str("fetch is not available"));
+        };
+        let response: Response = JsFuture::from(response).await?.dyn_into()?;
+        let body = JsFuture::from(response.array_buffer()?).await?;
+        RpcResponse::<T>::parse(&Uint8Array::new(&body).to_vec()).map_err(display_to_jsvalue)
+    }
+
+    /// Fetch the latest blockhash to use for a new transaction.
+    pub async fn get_latest_blockhash(&self) -> Result<Hash, JsValue> {
+        let request = RpcRequest::get_latest_blockhash(self.next_request_id(), self.commitment);
+        let result: RpcContextResult<RpcBlockhash> = self.send(request).await?;
+        result.value.hash().map_err(display_to_jsvalue)
+    }
+
+    /// Fetch an account, returning `None` if it does not exist.
+    pub async fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>, JsValue> {
+        let request = RpcRequest::get_account_info(self.next_request_id(), pubkey, self.commitment);
+        let result: RpcContextResult<Option<RpcAccount>> = self.send(request).await?;
+        result
+            .value
+            .map(|account| account.decode())
+            .transpose()
+            .map_err(display_to_jsvalue)
+    }
+
+    /// Submit a signed transaction, returning its base58 encoded signature.
+    pub async fn send_transaction(&self, transaction: &Transaction) -> Result<String, JsValue> {
+        let serialized = bincode::serialize(transaction).map_err(display_to_jsvalue)?;
+        let config = RpcSendTransactionConfig {
+            preflight_commitment: Some(self.commitment.commitment),
+            ..RpcSendTransactionConfig::default()
+        };
+        let request = RpcRequest::send_transaction(self.next_request_id(), &serialized, config);
+        self.send(request).await
+    }
+}
+
+fn account_to_jsvalue(account: Account) -> Result<JsValue, JsValue> {
+    let object = Object::new();
+    Reflect::set(
+        &object,
+        &"lamports".into(),
+        &BigInt::from(account.lamports).into(),
+    )?;
+    Reflect::set(
+        &object,
+        &"data".into(),
+        &Uint8Array::from(account.data.as_slice()).into(),
+    )?;
+    Reflect::set(&object, &"owner".into(), &account.owner.into())?;
+    Reflect::set(&object, &"executable".into(), &account.executable.into())?;
+    Reflect::set(
+        &object,
+        &"rentEpoch".into(),
+        &BigInt::from(account.rent_epoch).into(),
+    )?;
+    Ok(object.into())
+}
+
+#[wasm_bindgen]
+impl RpcClient {
+    /// Create a new `RpcClient` for the JSON-RPC endpoint at `url`
+    ///
+    /// * `commitment` - optional commitment level, `finalized` by default
+    #[wasm_bindgen(constructor)]
+    pub fn constructor(url: String, commitment: Option<String>) -> Result<RpcClient, JsValue> {
+        let commitment = commitment
+            .as_deref()
+            .map(CommitmentConfig::from_str)
+            .transpose()
+            .map_err(display_to_jsvalue)?
+            .unwrap_or_default();
+        Ok(Self::new(&url, commitment))
+    }
+
+    /// Resolves to the latest `Hash`
+    pub fn getLatestBlockhash(&self) -> Promise {
+        let client = self.clone();
+        future_to_promise(async move { client.get_latest_blockhash().await.map(JsValue::from) })
+    }
+
+    /// Resolves to `{ lamports, data, owner, executable, rentEpoch }`, or
+    /// `null` if the account does not exist
+    pub fn getAccountInfo(&self, pubkey: &Pubkey) -> Promise {
+        let client = self.clone();
+        let pubkey = *pubkey;
+        future_to_promise(async move {
+            match client.get_account(&pubkey).await? {
+                Some(account) => account_to_jsvalue(account),
+                None => Ok(JsValue::NULL),
+            }
+        })
+    }
+
+    /// Resolves to the signature of the submitted transaction
+    pub fn sendTransaction(&self, transaction: &Transaction) -> Promise {
+        let client = self.clone();
+        let transaction = transaction.clone();
+        future_to_promise(async move {
+            client
+                .send_transaction(&transaction)
+                .await
+                .map(JsValue::from)
+        })
+    }
+}
@@ -1,8 +1,11 @@
 //! `Transaction` Javascript interface
-#![cfg(target_arch = "wasm32")]
+#![cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
 #![allow(non_snake_case)]
 use {
-    crate::{hash::Hash, message::Message, signer::keypair::Keypair, transaction::Transaction},
+    crate::{
+        hash::Hash, message::Message, signature::Signature, signer::keypair::Keypair,
+        transaction::Transaction,
+    },
     solana_program::{
         pubkey::Pubkey,
         wasm::{display_to_jsvalue, instructions::Instructions},
@@ -44,6 +47,22 @@ impl Transaction {
         self.is_signed()
     }
 
+    /// Add a co-signer's signature of `messageData()`
+    pub fn addSignature(&mut self, pubkey: &Pubkey, signature: &[u8]) -> Result<(), JsValue> {
+        let signature = Signature::try_from(signature).map_err(display_to_jsvalue)?;
+        self.add_signature(pubkey, signature)
+            .map_err(display_to_jsvalue)
+    }
+
+    /// The `Pubkey`s of the required signers whose signatures are absent or
+    /// don't verify
+    pub fn missingSigners(&self) -> js_sys::Array {
+        self.missing_signers()
+            .into_iter()
+            .map(JsValue::from)
+            .collect()
+    }
+
     pub fn toBytes(&self) -> Box<[u8]> {
         bincode::serialize(self).unwrap().into()
     }
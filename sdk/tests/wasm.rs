@@ -0,0 +1,51 @@
+//! Tests for the Javascript interface.
+//!
+//! Run with `npm run test:wasm` under node, or `npm run test:wasm:browser` in
+//! headless browsers.
+#![cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
+
+use {
+    solana_sdk::{
+        offchain_message::OffchainMessage,
+        signature::{Keypair, Signer},
+        wasm::offchain_message::{
+            offchainMessagePayload, signMessagePayload, verifyMessageSignature,
+            verifyOffchainMessageSignature,
+        },
+    },
+    wasm_bindgen_test::*,
+};
+
+#[wasm_bindgen_test]
+fn sign_message_round_trip() {
+    let keypair = Keypair::new();
+    let payload = signMessagePayload("hello world");
+    assert_eq!(&payload[..], b"hello world");
+
+    let signature = keypair.sign_message(&payload);
+    assert!(verifyMessageSignature(&keypair.pubkey(), &payload, signature.as_ref()).unwrap());
+    assert!(!verifyMessageSignature(&keypair.pubkey(), b"hello", signature.as_ref()).unwrap());
+    assert!(verifyMessageSignature(&keypair.pubkey(), &payload, &[0; 63]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn offchain_message_round_trip() {
+    let keypair = Keypair::new();
+    let payload = offchainMessagePayload(0, b"hello world").unwrap();
+    let expected = OffchainMessage::new(0, b"hello world").unwrap();
+    assert_eq!(&payload[..], &expected.serialize().unwrap()[..]);
+
+    let signature = expected.sign(&keypair).unwrap();
+    assert!(
+        verifyOffchainMessageSignature(&keypair.pubkey(), &payload, signature.as_ref()).unwrap()
+    );
+    let other = Keypair::new();
+    assert!(
+        !verifyOffchainMessageSignature(&other.pubkey(), &payload, signature.as_ref()).unwrap()
+    );
+    assert!(
+        verifyOffchainMessageSignature(&keypair.pubkey(), b"hello", signature.as_ref()).is_err()
+    );
+
+    assert!(offchainMessagePayload(1, b"hello world").is_err());
+}
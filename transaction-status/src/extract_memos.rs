@@ -9,20 +9,18 @@ use {
 
 // A helper function to convert spl_memo::v1::id() as spl_sdk::pubkey::Pubkey to
 // solana_sdk::pubkey::Pubkey
-pub fn spl_memo_id_v1() -> Pubkey {
-    *MEMO_PROGRAM_ID_V1
+pub const fn spl_memo_id_v1() -> Pubkey {
+    Pubkey::new_from_array(spl_memo::v1::ID.to_bytes())
 }
 
 // A helper function to convert spl_memo::id() as spl_sdk::pubkey::Pubkey to
 // solana_sdk::pubkey::Pubkey
-pub fn spl_memo_id_v3() -> Pubkey {
-    *MEMO_PROGRAM_ID_V3
+pub const fn spl_memo_id_v3() -> Pubkey {
+    Pubkey::new_from_array(spl_memo::ID.to_bytes())
 }
 
-lazy_static! {
-    static ref MEMO_PROGRAM_ID_V1: Pubkey = Pubkey::new_from_array(spl_memo::v1::id().to_bytes());
-    static ref MEMO_PROGRAM_ID_V3: Pubkey = Pubkey::new_from_array(spl_memo::id().to_bytes());
-}
+const MEMO_PROGRAM_ID_V1: Pubkey = spl_memo_id_v1();
+const MEMO_PROGRAM_ID_V3: Pubkey = spl_memo_id_v3();
 
 pub fn extract_and_fmt_memos<T: ExtractMemos>(message: &T) -> Option<String> {
     let memos = message.extract_memos();
@@ -87,7 +85,7 @@ fn extract_memos_inner(
                 KeyType::MemoProgram => Some(&ix.data),
                 KeyType::OtherProgram => None,
                 KeyType::Unknown(program_id) => {
-                    if **program_id == *MEMO_PROGRAM_ID_V1 || **program_id == *MEMO_PROGRAM_ID_V3 {
+                    if **program_id == MEMO_PROGRAM_ID_V1 || **program_id == MEMO_PROGRAM_ID_V3 {
                         account_keys[index] = KeyType::MemoProgram;
                         Some(&ix.data)
                     } else {
@@ -10,8 +10,8 @@ use {
 
 // A helper function to convert spl_associated_token_account::id() as spl_sdk::pubkey::Pubkey
 // to solana_sdk::pubkey::Pubkey
-pub fn spl_associated_token_id() -> Pubkey {
-    Pubkey::new_from_array(spl_associated_token_account::id().to_bytes())
+pub const fn spl_associated_token_id() -> Pubkey {
+    Pubkey::new_from_array(spl_associated_token_account::ID.to_bytes())
 }
 
 pub fn parse_associated_token(
@@ -0,0 +1,128 @@
+use {
+    crate::parse_instruction::{
+        check_num_accounts, ParsableProgram, ParseInstructionError, ParsedInstructionEnum,
+    },
+    base64::{prelude::BASE64_STANDARD, Engine},
+    bincode::{deserialize, serialized_size},
+    serde_json::json,
+    solana_sdk::{
+        instruction::CompiledInstruction, message::AccountKeys, pubkey::Pubkey, short_vec,
+    },
+};
+
+// Mirrors `solana_config_program::ConfigKeys`, which prefixes the instruction
+// data of every config program instruction.
+#[derive(Deserialize, Serialize)]
+struct ConfigKeys {
+    #[serde(with = "short_vec")]
+    keys: Vec<(Pubkey, bool)>,
+}
+
+pub fn parse_config(
+    instruction: &CompiledInstruction,
+    account_keys: &AccountKeys,
+) -> Result<ParsedInstructionEnum, ParseInstructionError> {
+    let config_keys: ConfigKeys = deserialize(&instruction.data)
+        .map_err(|_| ParseInstructionError::InstructionNotParsable(ParsableProgram::Config))?;
+    let data_offset = serialized_size(&config_keys)
+        .map_err(|_| ParseInstructionError::InstructionNotParsable(ParsableProgram::Config))?
+        as usize;
+    match instruction.accounts.iter().max() {
+        Some(index) if (*index as usize) < account_keys.len() => {}
+        _ => {
+            // Runtime should prevent this from ever happening
+            return Err(ParseInstructionError::InstructionKeyMismatch(
+                ParsableProgram::Config,
+            ));
+        }
+    }
+    check_num_accounts(&instruction.accounts, 1, ParsableProgram::Config)?;
+    let keys: Vec<_> = config_keys
+        .keys
+        .iter()
+        .map(|(pubkey, signer)| {
+            json!({
+                "pubkey": pubkey.to_string(),
+                "signer": signer,
+            })
+        })
+        .collect();
+    let signers: Vec<_> = instruction.accounts[1..]
+        .iter()
+        .map(|index| account_keys[*index as usize].to_string())
+        .collect();
+    Ok(ParsedInstructionEnum {
+        instruction_type: "store".to_string(),
+        info: json!({
+            "configAccount": account_keys[instruction.accounts[0] as usize].to_string(),
+            "keys": keys,
+            "signers": signers,
+            "data": BASE64_STANDARD.encode(&instruction.data[data_offset..]),
+        }),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        solana_sdk::{
+            config,
+            instruction::{AccountMeta, Instruction},
+            message::Message,
+        },
+    };
+
+    #[test]
+    fn test_parse_config_store_ix() {
+        let config_pubkey = Pubkey::new_unique();
+        let signer_pubkey = Pubkey::new_unique();
+        let other_pubkey = Pubkey::new_unique();
+        let keys = vec![(signer_pubkey, true), (other_pubkey, false)];
+        let instruction = Instruction::new_with_bincode(
+            config::program::id(),
+            &(ConfigKeys { keys }, 42u64),
+            vec![
+                AccountMeta::new(config_pubkey, false),
+                AccountMeta::new_readonly(signer_pubkey, true),
+            ],
+        );
+        let mut message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_config(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "store".to_string(),
+                info: json!({
+                    "configAccount": config_pubkey.to_string(),
+                    "keys": [
+                        {"pubkey": signer_pubkey.to_string(), "signer": true},
+                        {"pubkey": other_pubkey.to_string(), "signer": false},
+                    ],
+                    "signers": [signer_pubkey.to_string()],
+                    "data": BASE64_STANDARD.encode(42u64.to_le_bytes()),
+                }),
+            }
+        );
+        assert!(parse_config(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys[0..1], None)
+        )
+        .is_err());
+        message.instructions[0].accounts.clear();
+        assert!(parse_config(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys, None)
+        )
+        .is_err());
+        message.instructions[0].data = vec![0xff];
+        assert!(parse_config(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys, None)
+        )
+        .is_err());
+    }
+}
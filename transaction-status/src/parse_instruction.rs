@@ -4,6 +4,7 @@ use {
         parse_address_lookup_table::parse_address_lookup_table,
         parse_associated_token::{parse_associated_token, spl_associated_token_id},
         parse_bpf_loader::{parse_bpf_loader, parse_bpf_upgradeable_loader},
+        parse_config::parse_config,
         parse_stake::parse_stake,
         parse_system::parse_system,
         parse_token::parse_token,
@@ -13,7 +14,7 @@ use {
     serde_json::Value,
     solana_account_decoder::parse_token::spl_token_ids,
     solana_sdk::{
-        address_lookup_table, instruction::CompiledInstruction, message::AccountKeys,
+        address_lookup_table, config, instruction::CompiledInstruction, message::AccountKeys,
         pubkey::Pubkey, stake, system_program, vote,
     },
     std::{
@@ -23,39 +24,42 @@ use {
     thiserror::Error,
 };
 
+const ADDRESS_LOOKUP_PROGRAM_ID: Pubkey = address_lookup_table::program::ID;
+const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = spl_associated_token_id();
+const BPF_LOADER_PROGRAM_ID: Pubkey = solana_sdk::bpf_loader::ID;
+const BPF_UPGRADEABLE_LOADER_PROGRAM_ID: Pubkey = solana_sdk::bpf_loader_upgradeable::ID;
+const CONFIG_PROGRAM_ID: Pubkey = config::program::ID;
+const MEMO_V1_PROGRAM_ID: Pubkey = spl_memo_id_v1();
+const MEMO_V3_PROGRAM_ID: Pubkey = spl_memo_id_v3();
+const STAKE_PROGRAM_ID: Pubkey = stake::program::ID;
+const SYSTEM_PROGRAM_ID: Pubkey = system_program::ID;
+const VOTE_PROGRAM_ID: Pubkey = vote::program::ID;
+
 lazy_static! {
-    static ref ADDRESS_LOOKUP_PROGRAM_ID: Pubkey = address_lookup_table::program::id();
-    static ref ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = spl_associated_token_id();
-    static ref BPF_LOADER_PROGRAM_ID: Pubkey = solana_sdk::bpf_loader::id();
-    static ref BPF_UPGRADEABLE_LOADER_PROGRAM_ID: Pubkey = solana_sdk::bpf_loader_upgradeable::id();
-    static ref MEMO_V1_PROGRAM_ID: Pubkey = spl_memo_id_v1();
-    static ref MEMO_V3_PROGRAM_ID: Pubkey = spl_memo_id_v3();
-    static ref STAKE_PROGRAM_ID: Pubkey = stake::program::id();
-    static ref SYSTEM_PROGRAM_ID: Pubkey = system_program::id();
-    static ref VOTE_PROGRAM_ID: Pubkey = vote::program::id();
     static ref PARSABLE_PROGRAM_IDS: HashMap<Pubkey, ParsableProgram> = {
         let mut m = HashMap::new();
         m.insert(
-            *ADDRESS_LOOKUP_PROGRAM_ID,
+            ADDRESS_LOOKUP_PROGRAM_ID,
             ParsableProgram::AddressLookupTable,
         );
         m.insert(
-            *ASSOCIATED_TOKEN_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
             ParsableProgram::SplAssociatedTokenAccount,
         );
-        m.insert(*MEMO_V1_PROGRAM_ID, ParsableProgram::SplMemo);
-        m.insert(*MEMO_V3_PROGRAM_ID, ParsableProgram::SplMemo);
+        m.insert(MEMO_V1_PROGRAM_ID, ParsableProgram::SplMemo);
+        m.insert(MEMO_V3_PROGRAM_ID, ParsableProgram::SplMemo);
         for spl_token_id in spl_token_ids() {
             m.insert(spl_token_id, ParsableProgram::SplToken);
         }
-        m.insert(*BPF_LOADER_PROGRAM_ID, ParsableProgram::BpfLoader);
+        m.insert(BPF_LOADER_PROGRAM_ID, ParsableProgram::BpfLoader);
         m.insert(
-            *BPF_UPGRADEABLE_LOADER_PROGRAM_ID,
+            BPF_UPGRADEABLE_LOADER_PROGRAM_ID,
             ParsableProgram::BpfUpgradeableLoader,
         );
-        m.insert(*STAKE_PROGRAM_ID, ParsableProgram::Stake);
-        m.insert(*SYSTEM_PROGRAM_ID, ParsableProgram::System);
-        m.insert(*VOTE_PROGRAM_ID, ParsableProgram::Vote);
+        m.insert(CONFIG_PROGRAM_ID, ParsableProgram::Config);
+        m.insert(STAKE_PROGRAM_ID, ParsableProgram::Stake);
+        m.insert(SYSTEM_PROGRAM_ID, ParsableProgram::System);
+        m.insert(VOTE_PROGRAM_ID, ParsableProgram::Vote);
         m
     };
 }
@@ -102,6 +106,7 @@ pub enum ParsableProgram {
     SplToken,
     BpfLoader,
     BpfUpgradeableLoader,
+    Config,
     Stake,
     System,
     Vote,
@@ -131,6 +136,7 @@ pub fn parse(
         ParsableProgram::BpfUpgradeableLoader => {
             serde_json::to_value(parse_bpf_upgradeable_loader(instruction, account_keys)?)?
         }
+        ParsableProgram::Config => serde_json::to_value(parse_config(instruction, account_keys)?)?,
         ParsableProgram::Stake => serde_json::to_value(parse_stake(instruction, account_keys)?)?,
         ParsableProgram::System => serde_json::to_value(parse_system(instruction, account_keys)?)?,
         ParsableProgram::Vote => serde_json::to_value(parse_vote(instruction, account_keys)?)?,
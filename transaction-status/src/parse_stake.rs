@@ -26,8 +26,13 @@ pub fn parse_stake(
         }
     }
     match stake_instruction {
-        StakeInstruction::Initialize(authorized, lockup) => {
+        StakeInstruction::Initialize(authorized, lockup)
+        | StakeInstruction::InitializeIfNeeded(authorized, lockup) => {
             check_num_stake_accounts(&instruction.accounts, 2)?;
+            let instruction_type = match stake_instruction {
+                StakeInstruction::Initialize(..) => "initialize",
+                _ => "initializeIfNeeded",
+            };
             let authorized = json!({
                 "staker": authorized.staker.to_string(),
                 "withdrawer": authorized.withdrawer.to_string(),
@@ -38,7 +43,7 @@ pub fn parse_stake(
                 "custodian": lockup.custodian.to_string(),
             });
             Ok(ParsedInstructionEnum {
-                instruction_type: "initialize".to_string(),
+                instruction_type: instruction_type.to_string(),
                 info: json!({
                     "stakeAccount": account_keys[instruction.accounts[0] as usize].to_string(),
                     "rentSysvar": account_keys[instruction.accounts[1] as usize].to_string(),
@@ -148,6 +153,17 @@ pub fn parse_stake(
                 }),
             })
         }
+        StakeInstruction::UpdateCustodian(new_custodian) => {
+            check_num_stake_accounts(&instruction.accounts, 2)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "updateCustodian".to_string(),
+                info: json!({
+                    "stakeAccount": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "custodian": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "newCustodian": new_custodian.to_string(),
+                }),
+            })
+        }
         StakeInstruction::Merge => {
             check_num_stake_accounts(&instruction.accounts, 5)?;
             Ok(ParsedInstructionEnum {
@@ -297,6 +313,18 @@ pub fn parse_stake(
                 }),
             })
         }
+        StakeInstruction::ReplenishRewardsPool(lamports) => {
+            check_num_stake_accounts(&instruction.accounts, 3)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "replenishRewardsPool".to_string(),
+                info: json!({
+                    "rewardsPool": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "funder": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "systemProgram": account_keys[instruction.accounts[2] as usize].to_string(),
+                    "lamports": lamports,
+                }),
+            })
+        }
     }
 }
 
@@ -316,7 +344,7 @@ mod test {
                 instruction::{self, LockupArgs},
                 state::{Authorized, Lockup, StakeAuthorize},
             },
-            sysvar,
+            system_program, sysvar,
         },
         std::iter::repeat_with,
     };
@@ -377,6 +405,112 @@ mod test {
         assert!(parse_stake(&message.instructions[0], &AccountKeys::new(&keys, None)).is_err());
     }
 
+    #[test]
+    fn test_parse_stake_update_custodian_ix() {
+        let stake_pubkey = Pubkey::new_unique();
+        let custodian = Pubkey::new_unique();
+        let new_custodian = Pubkey::new_unique();
+
+        let instruction = instruction::update_custodian(&stake_pubkey, &custodian, &new_custodian);
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_stake(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "updateCustodian".to_string(),
+                info: json!({
+                    "stakeAccount": stake_pubkey.to_string(),
+                    "custodian": custodian.to_string(),
+                    "newCustodian": new_custodian.to_string(),
+                }),
+            }
+        );
+        assert!(parse_stake(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys[0..1], None)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_stake_replenish_rewards_pool_ix() {
+        let pool_pubkey = Pubkey::new_unique();
+        let funder = Pubkey::new_unique();
+        let lamports = 55;
+
+        let instruction = instruction::replenish_rewards_pool(&pool_pubkey, &funder, lamports);
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_stake(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "replenishRewardsPool".to_string(),
+                info: json!({
+                    "rewardsPool": pool_pubkey.to_string(),
+                    "funder": funder.to_string(),
+                    "systemProgram": system_program::id().to_string(),
+                    "lamports": lamports,
+                }),
+            }
+        );
+        assert!(parse_stake(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys[0..2], None)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_stake_initialize_if_needed_ix() {
+        let stake_pubkey = Pubkey::new_unique();
+        let authorized = Authorized {
+            staker: Pubkey::new_unique(),
+            withdrawer: Pubkey::new_unique(),
+        };
+        let lockup = Lockup {
+            unix_timestamp: 1_234_567_890,
+            epoch: 11,
+            custodian: Pubkey::new_unique(),
+        };
+
+        let instruction = instruction::initialize_if_needed(&stake_pubkey, &authorized, &lockup);
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_stake(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "initializeIfNeeded".to_string(),
+                info: json!({
+                    "stakeAccount": stake_pubkey.to_string(),
+                    "rentSysvar": sysvar::rent::ID.to_string(),
+                    "authorized": {
+                        "staker": authorized.staker.to_string(),
+                        "withdrawer": authorized.withdrawer.to_string(),
+                    },
+                    "lockup": {
+                        "unixTimestamp": lockup.unix_timestamp,
+                        "epoch": lockup.epoch,
+                        "custodian": lockup.custodian.to_string(),
+                    }
+                }),
+            }
+        );
+        assert!(parse_stake(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys[0..1], None)
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_parse_stake_authorize_ix() {
         let stake_pubkey = Pubkey::new_unique();